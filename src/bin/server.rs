@@ -0,0 +1,72 @@
+//! Hosts online rooms, clients connect with `card_game --server ws://<address>`
+//! then create a room or join one by its code.
+//!
+//! ```sh
+//! cargo run --bin server -- --address 0.0.0.0:7878 --grace-period 60
+//! ```
+//!
+//! Disconnected players get their seat back when reconnecting within the grace period in seconds,
+//! a bot plays their turns meanwhile.
+//! Spectators asking for the god view see every hand `--god-view-delay` seconds late.
+use std::{process::exit, time::Duration};
+
+use bevy::{app::ScheduleRunnerPlugin, log::LogPlugin, prelude::*};
+
+use card_game::features::network::{
+    server::{GracePeriod, Server, ServerPlugin},
+    DEFAULT_PORT, POLL_INTERVAL,
+};
+
+fn main() {
+    let args = std::env::args().collect::<Vec<_>>();
+    let arg = |name: &str| {
+        args.iter()
+            .position(|arg| arg == name)
+            .and_then(|index| args.get(index + 1))
+    };
+    let address = arg("--address")
+        .cloned()
+        .unwrap_or_else(|| format!("0.0.0.0:{}", DEFAULT_PORT));
+    let mut grace_period = GracePeriod::default();
+    if let Some(seconds) = arg("--grace-period") {
+        let seconds = seconds.parse().unwrap_or_else(|error| {
+            eprintln!("Invalid grace period {:?}: {}", seconds, error);
+            exit(1);
+        });
+        grace_period.duration = Duration::from_secs(seconds);
+    }
+
+    let mut god_view_delay = None;
+    if let Some(seconds) = arg("--god-view-delay") {
+        let seconds = seconds.parse().unwrap_or_else(|error| {
+            eprintln!("Invalid god view delay {:?}: {}", seconds, error);
+            exit(1);
+        });
+        god_view_delay = Some(Duration::from_secs(seconds));
+    }
+
+    let server = Server::bind(&address)
+        .map(|server| server.with_grace_period(grace_period))
+        .map(|server| match god_view_delay {
+            Some(delay) => server.with_god_view_delay(delay),
+            None => server,
+        })
+        .unwrap_or_else(|error| {
+            eprintln!("Failed to listen on {}: {}", address, error);
+            exit(1);
+        });
+
+    App::new()
+        .add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_loop(POLL_INTERVAL)))
+        .add_plugins(LogPlugin::default())
+        .add_plugins(ServerPlugin)
+        .insert_resource(server)
+        .add_systems(Startup, log_address)
+        .run();
+}
+
+fn log_address(server: Res<Server>) {
+    if let Ok(address) = server.local_addr() {
+        info!("Listening on ws://{}", address);
+    }
+}
@@ -0,0 +1,166 @@
+//! Plays many games between bots without a window and prints statistics.
+//!
+//! ```sh
+//! cargo run --release --bin simulate -- --seats normal,hard,easy --seeds 0..1000 --format json
+//! ```
+//!
+//! `--record replays/` also writes the replay of every game to `replays/<seed>.ron`.
+//! The deck and house rules take the same arguments as the game, e.g. `--deck flip --stacking mixed`.
+use std::{fmt::Display, ops::Range, path::PathBuf, process::exit, str::FromStr};
+
+use card_game::features::{
+    ai::{Difficulty, SearchBudget},
+    deck::DeckRecipe,
+    lobby::{RoomSettings, MAX_SEATS, MIN_SEATS},
+    rules::HouseRules,
+    save::SaveFormat,
+    simulation::{record_game_with, GameRecord, SimulationReport},
+};
+
+const USAGE: &str = "usage: simulate [--seats normal,hard,...] [--seeds 0..1000] [--format csv|json] [--iterations 200] [--record directory]
+    [--deck classic|double|numbers|flip|party] [--hand-size 7] [--stacking off|same|mixed] [--draw one|until-playable]
+    [--jump-in] [--seven-o] [--forced-play]";
+
+enum Format {
+    Csv,
+    Json,
+}
+
+struct Args {
+    seats: Vec<Difficulty>,
+    seeds: Range<u64>,
+    format: Format,
+    budget: SearchBudget,
+    record: Option<PathBuf>,
+    deck: DeckRecipe,
+    rules: HouseRules,
+}
+
+fn parse<T: FromStr>(value: &str) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    value.parse().map_err(|error| format!("{}", error))
+}
+
+fn parse_seeds(seeds: &str) -> Result<Range<u64>, String> {
+    let (start, end) = seeds
+        .split_once("..")
+        .ok_or_else(|| format!("seeds {:?} should look like 0..1000", seeds))?;
+    let start = start.parse().map_err(|error| format!("{}", error))?;
+    let end = end.parse().map_err(|error| format!("{}", error))?;
+
+    Ok(start..end)
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        seats: vec![Difficulty::Normal; 4],
+        seeds: 0..1000,
+        format: Format::Csv,
+        budget: SearchBudget {
+            max_iterations: 200,
+            // Simulations must not depend on the machine speed
+            max_time: None,
+        },
+        record: None,
+        deck: DeckRecipe::default(),
+        rules: HouseRules::default(),
+    };
+    let mut arguments = std::env::args().skip(1);
+
+    while let Some(argument) = arguments.next() {
+        // House rules turned on without a value
+        let flag = match argument.as_str() {
+            "--jump-in" => Some(&mut args.rules.jump_in),
+            "--seven-o" => Some(&mut args.rules.seven_o),
+            "--forced-play" => Some(&mut args.rules.forced_play),
+            _ => None,
+        };
+        if let Some(flag) = flag {
+            *flag = true;
+            continue;
+        }
+
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--seats" => {
+                args.seats = value.split(',').map(str::parse).collect::<Result<_, _>>()?;
+                if !(MIN_SEATS..=MAX_SEATS).contains(&args.seats.len()) {
+                    return Err(format!("{} to {} seats are needed", MIN_SEATS, MAX_SEATS));
+                }
+            }
+            "--deck" => args.deck = parse(&value)?,
+            "--hand-size" => args.rules.hand_size = parse(&value)?,
+            "--stacking" => args.rules.stacking = parse(&value)?,
+            "--draw" => args.rules.draw = parse(&value)?,
+            "--seeds" => args.seeds = parse_seeds(&value)?,
+            "--format" => {
+                args.format = match value.as_str() {
+                    "csv" => Format::Csv,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format {:?}", value)),
+                }
+            }
+            "--iterations" => args.budget.max_iterations = parse(&value)?,
+            "--record" => args.record = Some(PathBuf::from(value)),
+            _ => return Err(format!("unknown argument {:?}", argument)),
+        }
+    }
+
+    // Every seat must get its hand with cards left for the discard pile
+    RoomSettings {
+        seats: args.seats.len(),
+        deck: args.deck,
+        rules: args.rules,
+        seed: None,
+    }
+    .check_cards()
+    .map_err(|error| error.to_string())?;
+
+    Ok(args)
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|error| {
+        eprintln!("{}\n{}", error, USAGE);
+        exit(1);
+    });
+
+    if let Some(directory) = &args.record {
+        std::fs::create_dir_all(directory).unwrap_or_else(|error| {
+            eprintln!("could not create {}: {}", directory.display(), error);
+            exit(1);
+        });
+    }
+
+    let mut report = SimulationReport::new(&args.seats);
+    for seed in args.seeds {
+        let replay = record_game_with(&args.seats, args.deck, args.rules, seed, &args.budget);
+        report.add(&GameRecord::from(&replay));
+
+        if let Some(directory) = &args.record {
+            let path = directory
+                .join(seed.to_string())
+                .with_extension(SaveFormat::Ron.extension());
+            let text = replay
+                .encode(SaveFormat::Ron)
+                .expect("replays are always serializable");
+            if let Err(error) = std::fs::write(&path, text) {
+                eprintln!("could not write {}: {}", path.display(), error);
+                exit(1);
+            }
+        }
+    }
+
+    match args.format {
+        Format::Csv => println!("{}", report.to_csv()),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&report).expect("reports are always serializable")
+        ),
+    }
+}
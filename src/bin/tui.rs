@@ -0,0 +1,93 @@
+//! Plays a game against bots in the terminal.
+//!
+//! ```sh
+//! cargo run --bin tui -- --bots normal,hard --seed 42
+//! ```
+use std::{
+    io::{self, BufRead, Write},
+    process::exit,
+};
+
+use card_game::features::{
+    ai::{self, Difficulty, SearchBudget},
+    lobby::MAX_SEATS,
+    rules::{GameState, Seat},
+    terminal::{describe_event, parse_move, render_table},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+const USAGE: &str = "usage: tui [--bots normal,hard,...] [--seed 42]";
+const HUMAN_SEAT: Seat = 0;
+
+fn parse_args() -> Result<(Vec<Difficulty>, u64), String> {
+    let mut bots = vec![Difficulty::Normal; 3];
+    let mut seed = rand::random();
+    let mut arguments = std::env::args().skip(1);
+
+    while let Some(argument) = arguments.next() {
+        let value = arguments
+            .next()
+            .ok_or_else(|| format!("missing value for {}", argument))?;
+
+        match argument.as_str() {
+            "--bots" => bots = value.split(',').map(str::parse).collect::<Result<_, _>>()?,
+            "--seed" => seed = value.parse().map_err(|error| format!("{}", error))?,
+            _ => return Err(format!("unknown argument {:?}", argument)),
+        }
+    }
+
+    // The human takes one more seat
+    if !(1..MAX_SEATS).contains(&bots.len()) {
+        return Err(format!("1 to {} bots are needed", MAX_SEATS - 1));
+    }
+
+    Ok((bots, seed))
+}
+
+fn main() {
+    let (bots, seed) = parse_args().unwrap_or_else(|error| {
+        eprintln!("{}\n{}", error, USAGE);
+        exit(1);
+    });
+
+    let mut state = GameState::from_seed(bots.len() + 1, seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let budget = SearchBudget::default();
+    let mut lines = io::stdin().lock().lines();
+
+    println!("seed: {}", seed);
+
+    while !state.is_over() {
+        let seat = state.current;
+
+        let action = if seat == HUMAN_SEAT {
+            println!("\n{}", render_table(&state, HUMAN_SEAT));
+            print!("your move (index [color] / d / p / c / s <player> / q): ");
+            io::stdout().flush().expect("stdout is writable");
+
+            let Some(Ok(line)) = lines.next() else {
+                return;
+            };
+            if line.trim() == "q" {
+                return;
+            }
+
+            match parse_move(&line) {
+                Ok(action) => action,
+                Err(error) => {
+                    println!("{}", error);
+                    continue;
+                }
+            }
+        } else {
+            ai::choose_action(bots[seat - 1], &state, seat, &budget, &mut rng)
+        };
+
+        match state.apply(seat, action) {
+            Ok(events) => events
+                .iter()
+                .for_each(|event| println!("{}", describe_event(event))),
+            Err(error) => println!("{}", error),
+        }
+    }
+}
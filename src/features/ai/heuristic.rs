@@ -0,0 +1,264 @@
+use crate::{
+    features::{
+        deck::CardInfo,
+        rules::{Action, GameState, Seat},
+    },
+    CardColor, CardVariant,
+};
+
+/// Under this many cards the next player gets attacked with action cards
+const ATTACK_THRESHOLD: usize = 2;
+/// A +4 played while keeping this many cards likely hid a card of the replaced color
+const CHALLENGE_THRESHOLD: usize = 5;
+
+/// ## Picks an action for `seat` with simple rules of thumb
+///
+/// - play a card of the active color rather than switching colors
+/// - hold wild cards while any other card can be played
+/// - dump +2, block and +4 cards on the next player when it is about to win
+/// - choose the color of a wild card by majority in hand
+/// - challenge a +4 played by someone keeping a lot of cards
+/// - swap hands with the opponent holding the fewest cards
+///
+/// Only public information and the hand of `seat` are looked at.
+pub fn choose_action(state: &GameState, seat: Seat) -> Action {
+    if let Some(challenge) = state.plus_four {
+        return if challenge.held >= CHALLENGE_THRESHOLD {
+            Action::ChallengePlusFour
+        } else {
+            Action::Draw
+        };
+    }
+    if let Some(target) = state
+        .swap_targets(seat)
+        .into_iter()
+        .min_by_key(|target| state.hands[*target].len())
+    {
+        return Action::SwapHands { target };
+    }
+
+    let hand = &state.hands[seat];
+    let attacking = state.hands[state.seat_after(seat, 1)].len() <= ATTACK_THRESHOLD;
+
+    state
+        .playable_cards(seat)
+        .into_iter()
+        .max_by_key(|index| score(state, hand, hand[*index], attacking))
+        .map(|card| Action::Play {
+            card,
+            color: (hand[card].0 == CardColor::Wild)
+                .then(|| choose_color(state.playable_colors(), hand, card)),
+        })
+        .unwrap_or(Action::Draw)
+}
+
+/// How much a playable `card` is worth playing now, higher is better
+fn score(state: &GameState, hand: &[CardInfo], (color, variant): CardInfo, attacking: bool) -> i32 {
+    match (color, variant) {
+        (_, CardVariant::DrawFive) if attacking => 105,
+        (_, CardVariant::PlusTwo) if attacking => 100,
+        (_, CardVariant::Block | CardVariant::SkipEveryone) if attacking => 95,
+        (_, CardVariant::PlusFour | CardVariant::WildDrawColor) if attacking => 90,
+        (CardColor::Wild, CardVariant::PlusFour | CardVariant::WildDrawColor) => 1,
+        (CardColor::Wild, _) => 0,
+        (color, variant) => {
+            let color_bonus = if color == state.active_color {
+                50
+            } else {
+                // Switching colors is better towards a color we hold a lot of
+                20 + color_count(hand, color) as i32
+            };
+            // Get rid of high value cards first
+            let value_bonus = match variant {
+                CardVariant::Number(number) => number as i32,
+                _ => 10,
+            };
+
+            color_bonus + value_bonus
+        }
+    }
+}
+
+fn color_count(hand: &[CardInfo], color: CardColor) -> usize {
+    hand.iter()
+        .filter(|(card_color, _)| *card_color == color)
+        .count()
+}
+
+/// Most held of the `colors` in `hand` without the card at index `played`, wild cards excluded
+pub fn choose_color(colors: [CardColor; 4], hand: &[CardInfo], played: usize) -> CardColor {
+    let remaining = hand
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != played)
+        .map(|(_, card)| *card)
+        .collect::<Vec<_>>();
+
+    colors
+        .into_iter()
+        .rev()
+        .max_by_key(|color| color_count(&remaining, *color))
+        .expect("there are playable colors")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::rules::{
+        challenge::PlusFourChallenge, flip::Side, Direction, HouseRules, PLAYABLE_COLORS,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_2: CardInfo = (CardColor::Red, CardVariant::Number(2));
+    const RED_9: CardInfo = (CardColor::Red, CardVariant::Number(9));
+    const BLUE_5: CardInfo = (CardColor::Blue, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const GREEN_1: CardInfo = (CardColor::Green, CardVariant::Number(1));
+    const WILD: CardInfo = (CardColor::Wild, CardVariant::Wild);
+    const PLUS_FOUR: CardInfo = (CardColor::Wild, CardVariant::PlusFour);
+    const RED_PLUS_TWO: CardInfo = (CardColor::Red, CardVariant::PlusTwo);
+
+    /// Seat 0 plays on a red 5, the next player holds `next_hand_size` cards
+    fn state(hand: Vec<CardInfo>, next_hand_size: usize) -> GameState {
+        GameState {
+            draw_pile: vec![GREEN_1; 10],
+            discard_pile: vec![RED_5],
+            hands: vec![hand, vec![GREEN_1; next_hand_size]],
+            current: 0,
+            direction: Direction::Clockwise,
+            active_color: CardColor::Red,
+            winner: None,
+            seed: 0,
+            reshuffles: 0,
+            rules: HouseRules::default(),
+            scores: vec![0; 2],
+            round: 0,
+            uno_called: None,
+            forgot_uno: None,
+            plus_four: None,
+            revealed: None,
+            draw_stack: 0,
+            swapping: None,
+            drawn: None,
+            side: Side::Light,
+        }
+    }
+
+    mod choose_action {
+        use super::*;
+
+        #[test]
+        fn challenges_plus_four_from_big_hands() {
+            let mut state = state(vec![RED_2], 7);
+            state.plus_four = Some(PlusFourChallenge {
+                offender: 1,
+                victim: 0,
+                previous_color: CardColor::Blue,
+                held: 6,
+            });
+
+            assert_eq!(choose_action(&state, 0), Action::ChallengePlusFour);
+            state.plus_four = state.plus_four.map(|challenge| PlusFourChallenge {
+                held: 2,
+                ..challenge
+            });
+            assert_eq!(choose_action(&state, 0), Action::Draw);
+        }
+
+        #[test]
+        fn swaps_with_smallest_hand() {
+            let mut state = state(vec![RED_2; 4], 3);
+            state.hands.push(vec![GREEN_1]);
+            state.swapping = Some(0);
+
+            assert_eq!(choose_action(&state, 0), Action::SwapHands { target: 2 });
+        }
+
+        #[test]
+        fn draws_without_playable_card() {
+            let state = state(vec![BLUE_7, GREEN_1], 7);
+
+            assert_eq!(choose_action(&state, 0), Action::Draw);
+        }
+
+        #[test]
+        fn prefers_matching_color() {
+            let state = state(vec![BLUE_5, RED_2], 7);
+
+            assert_eq!(
+                choose_action(&state, 0),
+                Action::Play {
+                    card: 1,
+                    color: None
+                }
+            );
+        }
+
+        #[test]
+        fn dumps_high_numbers_first() {
+            let state = state(vec![RED_2, RED_9], 7);
+
+            assert_eq!(
+                choose_action(&state, 0),
+                Action::Play {
+                    card: 1,
+                    color: None
+                }
+            );
+        }
+
+        #[test]
+        fn holds_wild_cards() {
+            let state = state(vec![WILD, PLUS_FOUR, BLUE_5], 7);
+
+            assert_eq!(
+                choose_action(&state, 0),
+                Action::Play {
+                    card: 2,
+                    color: None
+                }
+            );
+        }
+
+        #[test]
+        fn attacks_next_player_with_few_cards() {
+            let state = state(vec![RED_9, RED_PLUS_TWO], 1);
+
+            assert_eq!(
+                choose_action(&state, 0),
+                Action::Play {
+                    card: 1,
+                    color: None
+                }
+            );
+        }
+
+        #[test]
+        fn wild_gets_majority_color() {
+            let state = state(vec![WILD, BLUE_7, BLUE_7, GREEN_1], 7);
+
+            assert_eq!(
+                choose_action(&state, 0),
+                Action::Play {
+                    card: 0,
+                    color: Some(CardColor::Blue)
+                }
+            );
+        }
+    }
+
+    mod choose_color {
+        use super::*;
+
+        #[test]
+        fn ignores_played_and_wild_cards() {
+            let hand = vec![GREEN_1, WILD, PLUS_FOUR, RED_5, RED_2];
+
+            assert_eq!(choose_color(PLAYABLE_COLORS, &hand, 1), CardColor::Red);
+            assert_eq!(
+                choose_color(PLAYABLE_COLORS, &[RED_5, GREEN_1], 0),
+                CardColor::Green
+            );
+        }
+    }
+}
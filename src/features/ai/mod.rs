@@ -0,0 +1,522 @@
+pub mod heuristic;
+pub mod monte_carlo;
+
+use std::{str::FromStr, time::Duration};
+
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    game::{
+        ApplyRequestsSet, CallUnoRequest, CatchUnoRequest, ChallengeRequest, DrawCardRequest,
+        PassRequest, PlayCardRequest, Player, SeatKind, SwapHandsRequest, Table,
+    },
+    rules::{Action, GameState, Seat},
+};
+
+pub use monte_carlo::SearchBudget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// Plays a random legal card
+    Easy,
+    /// Plays with [`heuristic::choose_action`]
+    #[default]
+    Normal,
+    /// Searches with [`monte_carlo::choose_action`]
+    Hard,
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(difficulty: &str) -> Result<Self, Self::Err> {
+        match difficulty.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("unknown difficulty {:?}", difficulty)),
+        }
+    }
+}
+
+/// Picks the action of `seat` the way a bot of the given `difficulty` would
+pub fn choose_action<R: Rng + ?Sized>(
+    difficulty: Difficulty,
+    state: &GameState,
+    seat: Seat,
+    budget: &SearchBudget,
+    rng: &mut R,
+) -> Action {
+    match difficulty {
+        Difficulty::Easy => {
+            let mut actions = state.legal_actions(seat);
+            // Drawing is always legal, only do it without any card to play.
+            // Answering a +4 is a coin flip between challenging it and drawing
+            if actions.len() > 1 && state.plus_four.is_none() {
+                actions.retain(|action| *action != Action::Draw);
+            }
+
+            actions.choose(rng).copied().unwrap_or(Action::Draw)
+        }
+        Difficulty::Normal => heuristic::choose_action(state, seat),
+        Difficulty::Hard => monte_carlo::choose_action(state, seat, budget, rng),
+    }
+}
+
+/// Chance of an easy bot to forget calling uno
+pub const EASY_FORGETS_UNO: f64 = 0.3;
+
+/// ## Uno call or catch a bot makes before its turn action
+///
+/// Bots catch whoever forgot to call uno and call it themselves, except easy bots
+/// that sometimes forget.
+pub fn uno_action<R: Rng + ?Sized>(
+    difficulty: Difficulty,
+    state: &GameState,
+    seat: Seat,
+    rng: &mut R,
+) -> Option<Action> {
+    if let Some(target) = state.catchable().filter(|target| *target != seat) {
+        return Some(Action::CatchUno { target });
+    }
+
+    let forgets = difficulty == Difficulty::Easy && rng.gen_bool(EASY_FORGETS_UNO);
+    (state.can_call_uno(seat) && !forgets).then_some(Action::CallUno)
+}
+
+/// ## Turn played for a human who ran out of time, see [`crate::features::rules::HouseRules::turn_time`]
+///
+/// A normal bot calls uno or catches a forgotten one, then makes the move, which draws
+/// when nothing can be played.
+pub fn timeout_actions<R: Rng + ?Sized>(state: &GameState, seat: Seat, rng: &mut R) -> Vec<Action> {
+    uno_action(Difficulty::Normal, state, seat, rng)
+        .into_iter()
+        .chain([heuristic::choose_action(state, seat)])
+        .collect()
+}
+
+/// Time a bot needs to notice it can jump in, see [`GameState::can_jump_in`]
+pub fn reaction_delay(difficulty: Difficulty) -> Duration {
+    match difficulty {
+        Difficulty::Easy => Duration::from_millis(1500),
+        Difficulty::Normal => Duration::from_millis(600),
+        Difficulty::Hard => Duration::from_millis(300),
+    }
+}
+
+/// Out of turn play of `seat` when it holds a card identical to the top one, wild cards
+/// get the color [`heuristic::choose_color`] picks
+pub fn jump_in_action(state: &GameState, seat: Seat) -> Option<Action> {
+    let hand = &state.hands[seat];
+
+    state
+        .jump_in_actions(seat)
+        .into_iter()
+        .find(|action| match action {
+            Action::Play {
+                card,
+                color: Some(color),
+            } => *color == heuristic::choose_color(state.playable_colors(), hand, *card),
+            _ => true,
+        })
+}
+
+/// ## Bot jumping in on a table without time, like the server or simulations
+///
+/// The closest seat in turn order jumps in first, easy bots are never fast enough.
+pub fn headless_jump_in(
+    state: &GameState,
+    bot_at: impl Fn(Seat) -> Option<Difficulty>,
+) -> Option<(Seat, Action)> {
+    (1..state.players())
+        .map(|steps| state.seat_after(state.current, steps))
+        .filter(|seat| bot_at(*seat).is_some_and(|difficulty| difficulty != Difficulty::Easy))
+        .find_map(|seat| jump_in_action(state, seat).map(|action| (seat, action)))
+}
+
+/// A seat played by the computer, it waits for its `think` timer before acting on its turn
+/// and for its `react` timer before jumping in
+#[derive(Component, Debug)]
+pub struct Bot {
+    pub difficulty: Difficulty,
+    think: Timer,
+    react: Timer,
+}
+
+impl Bot {
+    pub fn new(difficulty: Difficulty, think_delay: Duration) -> Self {
+        Self {
+            difficulty,
+            think: Timer::new(think_delay, TimerMode::Once),
+            react: Timer::new(reaction_delay(difficulty), TimerMode::Once),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct BotSettings {
+    /// How long bots wait before playing, so humans can follow the game
+    pub think_delay: Duration,
+    /// Work allowed to [`Difficulty::Hard`] bots for each move
+    pub search_budget: SearchBudget,
+}
+
+impl Default for BotSettings {
+    fn default() -> Self {
+        Self {
+            think_delay: Duration::from_millis(800),
+            search_budget: SearchBudget::default(),
+        }
+    }
+}
+
+pub struct BotPlugin;
+
+/// This plugin lets bot seats play through the same requests as the local player
+impl Plugin for BotPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BotSettings>().add_systems(
+            Update,
+            (attach_bots, play_bot_turns.run_if(resource_exists::<Table>))
+                .chain()
+                .before(ApplyRequestsSet),
+        );
+    }
+}
+
+/// Give a [`Bot`] to every new player sitting on a bot seat
+fn attach_bots(
+    mut commands: Commands,
+    players_query: Query<(Entity, &SeatKind), Added<Player>>,
+    settings: Res<BotSettings>,
+) {
+    for (entity, kind) in players_query.iter() {
+        if let SeatKind::Bot(difficulty) = kind {
+            commands
+                .entity(entity)
+                .insert(Bot::new(*difficulty, settings.think_delay));
+        }
+    }
+}
+
+/// Send the request chosen by the bot whose turn it is once it is done thinking,
+/// any bot holding a card identical to the top one jumps in once it reacted
+fn play_bot_turns(
+    mut bots_query: Query<(&Player, &mut Bot)>,
+    table: Res<Table>,
+    time: Res<Time>,
+    settings: Res<BotSettings>,
+    mut writers: (
+        EventWriter<PlayCardRequest>,
+        EventWriter<DrawCardRequest>,
+        EventWriter<PassRequest>,
+        EventWriter<CallUnoRequest>,
+        EventWriter<CatchUnoRequest>,
+        EventWriter<ChallengeRequest>,
+        EventWriter<SwapHandsRequest>,
+    ),
+) {
+    for (Player { seat }, mut bot) in bots_query.iter_mut() {
+        if let Some(action) = jump_in_action(&table.0, *seat) {
+            if bot.react.tick(time.delta()).just_finished() {
+                bot.react.reset();
+                send_request(*seat, action, &mut writers);
+            }
+        } else {
+            bot.react.reset();
+        }
+
+        if table.0.is_over() || table.0.current != *seat {
+            bot.think.reset();
+            continue;
+        }
+
+        if !bot.think.tick(time.delta()).finished() {
+            continue;
+        }
+        bot.think.reset();
+
+        let mut rng = rand::thread_rng();
+        let uno = uno_action(bot.difficulty, &table.0, *seat, &mut rng);
+        let action = choose_action(
+            bot.difficulty,
+            &table.0,
+            *seat,
+            &settings.search_budget,
+            &mut rng,
+        );
+
+        for action in uno.into_iter().chain([action]) {
+            send_request(*seat, action, &mut writers);
+        }
+    }
+}
+
+/// Send the request of `action` for `seat`, with the writers of every kind of request
+pub fn send_request(
+    seat: Seat,
+    action: Action,
+    (
+        play_writer,
+        draw_writer,
+        pass_writer,
+        call_uno_writer,
+        catch_uno_writer,
+        challenge_writer,
+        swap_writer,
+    ): &mut (
+        EventWriter<PlayCardRequest>,
+        EventWriter<DrawCardRequest>,
+        EventWriter<PassRequest>,
+        EventWriter<CallUnoRequest>,
+        EventWriter<CatchUnoRequest>,
+        EventWriter<ChallengeRequest>,
+        EventWriter<SwapHandsRequest>,
+    ),
+) {
+    match action {
+        Action::Play { card, color } => {
+            play_writer.send(PlayCardRequest { seat, card, color });
+        }
+        Action::Draw => {
+            draw_writer.send(DrawCardRequest { seat });
+        }
+        Action::Pass => {
+            pass_writer.send(PassRequest { seat });
+        }
+        Action::CallUno => {
+            call_uno_writer.send(CallUnoRequest { seat });
+        }
+        Action::CatchUno { target } => {
+            catch_uno_writer.send(CatchUnoRequest { seat, target });
+        }
+        Action::ChallengePlusFour => {
+            challenge_writer.send(ChallengeRequest { seat });
+        }
+        Action::SwapHands { target } => {
+            swap_writer.send(SwapHandsRequest { seat, target });
+        }
+        // Bots never play once the round is over, the humans deal the next one
+        Action::NextRound => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn setup_app(think_delay: Duration) -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_event::<PlayCardRequest>()
+            .add_event::<DrawCardRequest>()
+            .add_event::<PassRequest>()
+            .add_event::<CallUnoRequest>()
+            .add_event::<CatchUnoRequest>()
+            .add_event::<ChallengeRequest>()
+            .add_event::<SwapHandsRequest>()
+            .insert_resource(BotSettings {
+                think_delay,
+                ..default()
+            })
+            .insert_resource(Table(GameState::from_seed(2, 0)))
+            .add_systems(Update, (attach_bots, play_bot_turns).chain());
+
+        app
+    }
+
+    fn requests_count(app: &App) -> usize {
+        app.world.resource::<Events<PlayCardRequest>>().len()
+            + app.world.resource::<Events<DrawCardRequest>>().len()
+    }
+
+    mod choose_action {
+        use super::*;
+
+        #[test]
+        fn easy_plays_when_it_can() {
+            let state = GameState::from_seed(2, 0);
+            let mut rng = StdRng::seed_from_u64(0);
+
+            let action = choose_action(
+                Difficulty::Easy,
+                &state,
+                0,
+                &SearchBudget::default(),
+                &mut rng,
+            );
+
+            assert!(state.legal_actions(0).contains(&action));
+            assert_eq!(action == Action::Draw, state.playable_cards(0).is_empty());
+        }
+
+        #[test]
+        fn legal_on_both_sides_of_flip_cards() {
+            let deck = crate::features::deck::Deck::from_recipe(
+                crate::features::deck::DeckRecipe::Flip,
+                0,
+            );
+            let mut state = GameState::new(deck.into_cards(), 3, 0);
+            let mut rng = StdRng::seed_from_u64(0);
+            let mut flips = 0;
+
+            while !state.is_over() {
+                let seat = state.current;
+                let difficulty = [Difficulty::Easy, Difficulty::Normal][seat % 2];
+                let action =
+                    choose_action(difficulty, &state, seat, &SearchBudget::default(), &mut rng);
+                let events = state.apply(seat, action).unwrap();
+                flips += events
+                    .iter()
+                    .filter(|event| {
+                        matches!(event, crate::features::rules::GameEvent::Flipped { .. })
+                    })
+                    .count();
+            }
+
+            assert!(flips > 0);
+        }
+    }
+
+    mod headless_jump_in {
+        use super::*;
+        use crate::{features::rules::HouseRules, CardColor, CardVariant};
+
+        #[test]
+        fn closest_bot_first() {
+            const RED_5: (CardColor, CardVariant) = (CardColor::Red, CardVariant::Number(5));
+            let mut state = GameState::from_seed(4, 0);
+            state.rules = HouseRules {
+                jump_in: true,
+                ..HouseRules::default()
+            };
+            state.discard_pile.push(RED_5);
+            state.current = 2;
+            for seat in [0, 1] {
+                state.hands[seat][0] = RED_5;
+            }
+
+            let bots = |_| Some(Difficulty::Normal);
+            assert_eq!(
+                headless_jump_in(&state, bots).map(|(seat, _)| seat),
+                Some(0)
+            );
+            let easy_first = |seat| {
+                Some(if seat == 0 {
+                    Difficulty::Easy
+                } else {
+                    Difficulty::Normal
+                })
+            };
+            assert_eq!(
+                headless_jump_in(&state, easy_first).map(|(seat, _)| seat),
+                Some(1)
+            );
+        }
+    }
+
+    mod uno_action {
+        use super::*;
+        use crate::{CardColor, CardVariant};
+
+        #[test]
+        fn catches_before_calling() {
+            let mut state = GameState::from_seed(3, 0);
+            state.hands[1].truncate(2);
+            state.current = 1;
+            state.forgot_uno = Some(0);
+            let mut rng = StdRng::seed_from_u64(0);
+
+            assert_eq!(
+                uno_action(Difficulty::Normal, &state, 1, &mut rng),
+                Some(Action::CatchUno { target: 0 })
+            );
+            state.forgot_uno = None;
+            assert_eq!(
+                uno_action(Difficulty::Normal, &state, 1, &mut rng),
+                Some(Action::CallUno)
+            );
+        }
+
+        #[test]
+        fn easy_sometimes_forgets() {
+            let mut state = GameState::from_seed(2, 0);
+            state.hands[0] = vec![(CardColor::Red, CardVariant::Number(1)); 2];
+            let mut rng = StdRng::seed_from_u64(0);
+
+            let calls = (0..100)
+                .filter(|_| uno_action(Difficulty::Easy, &state, 0, &mut rng).is_some())
+                .count();
+
+            assert!((50..100).contains(&calls), "{} calls", calls);
+        }
+    }
+
+    mod attach_bots {
+        use super::*;
+
+        #[test]
+        fn only_bot_seats() {
+            let mut app = setup_app(Duration::ZERO);
+
+            let bot = app
+                .world
+                .spawn((Player { seat: 0 }, SeatKind::Bot(Difficulty::Normal)))
+                .id();
+            let human = app.world.spawn((Player { seat: 1 }, SeatKind::Human)).id();
+
+            app.update();
+
+            assert_eq!(
+                app.world.entity(bot).get::<Bot>().map(|bot| bot.difficulty),
+                Some(Difficulty::Normal)
+            );
+            assert!(!app.world.entity(human).contains::<Bot>());
+        }
+    }
+
+    mod play_bot_turns {
+        use super::*;
+
+        #[test]
+        fn plays_on_its_turn() {
+            let mut app = setup_app(Duration::ZERO);
+
+            app.world
+                .spawn((Player { seat: 0 }, SeatKind::Bot(Difficulty::Normal)));
+
+            app.update();
+
+            assert_eq!(requests_count(&app), 1);
+        }
+
+        #[test]
+        fn waits_for_its_turn() {
+            let mut app = setup_app(Duration::ZERO);
+
+            app.world
+                .spawn((Player { seat: 1 }, SeatKind::Bot(Difficulty::Normal)));
+
+            app.update();
+
+            assert_eq!(requests_count(&app), 0);
+        }
+
+        #[test]
+        fn waits_think_delay() {
+            let mut app = setup_app(Duration::from_secs(3600));
+
+            app.world
+                .spawn((Player { seat: 0 }, SeatKind::Bot(Difficulty::Normal)));
+
+            app.update();
+            app.update();
+
+            assert_eq!(requests_count(&app), 0);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::time::Duration;
+
+use bevy::utils::Instant;
+use rand::{seq::SliceRandom, Rng};
+
+use crate::features::rules::{Action, GameState, Seat};
+
+use super::heuristic;
+
+/// Playouts longer than this are stopped and scored from the hand sizes
+const MAX_PLAYOUT_TURNS: usize = 300;
+/// Chance for a player to take a random legal action instead of the heuristic one during playouts
+const PLAYOUT_RANDOMNESS: f64 = 0.2;
+/// Exploration constant of the UCB1 formula
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+/// How many standard errors a move must win by to be preferred over the heuristic one
+const CONFIDENCE: f64 = 2.;
+
+/// How much work the search may do before answering, whichever limit is hit first
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchBudget {
+    pub max_iterations: usize,
+    /// Keeps the app responsive, `None` only stops on iterations
+    pub max_time: Option<Duration>,
+}
+
+impl Default for SearchBudget {
+    fn default() -> Self {
+        Self {
+            max_iterations: 2000,
+            max_time: Some(Duration::from_millis(50)),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ActionStats {
+    visits: u32,
+    reward: f64,
+}
+
+impl ActionStats {
+    fn mean(&self) -> f64 {
+        if self.visits == 0 {
+            return 0.;
+        }
+
+        self.reward / self.visits as f64
+    }
+
+    fn ucb(&self, total_visits: u32) -> f64 {
+        if self.visits == 0 {
+            return f64::INFINITY;
+        }
+
+        self.mean() + EXPLORATION * ((total_visits as f64).ln() / self.visits as f64).sqrt()
+    }
+
+    /// Squared standard error of the mean, rewards being between 0 and 1
+    fn variance_of_mean(&self) -> f64 {
+        let mean = self.mean();
+
+        mean * (1. - mean) / self.visits.max(1) as f64
+    }
+
+    /// Whether the mean reward is higher than the `other` one by more than [`CONFIDENCE`] standard errors
+    fn is_clearly_better(&self, other: &ActionStats) -> bool {
+        let margin = CONFIDENCE * (self.variance_of_mean() + other.variance_of_mean()).sqrt();
+
+        self.visits > 0 && self.mean() - other.mean() > margin
+    }
+}
+
+/// ## Picks an action for `seat` with Monte Carlo playouts
+///
+/// Each iteration picks a root action with UCB1, samples the hidden cards with [`determinize`],
+/// then plays the game until the end with a noisy heuristic policy. The heuristic move is kept
+/// unless another action gets a clearly better mean reward.
+pub fn choose_action<R: Rng + ?Sized>(
+    state: &GameState,
+    seat: Seat,
+    budget: &SearchBudget,
+    rng: &mut R,
+) -> Action {
+    let actions = candidate_actions(state, seat);
+    if actions.len() <= 1 {
+        return actions.first().copied().unwrap_or(Action::Draw);
+    }
+
+    let start = Instant::now();
+    let mut stats = vec![ActionStats::default(); actions.len()];
+
+    for iteration in 0..budget.max_iterations {
+        if budget
+            .max_time
+            .is_some_and(|max_time| start.elapsed() >= max_time)
+        {
+            break;
+        }
+
+        let (index, _) = stats
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.ucb(iteration as u32).total_cmp(&b.ucb(iteration as u32)))
+            .expect("there are legal actions");
+
+        let mut sample = determinize(state, seat, rng);
+        let reward = match sample.apply(seat, actions[index]) {
+            Ok(_) => playout(&mut sample, seat, rng),
+            Err(_) => 0.,
+        };
+
+        stats[index].visits += 1;
+        stats[index].reward += reward;
+    }
+
+    // Playouts are noisy: only leave the heuristic move when another one is clearly better
+    let fallback = heuristic::choose_action(state, seat);
+    let fallback_index = actions
+        .iter()
+        .position(|action| *action == fallback)
+        .unwrap_or_default();
+    let (best_index, _) = stats
+        .iter()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.mean().total_cmp(&b.mean()))
+        .expect("there are legal actions");
+
+    if stats[best_index].is_clearly_better(&stats[fallback_index]) {
+        actions[best_index]
+    } else {
+        actions[fallback_index]
+    }
+}
+
+/// Legal actions of `seat` worth searching: wild cards only get the color [`heuristic::choose_color`] picks,
+/// sampling every color would split the budget for little gain
+fn candidate_actions(state: &GameState, seat: Seat) -> Vec<Action> {
+    let hand = &state.hands[seat];
+
+    state
+        .legal_actions(seat)
+        .into_iter()
+        .filter(|action| match action {
+            Action::Play {
+                card,
+                color: Some(color),
+            } => *color == heuristic::choose_color(state.playable_colors(), hand, *card),
+            _ => true,
+        })
+        .collect()
+}
+
+/// ## Samples the hidden information of `state` as seen by `seat`
+///
+/// The hand of `seat`, the discard pile and a hand revealed to `seat` by a challenge are known,
+/// every other card could be anywhere in the draw pile or the other hands: they are shuffled
+/// and dealt back keeping the hand sizes.
+pub fn determinize<R: Rng + ?Sized>(state: &GameState, seat: Seat, rng: &mut R) -> GameState {
+    let mut sample = state.clone();
+    let revealed = state
+        .revealed
+        .filter(|revealed| revealed.to == seat)
+        .map(|revealed| revealed.seat);
+    let is_known = |hand_seat: Seat| hand_seat == seat || Some(hand_seat) == revealed;
+
+    let mut hidden = sample.draw_pile.clone();
+    sample
+        .hands
+        .iter()
+        .enumerate()
+        .filter(|(hand_seat, _)| !is_known(*hand_seat))
+        .for_each(|(_, hand)| hidden.extend(hand.iter().copied()));
+    hidden.shuffle(rng);
+
+    for (hand_seat, hand) in sample.hands.iter_mut().enumerate() {
+        if !is_known(hand_seat) {
+            let len = hand.len();
+            *hand = hidden.split_off(hidden.len() - len);
+        }
+    }
+    sample.draw_pile = hidden;
+
+    sample
+}
+
+/// Play `state` until the end, returns 1 when `seat` wins and a share of it when stopped early
+fn playout<R: Rng + ?Sized>(state: &mut GameState, seat: Seat, rng: &mut R) -> f64 {
+    for _ in 0..MAX_PLAYOUT_TURNS {
+        if let Some(winner) = state.winner {
+            return if winner == seat { 1. } else { 0. };
+        }
+
+        let current = state.current;
+        let action = if rng.gen_bool(PLAYOUT_RANDOMNESS) {
+            *state
+                .legal_actions(current)
+                .choose(rng)
+                .expect("the current seat can always draw")
+        } else {
+            heuristic::choose_action(state, current)
+        };
+
+        if state.apply(current, action).is_err() {
+            break;
+        }
+    }
+
+    let total_cards: usize = state.hands.iter().map(Vec::len).sum();
+    if total_cards == 0 {
+        return 0.;
+    }
+
+    1. - state.hands[seat].len() as f64 / total_cards as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::CardInfo,
+            rules::{challenge::RevealedHand, flip::Side, Direction, HouseRules},
+        },
+        CardColor, CardVariant,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    const RED_1: CardInfo = (CardColor::Red, CardVariant::Number(1));
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+
+    const BUDGET: SearchBudget = SearchBudget {
+        max_iterations: 200,
+        max_time: None,
+    };
+
+    mod choose_action {
+        use super::*;
+
+        #[test]
+        fn plays_winning_card() {
+            let state = GameState {
+                draw_pile: vec![BLUE_7; 20],
+                discard_pile: vec![RED_5],
+                hands: vec![vec![RED_1], vec![BLUE_7; 3], vec![BLUE_7; 3]],
+                current: 0,
+                direction: Direction::Clockwise,
+                active_color: CardColor::Red,
+                winner: None,
+                seed: 0,
+                reshuffles: 0,
+                rules: HouseRules::default(),
+                scores: vec![0; 3],
+                round: 0,
+                uno_called: None,
+                forgot_uno: None,
+                plus_four: None,
+                revealed: None,
+                draw_stack: 0,
+                swapping: None,
+                drawn: None,
+                side: Side::Light,
+            };
+
+            let action = choose_action(&state, 0, &BUDGET, &mut StdRng::seed_from_u64(0));
+
+            assert_eq!(
+                action,
+                Action::Play {
+                    card: 0,
+                    color: None
+                }
+            );
+        }
+
+        #[test]
+        fn always_legal() {
+            let mut rng = StdRng::seed_from_u64(1);
+
+            for seed in 0..5 {
+                let state = GameState::from_seed(4, seed);
+
+                let action = choose_action(&state, 0, &BUDGET, &mut rng);
+
+                assert!(state.legal_actions(0).contains(&action));
+            }
+        }
+    }
+
+    mod determinize {
+        use super::*;
+
+        fn sorted(cards: Vec<CardInfo>) -> Vec<String> {
+            let mut names = cards
+                .into_iter()
+                .map(|card| format!("{:?}", card))
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        }
+
+        #[test]
+        fn keeps_known_cards_and_sizes() {
+            let state = GameState::from_seed(3, 7);
+
+            let sample = determinize(&state, 1, &mut StdRng::seed_from_u64(0));
+
+            assert_eq!(sample.hands[1], state.hands[1]);
+            assert_eq!(sample.discard_pile, state.discard_pile);
+            assert_eq!(sample.draw_pile.len(), state.draw_pile.len());
+            assert!(sample
+                .hands
+                .iter()
+                .zip(state.hands.iter())
+                .all(|(sampled, real)| sampled.len() == real.len()));
+        }
+
+        #[test]
+        fn keeps_hidden_cards() {
+            let state = GameState::from_seed(3, 7);
+
+            let sample = determinize(&state, 1, &mut StdRng::seed_from_u64(0));
+
+            let hidden = |state: &GameState| {
+                let mut cards = state.draw_pile.clone();
+                cards.extend(state.hands[0].iter().copied());
+                cards.extend(state.hands[2].iter().copied());
+                sorted(cards)
+            };
+
+            assert_eq!(hidden(&sample), hidden(&state));
+        }
+
+        #[test]
+        fn keeps_revealed_hands() {
+            let mut state = GameState::from_seed(3, 7);
+            state.revealed = Some(RevealedHand { seat: 2, to: 1 });
+
+            for seed in 0..10 {
+                let sample = determinize(&state, 1, &mut StdRng::seed_from_u64(seed));
+
+                assert_eq!(sample.hands[2], state.hands[2]);
+            }
+
+            // Only the player it was revealed to knows the hand
+            let sample = determinize(&state, 0, &mut StdRng::seed_from_u64(0));
+            assert_ne!(sample.hands[2], state.hands[2]);
+        }
+    }
+}
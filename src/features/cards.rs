@@ -1,12 +1,37 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Component)]
+use crate::features::rules::effects::{self, EffectId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, Serialize, Deserialize)]
 pub enum CardColor {
     Yellow,
     Red,
     Blue,
     Green,
     Wild,
+    /// Colors of the dark side of Flip cards, see [`CardColor::flipped`]
+    Pink,
+    Teal,
+    Orange,
+    Purple,
+}
+
+impl CardColor {
+    /// Color on the other side of a Flip card, wild cards stay wild
+    pub fn flipped(self) -> Self {
+        match self {
+            CardColor::Yellow => CardColor::Orange,
+            CardColor::Red => CardColor::Pink,
+            CardColor::Blue => CardColor::Teal,
+            CardColor::Green => CardColor::Purple,
+            CardColor::Wild => CardColor::Wild,
+            CardColor::Orange => CardColor::Yellow,
+            CardColor::Pink => CardColor::Red,
+            CardColor::Teal => CardColor::Blue,
+            CardColor::Purple => CardColor::Green,
+        }
+    }
 }
 
 impl From<CardColor> for String {
@@ -17,11 +42,15 @@ impl From<CardColor> for String {
             CardColor::Blue => "blue".to_string(),
             CardColor::Green => "green".to_string(),
             CardColor::Wild => "wild".to_string(),
+            CardColor::Pink => "pink".to_string(),
+            CardColor::Teal => "teal".to_string(),
+            CardColor::Orange => "orange".to_string(),
+            CardColor::Purple => "purple".to_string(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Component)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, Serialize, Deserialize)]
 pub enum CardVariant {
     Number(u8),
     Invert,
@@ -29,6 +58,39 @@ pub enum CardVariant {
     PlusTwo,
     PlusFour,
     Wild,
+    /// Turns every card of the game over to its other side
+    Flip,
+    /// Dark side of a +2
+    DrawFive,
+    /// Dark side of a block, every other player is skipped
+    SkipEveryone,
+    /// Dark side of a +4, the next player draws until it gets the chosen color
+    WildDrawColor,
+    /// Action card of a registered [`effects::CardEffect`]
+    Custom(EffectId),
+}
+
+impl CardVariant {
+    /// Variant on the other side of a Flip card
+    pub fn flipped(self) -> Self {
+        match self {
+            CardVariant::Block => CardVariant::SkipEveryone,
+            CardVariant::PlusTwo => CardVariant::DrawFive,
+            CardVariant::PlusFour => CardVariant::WildDrawColor,
+            CardVariant::SkipEveryone => CardVariant::Block,
+            CardVariant::DrawFive => CardVariant::PlusTwo,
+            CardVariant::WildDrawColor => CardVariant::PlusFour,
+            variant => variant,
+        }
+    }
+
+    /// Effect of a custom card, if it was registered
+    pub fn custom_effect(self) -> Option<std::sync::Arc<dyn effects::CardEffect>> {
+        match self {
+            CardVariant::Custom(id) => effects::effect(id),
+            _ => None,
+        }
+    }
 }
 
 impl From<CardVariant> for String {
@@ -40,6 +102,14 @@ impl From<CardVariant> for String {
             CardVariant::PlusTwo => "2plus".to_string(),
             CardVariant::PlusFour => "4_plus".to_string(),
             CardVariant::Wild => "wild_card".to_string(),
+            CardVariant::Flip => "flip".to_string(),
+            CardVariant::DrawFive => "5plus".to_string(),
+            CardVariant::SkipEveryone => "skip_everyone".to_string(),
+            CardVariant::WildDrawColor => "draw_color".to_string(),
+            CardVariant::Custom(id) => effects::effect(id).map_or_else(
+                || format!("custom_{}", id.0),
+                |effect| effect.key().to_string(),
+            ),
         }
     }
 }
@@ -51,10 +121,43 @@ pub struct CardBundle {
 }
 
 impl CardBundle {
+    /// Path of the texture of a card, dark side cards have their own folder per color
+    /// and custom cards get theirs from [`effects::CardEffect::texture_path`]
     pub fn texture_path(color: CardColor, variant: CardVariant) -> String {
+        if let Some(effect) = variant.custom_effect() {
+            return effect.texture_path(color);
+        }
         let color_str: String = color.into();
         let variant_str: String = variant.into();
 
         format!("cards/{}/{}_{}.png", color_str, variant_str, color_str)
     }
+
+    /// Human readable name of a card, e.g. "blue 9" or "wild"
+    pub fn name(color: CardColor, variant: CardVariant) -> String {
+        let color_str: String = color.into();
+
+        match variant {
+            CardVariant::Number(number) => format!("{} {}", color_str, number),
+            CardVariant::Invert => format!("{} invert", color_str),
+            CardVariant::Block => format!("{} block", color_str),
+            CardVariant::PlusTwo => format!("{} +2", color_str),
+            CardVariant::PlusFour => "wild +4".to_string(),
+            CardVariant::Wild => "wild".to_string(),
+            CardVariant::Flip => format!("{} flip", color_str),
+            CardVariant::DrawFive => format!("{} +5", color_str),
+            CardVariant::SkipEveryone => format!("{} skip everyone", color_str),
+            CardVariant::WildDrawColor => "wild draw color".to_string(),
+            CardVariant::Custom(_) => {
+                let name = variant.custom_effect().map_or_else(
+                    || "custom card".to_string(),
+                    |effect| effect.name().to_string(),
+                );
+                match color {
+                    CardColor::Wild => format!("wild {}", name),
+                    _ => format!("{} {}", color_str, name),
+                }
+            }
+        }
+    }
 }
@@ -1,7 +1,13 @@
-use rand::seq::SliceRandom;
+use std::str::FromStr;
+
+use rand::{seq::SliceRandom, Rng};
+use serde::{Deserialize, Serialize};
 
 use super::CardInfo;
-use crate::{CardColor, CardVariant};
+use crate::{
+    features::rules::effects::{self, EffectId},
+    CardColor, CardVariant,
+};
 
 pub struct DeckGenerator {
     number0: u8,
@@ -11,6 +17,10 @@ pub struct DeckGenerator {
     plus_2: u8,
     plus_4: u8,
     wild_card: u8,
+    /// Flip cards of each color, their dark side comes from [`crate::features::rules::flip::flipped`]
+    flip: u8,
+    /// Cards of each registered effect, of each color unless the effect is wild
+    custom: Vec<(EffectId, u8)>,
 }
 
 impl Default for DeckGenerator {
@@ -23,6 +33,84 @@ impl Default for DeckGenerator {
             plus_2: 2,
             plus_4: 4,
             wild_card: 4,
+            flip: 0,
+            custom: Vec::new(),
+        }
+    }
+}
+
+/// Decks a table can choose to play with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DeckRecipe {
+    #[default]
+    Classic,
+    /// Two classic decks shuffled together, for big tables
+    Double,
+    /// Only number cards, without any action or wild card
+    NumbersOnly,
+    /// Classic cards with Flip cards turning the game over to the dark side
+    Flip,
+    /// Classic cards with the custom cards of [`effects`] shipped with the game
+    Party,
+}
+
+impl From<DeckRecipe> for DeckGenerator {
+    fn from(recipe: DeckRecipe) -> Self {
+        let classic = Self::default();
+
+        match recipe {
+            DeckRecipe::Classic => classic,
+            DeckRecipe::Double => Self {
+                number0: classic.number0 * 2,
+                numbers: classic.numbers * 2,
+                invert: classic.invert * 2,
+                block: classic.block * 2,
+                plus_2: classic.plus_2 * 2,
+                plus_4: classic.plus_4 * 2,
+                wild_card: classic.wild_card * 2,
+                flip: classic.flip * 2,
+                custom: Vec::new(),
+            },
+            DeckRecipe::NumbersOnly => Self {
+                invert: 0,
+                block: 0,
+                plus_2: 0,
+                plus_4: 0,
+                wild_card: 0,
+                ..classic
+            },
+            DeckRecipe::Flip => Self { flip: 2, ..classic },
+            DeckRecipe::Party => Self {
+                custom: vec![(effects::DISCARD_ALL, 1), (effects::EVERYONE_DRAWS, 1)],
+                ..classic
+            },
+        }
+    }
+}
+
+impl From<DeckRecipe> for String {
+    fn from(recipe: DeckRecipe) -> Self {
+        match recipe {
+            DeckRecipe::Classic => "classic".to_string(),
+            DeckRecipe::Double => "double".to_string(),
+            DeckRecipe::NumbersOnly => "numbers only".to_string(),
+            DeckRecipe::Flip => "flip".to_string(),
+            DeckRecipe::Party => "party".to_string(),
+        }
+    }
+}
+
+impl FromStr for DeckRecipe {
+    type Err = String;
+
+    fn from_str(recipe: &str) -> Result<Self, Self::Err> {
+        match recipe.to_lowercase().as_str() {
+            "classic" => Ok(DeckRecipe::Classic),
+            "double" => Ok(DeckRecipe::Double),
+            "numbers" => Ok(DeckRecipe::NumbersOnly),
+            "flip" => Ok(DeckRecipe::Flip),
+            "party" => Ok(DeckRecipe::Party),
+            _ => Err(format!("unknown deck {:?}", recipe)),
         }
     }
 }
@@ -64,7 +152,7 @@ impl DeckGenerator {
     /// we want that card to be created and C the number of possible colors the card has.
     ///
     /// ### Example
-    /// ```rust
+    /// ```rust,ignore
     ///let deck_gen = DeckGenerator {
     ///    number0: 1,
     ///    numbers: 2,
@@ -73,12 +161,20 @@ impl DeckGenerator {
     ///    plus_2: 2,
     ///    plus_4: 4,
     ///    wild_card: 4,
+    ///    flip: 1,
+    ///    custom: vec![(effects::DISCARD_ALL, 1)],
     ///};
-    /// let deck: Deck = deck_gen.generate_deck();
+    /// let cards: Vec<CardInfo> = deck_gen.generate_deck();
     ///```
     /// This would create a deck with one 0 card in each color, 2 cards of every other number in each color,
-    /// two invert/block/+2 cards in each colors, 4 +4/jokers in wild (grey) color.
+    /// two invert/block/+2 cards in each colors, 4 +4/jokers in wild (grey) color, one flip card
+    /// and one discard all card in each color. The cards come out shuffled.
     pub fn generate_deck(self) -> Vec<CardInfo> {
+        self.generate_deck_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Same as [`DeckGenerator::generate_deck`] but shuffles with the given `rng`, allowing seeded decks
+    pub fn generate_deck_with_rng<R: Rng + ?Sized>(self, rng: &mut R) -> Vec<CardInfo> {
         let mut deck = Vec::new();
 
         // Add all colored 0 cards
@@ -100,9 +196,50 @@ impl DeckGenerator {
         deck.append(&mut add_wild_card(CardVariant::PlusFour, self.plus_4));
         // Add all wild cards
         deck.append(&mut add_wild_card(CardVariant::Wild, self.wild_card));
+        // Add all colored flip cards
+        deck.append(&mut add_colored_card(CardVariant::Flip, self.flip));
+        // Add all custom cards
+        self.custom.into_iter().for_each(|(id, number)| {
+            let variant = CardVariant::Custom(id);
+            match variant.custom_effect() {
+                Some(effect) if effect.is_wild() => {
+                    deck.append(&mut add_wild_card(variant, number))
+                }
+                _ => deck.append(&mut add_colored_card(variant, number)),
+            }
+        });
 
         // shuffle the deck using rand
-        deck.shuffle(&mut rand::thread_rng());
+        deck.shuffle(rng);
         deck
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod from_recipe {
+        use super::*;
+
+        #[test]
+        fn recipe_sizes() {
+            let size = |recipe| DeckGenerator::from(recipe).generate_deck().len();
+
+            assert_eq!(size(DeckRecipe::Classic), 108);
+            assert_eq!(size(DeckRecipe::Double), 216);
+            assert_eq!(size(DeckRecipe::NumbersOnly), 76);
+            assert_eq!(size(DeckRecipe::Flip), 116);
+            assert_eq!(size(DeckRecipe::Party), 116);
+        }
+
+        #[test]
+        fn numbers_only() {
+            let deck = DeckGenerator::from(DeckRecipe::NumbersOnly).generate_deck();
+
+            assert!(deck
+                .iter()
+                .all(|(_, variant)| matches!(variant, CardVariant::Number(_))));
+        }
+    }
+}
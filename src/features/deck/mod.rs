@@ -1,14 +1,25 @@
 pub mod generator;
 
 use bevy::prelude::*;
+use rand::{rngs::StdRng, SeedableRng};
 
 use crate::{
-    utils::mouse::{coordinates::UIMouseCoordinates, hover::Hoverable, Clickable, Hovered},
+    features::{
+        game::{
+            layout::DECK_POSITION, ApplyRequestsSet, DrawCardRequest, LocalPlayer, Player,
+            TableView,
+        },
+        rules::projection::SeenCard,
+    },
+    utils::{
+        mouse::{hover::Hoverable, Clickable, Clicked},
+        tooltip::Tooltip,
+    },
     CardBundle,
 };
 
 use crate::{CardColor, CardVariant};
-pub use generator::DeckGenerator;
+pub use generator::{DeckGenerator, DeckRecipe};
 
 pub type CardInfo = (CardColor, CardVariant);
 
@@ -21,250 +32,209 @@ impl Default for Deck {
     }
 }
 
-pub struct DeckPlugin;
+impl Deck {
+    /// Default deck shuffled from `seed`, the same seed always gives the same deck
+    pub fn from_seed(seed: u64) -> Self {
+        Self::from_recipe(DeckRecipe::Classic, seed)
+    }
 
-#[derive(Component)]
-struct InDeckMarker;
+    /// Deck of `recipe` shuffled from `seed`
+    pub fn from_recipe(recipe: DeckRecipe, seed: u64) -> Self {
+        Self(DeckGenerator::from(recipe).generate_deck_with_rng(&mut StdRng::seed_from_u64(seed)))
+    }
 
-#[derive(Component)]
-struct NodeDeckMarker;
+    pub fn into_cards(self) -> Vec<CardInfo> {
+        self.0
+    }
+}
+
+pub struct DeckPlugin;
 
 #[derive(Component)]
-struct TextDeckMarker;
+struct InDeckMarker;
 
 #[derive(Component)]
 struct DeckMarker;
 
-const DEFAULT_OFFSET: f32 = 15.;
-
 pub const CARD_BACK_PATH: &str = "cards/card_back/card_back.png";
 
 impl Plugin for DeckPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (fill_deck, spawn_deck_sprite))
-            .add_systems(
-                Update,
-                (
-                    show_deck_data.run_if(is_deck_hovered),
-                    hide_deck_data.run_if(not(is_deck_hovered)),
-                ),
-            );
+        app.add_systems(Startup, spawn_deck_sprite).add_systems(
+            Update,
+            (
+                fill_deck.run_if(resource_exists_and_changed::<TableView>),
+                draw_on_deck_click.before(ApplyRequestsSet),
+            ),
+        );
     }
 }
 
-// Spawn one entity invisible per card in the deck
-fn fill_deck(mut commands: Commands) {
-    let deck = Deck::default();
-
-    deck.0.into_iter().for_each(|(color, variant)| {
-        commands.spawn((CardBundle { color, variant }, InDeckMarker));
+// Spawn one entity invisible per card in the draw pile of the table, only face up cards know what they are
+fn fill_deck(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    deck_query: Query<Entity, With<InDeckMarker>>,
+) {
+    deck_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+
+    table_view.0.draw_pile.iter().for_each(|card| match card {
+        SeenCard::FaceUp((color, variant)) => {
+            commands.spawn((
+                CardBundle {
+                    color: *color,
+                    variant: *variant,
+                },
+                InDeckMarker,
+            ));
+        }
+        SeenCard::FaceDown => {
+            commands.spawn(InDeckMarker);
+        }
     });
 }
 
-/// Spawn a card back sprite representing the deck and an UI node containing text to show how many cards are left
+/// Spawn a card back sprite representing the deck, its tooltip shows how many cards are left
 fn spawn_deck_sprite(mut commands: Commands, asset_server: Res<AssetServer>) {
-    // UI node
-    commands
-        .spawn((
-            NodeBundle {
-                background_color: BackgroundColor(Color::DARK_GRAY),
-                border_color: BorderColor(Color::BLACK),
-                visibility: Visibility::Hidden,
-                ..default()
-            },
-            NodeDeckMarker,
-        ))
-        .with_children(|builder| {
-            builder.spawn((TextBundle::default(), TextDeckMarker));
-        });
-
     let texture = asset_server.load(CARD_BACK_PATH);
     // Card back
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(0., 300., 0.),
+            transform: Transform::from_translation(DECK_POSITION.extend(0.)),
             texture,
             ..default()
         },
         DeckMarker,
         Hoverable,
         Clickable,
+        Tooltip::new(deck_tooltip_text),
     ));
 }
 
-fn is_deck_hovered(deck_hovered_query: Query<(), (With<DeckMarker>, With<Hovered>)>) -> bool {
-    deck_hovered_query.iter().count() == 1
-}
-
-/// Make the node showing how many cards left in deck and update it's style position, update text inside node
-fn show_deck_data(
-    mut node_query: Query<(&mut Visibility, &mut Style), With<NodeDeckMarker>>,
-    mut text_query: Query<&mut Text, With<TextDeckMarker>>,
-    ui_mouse_coordinates: Res<UIMouseCoordinates>,
-    deck_query: Query<(), With<InDeckMarker>>,
+/// Ask to draw a card for the local player when the deck gets clicked
+fn draw_on_deck_click(
+    deck_query: Query<(), (With<DeckMarker>, Added<Clicked>)>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut draw_writer: EventWriter<DrawCardRequest>,
 ) {
-    let (mut visibility, mut style) = node_query.single_mut();
-    let mut text = text_query.single_mut();
-
-    let len = deck_query.iter().count();
-
-    let UIMouseCoordinates(Vec2 { x, y }) = ui_mouse_coordinates.into_inner();
-    style.left = Val::Px(*x + DEFAULT_OFFSET);
-    style.top = Val::Px(*y);
-
-    *text = Text::from_section(format!("cards remaining: {}", len), TextStyle::default());
-    *visibility = Visibility::Visible;
+    if let (false, Ok(Player { seat })) = (deck_query.is_empty(), local_query.get_single()) {
+        draw_writer.send(DrawCardRequest { seat: *seat });
+    }
 }
 
-/// Hides the node showing how many cards left in deck
-fn hide_deck_data(mut node_query: Query<&mut Visibility, With<NodeDeckMarker>>) {
-    let mut visibility = node_query.single_mut();
+/// Text of the deck tooltip: how many cards are left in deck
+fn deck_tooltip_text(_: Entity, world: &World) -> String {
+    let len = world
+        .iter_entities()
+        .filter(|entity| entity.contains::<InDeckMarker>())
+        .count();
 
-    *visibility = Visibility::Hidden;
+    format!("cards remaining: {}", len)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::test::count_entities::*;
+    use crate::{features::rules::GameState, utils::test::count_entities::*};
 
     mod fill_deck {
         use super::*;
 
         #[test]
         fn spawned_all_entities() {
-            let deck_size = Deck::default().0.len();
+            let state = GameState::from_seed(2, 0);
+            let deck_size = state.draw_pile.len();
 
             let mut app = App::new();
 
-            app.add_systems(Startup, fill_deck)
+            app.insert_resource(TableView(state.view_for(Some(0))))
+                .add_systems(Startup, fill_deck)
                 .add_systems(Update, count_entities::<InDeckMarker>)
                 .init_resource::<EntityCount>();
 
             app.update();
 
+            let identified = app
+                .world
+                .query_filtered::<(), With<CardColor>>()
+                .iter(&app.world)
+                .count();
             let entities_count = app.world.resource::<EntityCount>();
 
             assert_eq!(entities_count.0, deck_size);
+            assert_eq!(identified, 0);
         }
-    }
-
-    mod is_deck_hovered {
-        use super::*;
-
-        #[derive(Resource, Default, Debug)]
-        struct Test(u8);
 
         #[test]
-        fn deck_hovered() {
+        fn replaced_entities_on_refill() {
             let mut app = App::new();
 
-            app.world.spawn((Hovered, DeckMarker));
-
-            app.add_systems(
-                Update,
-                (|mut test_resource: ResMut<Test>| test_resource.0 += 1).run_if(is_deck_hovered),
-            )
-            .init_resource::<Test>();
+            app.insert_resource(TableView(GameState::from_seed(2, 0).view_for(Some(0))))
+                .add_systems(Update, (fill_deck, count_entities::<InDeckMarker>).chain())
+                .init_resource::<EntityCount>();
 
             app.update();
-
-            let Test(value) = app.world.resource::<Test>();
-
-            assert_eq!(*value, 1);
-        }
-
-        #[test]
-        fn deck_not_hovered() {
-            let mut app = App::new();
-
-            app.world.spawn(DeckMarker);
-
-            app.add_systems(
-                Update,
-                (|mut test_resource: ResMut<Test>| test_resource.0 += 1).run_if(is_deck_hovered),
-            )
-            .init_resource::<Test>();
-
+            app.world
+                .resource_mut::<TableView>()
+                .0
+                .draw_pile
+                .truncate(3);
+            app.update();
             app.update();
 
-            let Test(value) = app.world.resource::<Test>();
+            let entities_count = app.world.resource::<EntityCount>();
 
-            assert_eq!(*value, 0);
+            assert_eq!(entities_count.0, 3);
         }
     }
 
-    mod show_deck_data {
+    mod draw_on_deck_click {
         use super::*;
 
         #[test]
-        fn set_visibility_style_and_text() {
+        fn sends_request_for_local_player() {
             let mut app = App::new();
 
-            app.add_systems(Update, show_deck_data)
-                .init_resource::<UIMouseCoordinates>();
-
-            let node = app
-                .world
-                .spawn((
-                    NodeBundle {
-                        visibility: Visibility::Hidden,
-                        ..default()
-                    },
-                    NodeDeckMarker,
-                ))
-                .id();
-            let text = app
-                .world
-                .spawn((TextBundle::default(), TextDeckMarker))
-                .id();
-            app.world.entity_mut(node).add_child(text);
+            app.add_event::<DrawCardRequest>()
+                .add_systems(Update, draw_on_deck_click);
+            app.world.spawn((Player { seat: 2 }, LocalPlayer));
+            app.world.spawn((DeckMarker, Clicked));
 
             app.update();
 
-            let text = app
+            let requests = app
                 .world
-                .entity(text)
-                .get::<Text>()
-                .and_then(|text| text.sections.first().map(|section| section.value.clone()))
-                .unwrap();
-            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
-            let style = app.world.entity(node).get::<Style>().unwrap();
-
-            assert_eq!(visibility, Visibility::Visible);
-            assert_eq!(text, "cards remaining: 0".to_string());
-            assert_eq!(
-                (style.top, style.left),
-                (Val::Px(0.), Val::Px(DEFAULT_OFFSET))
-            );
+                .resource_mut::<Events<DrawCardRequest>>()
+                .drain()
+                .collect::<Vec<_>>();
+
+            assert_eq!(requests, vec![DrawCardRequest { seat: 2 }]);
         }
     }
 
-    mod hide_deck_data {
+    mod deck_tooltip_text {
         use super::*;
 
         #[test]
-        fn set_visibility() {
-            let mut app = App::new();
+        fn counts_cards_in_deck() {
+            let state = GameState::from_seed(2, 0);
+            let deck_size = state.draw_pile.len();
 
-            app.add_systems(Update, hide_deck_data);
+            let mut app = App::new();
 
-            let node = app
-                .world
-                .spawn((
-                    NodeBundle {
-                        visibility: Visibility::Visible,
-                        ..default()
-                    },
-                    NodeDeckMarker,
-                ))
-                .id();
+            app.insert_resource(TableView(state.view_for(Some(0))))
+                .add_systems(Startup, fill_deck);
 
             app.update();
 
-            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
+            let deck = app.world.spawn(DeckMarker).id();
 
-            assert_eq!(visibility, Visibility::Hidden);
+            assert_eq!(
+                deck_tooltip_text(deck, &app.world),
+                format!("cards remaining: {}", deck_size)
+            );
         }
     }
 }
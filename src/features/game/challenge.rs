@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+
+use super::{ChallengeRequest, DrawCardRequest, LocalPlayer, Player, TableView};
+use crate::features::rules::{challenge::PlusFourChallenge, projection::PlayerView, Seat};
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum ChallengeButton {
+    Challenge,
+    Accept,
+}
+
+#[derive(Component)]
+struct ChallengePromptMarker;
+
+pub struct ChallengePlugin;
+
+/// This plugin asks the local player whether to challenge a +4 played on it
+impl Plugin for ChallengePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_challenge_prompt.run_if(resource_exists_and_changed::<TableView>),
+                press_challenge_buttons,
+            ),
+        );
+    }
+}
+
+/// The +4 `seat` has to answer in `view`
+fn pending_challenge(view: &PlayerView, seat: Seat) -> Option<PlusFourChallenge> {
+    view.plus_four
+        .filter(|challenge| challenge.victim == seat && view.current == seat)
+}
+
+fn show_challenge_prompt(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    prompt_query: Query<Entity, With<ChallengePromptMarker>>,
+) {
+    prompt_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let Some(challenge) = local_query
+        .get_single()
+        .ok()
+        .and_then(|player| pending_challenge(&table_view.0, player.seat))
+    else {
+        return;
+    };
+    let color: String = challenge.previous_color.into();
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    top: Val::Percent(30.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                z_index: ZIndex::Global(40),
+                ..default()
+            },
+            ChallengePromptMarker,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                format!(
+                    "Player {} played a +4 on {}: challenge it if you think they had {} cards",
+                    challenge.offender + 1,
+                    color,
+                    color
+                ),
+                TextStyle::default(),
+            ));
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(10.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|builder| {
+                    for (button, label) in [
+                        (ChallengeButton::Challenge, "Challenge (draw 6 if wrong)"),
+                        (ChallengeButton::Accept, "Draw 4"),
+                    ] {
+                        builder
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        padding: UiRect::all(Val::Px(10.)),
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(Color::DARK_GRAY),
+                                    ..default()
+                                },
+                                button,
+                            ))
+                            .with_children(|builder| {
+                                builder
+                                    .spawn(TextBundle::from_section(label, TextStyle::default()));
+                            });
+                    }
+                });
+        });
+}
+
+fn press_challenge_buttons(
+    buttons_query: Query<(&Interaction, &ChallengeButton), Changed<Interaction>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut challenge_writer: EventWriter<ChallengeRequest>,
+    mut draw_writer: EventWriter<DrawCardRequest>,
+) {
+    let Ok(&Player { seat }) = local_query.get_single() else {
+        return;
+    };
+
+    for (_, button) in buttons_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        match button {
+            ChallengeButton::Challenge => {
+                challenge_writer.send(ChallengeRequest { seat });
+            }
+            ChallengeButton::Accept => {
+                draw_writer.send(DrawCardRequest { seat });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{Action, GameState},
+        CardColor, CardVariant,
+    };
+
+    mod pending_challenge {
+        use super::*;
+
+        #[test]
+        fn only_for_the_victim() {
+            let mut state = GameState::from_seed(3, 0);
+            state.discard_pile = vec![(CardColor::Red, CardVariant::Number(5))];
+            state.hands[0][0] = (CardColor::Wild, CardVariant::PlusFour);
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: Some(CardColor::Blue),
+                    },
+                )
+                .unwrap();
+
+            assert!(pending_challenge(&state.view_for(Some(1)), 1).is_some());
+            assert!(pending_challenge(&state.view_for(Some(2)), 2).is_none());
+            state.apply(1, Action::Draw).unwrap();
+            assert!(pending_challenge(&state.view_for(Some(1)), 1).is_none());
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use super::{LocalPlayer, PassRequest, Player, TableView};
+
+/// Key keeping the playable card just drawn
+const PASS_KEY: KeyCode = KeyCode::KeyP;
+
+#[derive(Component)]
+struct PassButton;
+
+#[derive(Component)]
+struct PassButtonMarker;
+
+pub struct DrawingPlugin;
+
+/// This plugin lets the local player keep a playable card it drew instead of playing it
+impl Plugin for DrawingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_pass_button.run_if(resource_exists_and_changed::<TableView>),
+                press_pass_button.run_if(resource_exists::<TableView>),
+            ),
+        );
+    }
+}
+
+fn show_pass_button(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    buttons_query: Query<Entity, With<PassButtonMarker>>,
+) {
+    buttons_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let Ok(player) = local_query.get_single() else {
+        return;
+    };
+    if !table_view.0.can_pass(player.seat) {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.),
+                    bottom: Val::Px(20.),
+                    ..default()
+                },
+                ..default()
+            },
+            PassButtonMarker,
+        ))
+        .with_children(|builder| {
+            builder
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(10.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::DARK_GRAY),
+                        ..default()
+                    },
+                    PassButton,
+                ))
+                .with_children(|builder| {
+                    builder.spawn(TextBundle::from_section(
+                        "Keep it and pass (P)",
+                        TextStyle::default(),
+                    ));
+                });
+        });
+}
+
+fn press_pass_button(
+    table_view: Res<TableView>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    buttons_query: Query<&Interaction, (Changed<Interaction>, With<PassButton>)>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut pass_writer: EventWriter<PassRequest>,
+) {
+    let Ok(&Player { seat }) = local_query.get_single() else {
+        return;
+    };
+
+    let clicked = buttons_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed);
+    let typed = keys.is_some_and(|keys| keys.just_pressed(PASS_KEY));
+    if clicked || (typed && table_view.0.can_pass(seat)) {
+        pass_writer.send(PassRequest { seat });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{drawing::DrawRule, Action, GameState, HouseRules},
+        CardColor, CardVariant,
+    };
+
+    mod press_pass_button {
+        use super::*;
+
+        fn setup_app(draw: DrawRule) -> App {
+            let mut state = GameState::with_rules(
+                crate::features::deck::Deck::default().into_cards(),
+                2,
+                0,
+                HouseRules {
+                    draw,
+                    ..HouseRules::default()
+                },
+            );
+            state.discard_pile = vec![(CardColor::Red, CardVariant::Number(5))];
+            state.active_color = CardColor::Red;
+            state.draw_pile = vec![(CardColor::Red, CardVariant::Number(9))];
+            state.apply(0, Action::Draw).unwrap();
+
+            let mut app = App::new();
+            let mut keys = ButtonInput::<KeyCode>::default();
+            keys.press(PASS_KEY);
+            app.add_event::<PassRequest>()
+                .insert_resource(keys)
+                .insert_resource(TableView(state.view_for(Some(0))))
+                .add_systems(Update, press_pass_button);
+            app.world.spawn((Player { seat: 0 }, LocalPlayer));
+
+            app
+        }
+
+        fn requests(app: &App) -> usize {
+            app.world.resource::<Events<PassRequest>>().len()
+        }
+
+        #[test]
+        fn key_keeps_a_drawn_card() {
+            let mut app = setup_app(DrawRule::UntilPlayable);
+
+            app.update();
+
+            assert_eq!(requests(&app), 1);
+        }
+
+        #[test]
+        fn nothing_to_keep() {
+            let mut app = setup_app(DrawRule::DrawOne);
+
+            app.update();
+
+            assert_eq!(requests(&app), 0);
+        }
+    }
+}
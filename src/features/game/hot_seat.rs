@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+
+use super::{project_table, ApplyRequestsSet, LocalPlayer, Player, Table};
+use crate::features::rules::Seat;
+
+/// ## Several humans sharing one screen
+///
+/// Every hand stays face down until the player whose turn it is lifts the curtain,
+/// only then does it become the [`LocalPlayer`] with its hand face up and clickable.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct HotSeat {
+    pub humans: Vec<Seat>,
+    /// Seat whose hand is shown, `None` while the curtain is down
+    pub revealed: Option<Seat>,
+}
+
+impl HotSeat {
+    pub fn new(humans: Vec<Seat>) -> Self {
+        Self {
+            humans,
+            revealed: None,
+        }
+    }
+}
+
+#[derive(Component)]
+struct CurtainMarker;
+
+#[derive(Component)]
+struct CurtainButton;
+
+pub struct HotSeatPlugin;
+
+/// This plugin hides the hands between the turns of the humans of a [`HotSeat`] game
+impl Plugin for HotSeatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                lift_curtain,
+                hide_between_turns,
+                show_curtain.run_if(
+                    resource_changed::<HotSeat>.or_else(resource_exists_and_changed::<Table>),
+                ),
+            )
+                .chain()
+                .after(ApplyRequestsSet)
+                .before(project_table)
+                .run_if(resource_exists::<HotSeat>.and_then(resource_exists::<Table>)),
+        )
+        .add_systems(Update, remove_curtain.run_if(resource_removed::<HotSeat>()));
+    }
+}
+
+/// A game without hot seat was loaded, nobody needs to pass the device anymore
+fn remove_curtain(mut commands: Commands, curtain_query: Query<Entity, With<CurtainMarker>>) {
+    curtain_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+}
+
+/// The turn moved on, hide the hand that was shown
+fn hide_between_turns(
+    mut commands: Commands,
+    table: Res<Table>,
+    mut hot_seat: ResMut<HotSeat>,
+    local_query: Query<Entity, With<LocalPlayer>>,
+) {
+    if hot_seat
+        .revealed
+        .is_some_and(|seat| seat != table.0.current || table.0.is_over())
+    {
+        hot_seat.revealed = None;
+        local_query.iter().for_each(|entity| {
+            commands.entity(entity).remove::<LocalPlayer>();
+        });
+    }
+}
+
+/// Cover the table with a "pass the device" screen while a human has to play with its hand hidden
+fn show_curtain(
+    mut commands: Commands,
+    table: Res<Table>,
+    hot_seat: Res<HotSeat>,
+    curtain_query: Query<Entity, With<CurtainMarker>>,
+) {
+    curtain_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let seat = table.0.current;
+    if hot_seat.revealed.is_some() || table.0.is_over() || !hot_seat.humans.contains(&seat) {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(20.),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::BLACK),
+                z_index: ZIndex::Global(100),
+                ..default()
+            },
+            CurtainMarker,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                format!("Pass the device to player {}", seat + 1),
+                TextStyle::default(),
+            ));
+            builder
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(10.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::DARK_GRAY),
+                        ..default()
+                    },
+                    CurtainButton,
+                ))
+                .with_children(|builder| {
+                    builder.spawn(TextBundle::from_section(
+                        "Show my hand",
+                        TextStyle::default(),
+                    ));
+                });
+        });
+}
+
+/// The player whose turn it is took the device, show its hand
+fn lift_curtain(
+    mut commands: Commands,
+    table: Res<Table>,
+    mut hot_seat: ResMut<HotSeat>,
+    buttons_query: Query<&Interaction, (With<CurtainButton>, Changed<Interaction>)>,
+    players_query: Query<(Entity, &Player)>,
+) {
+    if !buttons_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+
+    let seat = table.0.current;
+    hot_seat.revealed = Some(seat);
+    players_query
+        .iter()
+        .filter(|(_, player)| player.seat == seat)
+        .for_each(|(entity, _)| {
+            commands.entity(entity).insert(LocalPlayer);
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{
+        game::TableView,
+        rules::{projection::SeenCard, Action, GameState},
+    };
+
+    fn setup_app() -> App {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(HotSeatPlugin)
+            .insert_resource(Table(GameState::from_seed(2, 0)))
+            .insert_resource(HotSeat::new(vec![0, 1]))
+            .add_systems(
+                Update,
+                project_table.run_if(
+                    resource_exists_and_changed::<Table>
+                        .or_else(resource_exists_and_changed::<HotSeat>),
+                ),
+            );
+        app.world.spawn(Player { seat: 0 });
+        app.world.spawn(Player { seat: 1 });
+
+        app
+    }
+
+    fn local_seat(app: &mut App) -> Option<Seat> {
+        app.world
+            .query_filtered::<&Player, With<LocalPlayer>>()
+            .get_single(&app.world)
+            .ok()
+            .map(|player| player.seat)
+    }
+
+    fn curtains(app: &mut App) -> usize {
+        app.world
+            .query_filtered::<(), With<CurtainMarker>>()
+            .iter(&app.world)
+            .count()
+    }
+
+    fn press_curtain_button(app: &mut App) {
+        let button = app
+            .world
+            .query_filtered::<Entity, With<CurtainButton>>()
+            .single(&app.world);
+        app.world.entity_mut(button).insert(Interaction::Pressed);
+    }
+
+    mod lift_curtain {
+        use super::*;
+
+        #[test]
+        fn shows_only_current_hand() {
+            let mut app = setup_app();
+
+            app.update();
+
+            assert_eq!(curtains(&mut app), 1);
+            assert_eq!(local_seat(&mut app), None);
+            assert!(app.world.resource::<TableView>().0.hand(0).is_empty());
+
+            press_curtain_button(&mut app);
+            app.update();
+
+            let view = &app.world.resource::<TableView>().0;
+            assert!(!view.hand(0).is_empty());
+            assert!(view.hands[1].iter().all(|card| *card == SeenCard::FaceDown));
+            assert_eq!(local_seat(&mut app), Some(0));
+            assert_eq!(curtains(&mut app), 0);
+        }
+    }
+
+    mod hide_between_turns {
+        use super::*;
+
+        #[test]
+        fn curtain_for_next_player() {
+            let mut app = setup_app();
+            app.update();
+            press_curtain_button(&mut app);
+            app.update();
+
+            app.world
+                .resource_mut::<Table>()
+                .0
+                .apply(0, Action::Draw)
+                .unwrap();
+            app.update();
+
+            assert_eq!(local_seat(&mut app), None);
+            assert_eq!(app.world.resource::<HotSeat>().revealed, None);
+            assert_eq!(curtains(&mut app), 1);
+            assert!(app.world.resource::<TableView>().0.hand(0).is_empty());
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+
+use crate::{utils::mouse::Clicked, CardColor};
+
+use super::{view::InHand, ApplyRequestsSet, LocalPlayer, PlayCardRequest, Player, TableView};
+
+/// Index of the wild card of the local player waiting for a color before being played
+#[derive(Resource, Debug, Default)]
+pub struct PendingWild(pub Option<usize>);
+
+#[derive(Component)]
+struct ColorPickerMarker;
+
+#[derive(Component)]
+struct ColorButton(CardColor);
+
+pub struct InputPlugin;
+
+/// This plugin turns the local player clicks into play requests, asking for a color when a wild card is played
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PendingWild>().add_systems(
+            Update,
+            (
+                cancel_pending_wild.run_if(resource_exists_and_changed::<TableView>),
+                (play_clicked_card, pick_color).run_if(resource_exists::<TableView>),
+                show_color_picker.run_if(resource_changed::<PendingWild>),
+            )
+                .chain()
+                .before(ApplyRequestsSet),
+        );
+    }
+}
+
+pub fn ui_color(color: CardColor) -> Color {
+    match color {
+        CardColor::Yellow => Color::YELLOW,
+        CardColor::Red => Color::RED,
+        CardColor::Blue => Color::BLUE,
+        CardColor::Green => Color::GREEN,
+        CardColor::Wild => Color::DARK_GRAY,
+        CardColor::Pink => Color::rgb(0.9, 0., 0.5),
+        CardColor::Teal => Color::rgb(0., 0.6, 0.6),
+        CardColor::Orange => Color::ORANGE,
+        CardColor::Purple => Color::PURPLE,
+    }
+}
+
+/// Send a play request for the clicked card of the local player, overlapping cards resolve to the one on top
+fn play_clicked_card(
+    clicked_query: Query<&InHand, Added<Clicked>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    table_view: Res<TableView>,
+    mut pending_wild: ResMut<PendingWild>,
+    mut play_writer: EventWriter<PlayCardRequest>,
+) {
+    let Ok(Player { seat }) = local_query.get_single() else {
+        return;
+    };
+    let Some(InHand { index, .. }) = clicked_query
+        .iter()
+        .filter(|card| card.seat == *seat)
+        .max_by_key(|card| card.index)
+    else {
+        return;
+    };
+
+    match table_view.0.hand(*seat).get(*index) {
+        Some((CardColor::Wild, _)) => pending_wild.0 = Some(*index),
+        Some(_) => {
+            play_writer.send(PlayCardRequest {
+                seat: *seat,
+                card: *index,
+                color: None,
+            });
+        }
+        None => {}
+    }
+}
+
+/// Forget the pending wild card as soon as the table changes since hand indexes may have moved
+fn cancel_pending_wild(mut pending_wild: ResMut<PendingWild>) {
+    if pending_wild.0.is_some() {
+        pending_wild.0 = None;
+    }
+}
+
+/// Show one button per color while a wild card is pending, hide them otherwise
+fn show_color_picker(
+    mut commands: Commands,
+    pending_wild: Res<PendingWild>,
+    table_view: Option<Res<TableView>>,
+    picker_query: Query<Entity, With<ColorPickerMarker>>,
+) {
+    picker_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let Some(table_view) = table_view.filter(|_| pending_wild.0.is_some()) else {
+        return;
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    bottom: Val::Percent(40.),
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            ColorPickerMarker,
+        ))
+        .with_children(|builder| {
+            for color in table_view.0.side.colors() {
+                builder.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(60.),
+                            height: Val::Px(60.),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(ui_color(color)),
+                        ..default()
+                    },
+                    ColorButton(color),
+                ));
+            }
+        });
+}
+
+/// Play the pending wild card with the color of the pressed button
+fn pick_color(
+    buttons_query: Query<(&Interaction, &ColorButton), Changed<Interaction>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut pending_wild: ResMut<PendingWild>,
+    mut play_writer: EventWriter<PlayCardRequest>,
+) {
+    let (Some(card), Ok(Player { seat })) = (pending_wild.0, local_query.get_single()) else {
+        return;
+    };
+
+    if let Some((_, ColorButton(color))) = buttons_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        play_writer.send(PlayCardRequest {
+            seat: *seat,
+            card,
+            color: Some(*color),
+        });
+        pending_wild.0 = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{deck::CardInfo, rules::GameState},
+        CardVariant,
+    };
+
+    const WILD: CardInfo = (CardColor::Wild, CardVariant::Wild);
+    const RED_1: CardInfo = (CardColor::Red, CardVariant::Number(1));
+
+    fn setup_app(hand: Vec<CardInfo>) -> App {
+        let mut app = App::new();
+
+        let mut state = GameState::from_seed(2, 0);
+        state.hands[0] = hand;
+
+        app.add_event::<PlayCardRequest>()
+            .init_resource::<PendingWild>()
+            .insert_resource(TableView(state.view_for(Some(0))))
+            .add_systems(Update, play_clicked_card);
+        app.world.spawn((Player { seat: 0 }, LocalPlayer));
+
+        app
+    }
+
+    mod play_clicked_card {
+        use super::*;
+
+        #[test]
+        fn sends_request_for_top_card() {
+            let mut app = setup_app(vec![RED_1, RED_1]);
+
+            app.world.spawn_batch([
+                (InHand { seat: 0, index: 0 }, Clicked),
+                (InHand { seat: 0, index: 1 }, Clicked),
+            ]);
+
+            app.update();
+
+            let requests = app
+                .world
+                .resource_mut::<Events<PlayCardRequest>>()
+                .drain()
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                requests,
+                vec![PlayCardRequest {
+                    seat: 0,
+                    card: 1,
+                    color: None
+                }]
+            );
+        }
+
+        #[test]
+        fn wild_waits_for_color() {
+            let mut app = setup_app(vec![WILD]);
+
+            app.world.spawn((InHand { seat: 0, index: 0 }, Clicked));
+
+            app.update();
+
+            let requests = app.world.resource::<Events<PlayCardRequest>>();
+            let pending_wild = app.world.resource::<PendingWild>();
+
+            assert!(requests.is_empty());
+            assert_eq!(pending_wild.0, Some(0));
+        }
+
+        #[test]
+        fn opponent_card_ignored() {
+            let mut app = setup_app(vec![RED_1]);
+
+            app.world.spawn((InHand { seat: 1, index: 0 }, Clicked));
+
+            app.update();
+
+            let requests = app.world.resource::<Events<PlayCardRequest>>();
+
+            assert!(requests.is_empty());
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::{prelude::*, render::camera::ScalingMode, window::PrimaryWindow};
+
+use crate::features::rules::Seat;
+
+/// Part of the table always in view whatever the window, everything is placed in its units
+pub const DESIGN_SIZE: Vec2 = Vec2::new(1280., 720.);
+/// Size of the card textures
+pub const CARD_SIZE: Vec2 = Vec2::new(164., 255.);
+pub const DECK_POSITION: Vec2 = Vec2::new(-100., 0.);
+pub const DISCARD_PILE_POSITION: Vec2 = Vec2::new(100., 0.);
+
+/// Room kept between the local hand and the bottom of the window
+const LOCAL_HAND_MARGIN: f32 = 20.;
+/// Part of the window width the local hand may cover
+const LOCAL_HAND_WIDTH: f32 = 0.8;
+const LOCAL_CARD_SPACING: f32 = 110.;
+/// Opponent hands are smaller than the local one
+const OPPONENT_SCALE: f32 = 0.5;
+/// Angle left free at the bottom of the table for the local hand
+const LOCAL_GAP: f32 = 0.7;
+/// Cards of a fan turn around a point this far behind the hand
+const FAN_RADIUS: f32 = 250.;
+/// Angle between two cards of a fan, until the fan is as open as it gets
+const FAN_STEP: f32 = 0.08;
+const FAN_MAX_ANGLE: f32 = 1.4;
+
+/// ## Where everything sits on the table
+///
+/// Seats go clockwise around an ellipse fitting the window, the local player at the bottom
+/// with its hand face up in a row and every opponent holding a fan opened with its card count.
+/// Spectators see every seat as a fan, the first one at the bottom. The table grows past
+/// [`DESIGN_SIZE`] on the longer side of the window, see [`table_camera`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TableLayout {
+    /// Visible part of the table, in world units
+    pub size: Vec2,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        Self { size: DESIGN_SIZE }
+    }
+}
+
+impl TableLayout {
+    /// Fans stay inside the window
+    fn radii(&self) -> Vec2 {
+        (self.size / 2. - CARD_SIZE * OPPONENT_SCALE).max(Vec2::ZERO)
+    }
+
+    /// Center of the hand of `seat`, the local hand is centered at the bottom of the window
+    pub fn seat_anchor(&self, seat: Seat, local_seat: Option<Seat>, players: usize) -> Vec2 {
+        let bottom = local_seat.unwrap_or(0);
+        let rank = (seat + players - bottom) % players;
+
+        let angle = match local_seat {
+            Some(_) if rank == 0 => {
+                return Vec2::new(0., -self.size.y / 2. + CARD_SIZE.y / 2. + LOCAL_HAND_MARGIN)
+            }
+            // Opponents share the ellipse but its bottom, half a share from each end
+            Some(_) => {
+                let share = (TAU - 2. * LOCAL_GAP) / (players - 1) as f32;
+                -FRAC_PI_2 - LOCAL_GAP - share * (rank as f32 - 0.5)
+            }
+            None => -FRAC_PI_2 - TAU * rank as f32 / players as f32,
+        };
+
+        Vec2::new(angle.cos(), angle.sin()) * self.radii()
+    }
+
+    /// Place of every card of a hand of `len` cards held at `anchor`
+    pub fn hand_transforms(&self, anchor: Vec2, len: usize, local: bool) -> Vec<Transform> {
+        if local {
+            let width = self.size.x * LOCAL_HAND_WIDTH;
+            let spacing = (width / len.max(1) as f32).min(LOCAL_CARD_SPACING);
+
+            return (0..len)
+                .map(|index| {
+                    let offset = index as f32 - len.saturating_sub(1) as f32 / 2.;
+                    Transform::from_xyz(anchor.x + offset * spacing, anchor.y, card_z(index))
+                })
+                .collect();
+        }
+
+        // The fan opens toward the middle of the table and cards face it
+        let toward_middle = (-anchor).try_normalize().unwrap_or(Vec2::Y);
+        let facing = Vec2::Y.angle_between(toward_middle);
+        let pivot = anchor - toward_middle * FAN_RADIUS;
+        let step = FAN_STEP.min(FAN_MAX_ANGLE / len.max(1) as f32);
+
+        (0..len)
+            .map(|index| {
+                let tilt = -(index as f32 - len.saturating_sub(1) as f32 / 2.) * step;
+                let position = pivot + Vec2::from_angle(tilt).rotate(toward_middle) * FAN_RADIUS;
+
+                Transform::from_translation(position.extend(card_z(index)))
+                    .with_rotation(Quat::from_rotation_z(facing + tilt))
+                    .with_scale(Vec3::splat(OPPONENT_SCALE))
+            })
+            .collect()
+    }
+}
+
+/// Cards further in a hand show on top of the previous ones
+fn card_z(index: usize) -> f32 {
+    1. + index as f32 * 0.01
+}
+
+/// The camera scales the table so that [`DESIGN_SIZE`] fits the window, cards follow the window
+/// size and the density of its pixels
+pub fn table_camera() -> Camera2dBundle {
+    let mut camera = Camera2dBundle::default();
+    camera.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: DESIGN_SIZE.x,
+        min_height: DESIGN_SIZE.y,
+    };
+
+    camera
+}
+
+/// Part of the table seen through [`table_camera`] in a window of `window_size`
+pub fn visible_size(window_size: Vec2) -> Vec2 {
+    window_size * (DESIGN_SIZE / window_size).max_element()
+}
+
+/// The table covers the whole window
+pub(super) fn fit_layout_to_window(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut layout: ResMut<TableLayout>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+
+    // A minimized window has no size
+    if window_size.cmpgt(Vec2::ZERO).all() {
+        layout.set_if_neq(TableLayout {
+            size: visible_size(window_size),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAYOUT: TableLayout = TableLayout {
+        size: Vec2::new(1920., 1080.),
+    };
+
+    fn inside(position: Vec2) -> bool {
+        position.abs().cmple(LAYOUT.size / 2.).all()
+    }
+
+    mod seat_anchor {
+        use super::*;
+
+        #[test]
+        fn local_player_at_the_bottom() {
+            let local = LAYOUT.seat_anchor(2, Some(2), 3);
+            let next = LAYOUT.seat_anchor(0, Some(2), 3);
+            let last = LAYOUT.seat_anchor(1, Some(2), 3);
+
+            assert_eq!(local.x, 0.);
+            assert!(local.y < -LAYOUT.size.y / 4.);
+            // Play goes clockwise: the next player sits on the left
+            assert!(next.x < 0. && next.y > 0.);
+            assert!(last.x > 0. && last.y > 0.);
+        }
+
+        #[test]
+        fn single_opponent_on_top() {
+            let opponent = LAYOUT.seat_anchor(1, Some(0), 2);
+
+            assert!(opponent.x.abs() < 0.01);
+            assert!(opponent.y > 0.);
+        }
+
+        #[test]
+        fn every_seat_count_fits() {
+            for players in 2..=10 {
+                for local_seat in [Some(0), None] {
+                    let anchors = (0..players)
+                        .map(|seat| LAYOUT.seat_anchor(seat, local_seat, players))
+                        .collect::<Vec<_>>();
+
+                    assert!(anchors.iter().all(|anchor| inside(*anchor)));
+                    for (index, anchor) in anchors.iter().enumerate() {
+                        assert!(anchors[index + 1..]
+                            .iter()
+                            .all(|other| anchor.distance(*other) > CARD_SIZE.x * OPPONENT_SCALE));
+                    }
+                }
+            }
+        }
+    }
+
+    mod visible_size {
+        use super::*;
+
+        #[test]
+        fn design_size_always_fits() {
+            let close = |window: Vec2, size: Vec2| visible_size(window).distance(size) < 0.01;
+
+            assert!(close(DESIGN_SIZE * 1.5, DESIGN_SIZE));
+            assert!(close(Vec2::new(2560., 1080.), Vec2::new(1706.667, 720.)));
+            assert!(close(Vec2::new(800., 800.), Vec2::new(1280., 1280.)));
+        }
+    }
+
+    mod hand_transforms {
+        use super::*;
+
+        #[test]
+        fn local_hand_in_a_row() {
+            let cards = LAYOUT.hand_transforms(Vec2::ZERO, 3, true);
+
+            assert_eq!(
+                cards
+                    .iter()
+                    .map(|card| card.translation.x)
+                    .collect::<Vec<_>>(),
+                vec![-LOCAL_CARD_SPACING, 0., LOCAL_CARD_SPACING]
+            );
+            assert!(cards[0].translation.z < cards[2].translation.z);
+        }
+
+        #[test]
+        fn fans_open_with_the_card_count() {
+            let anchor = LAYOUT.seat_anchor(1, Some(0), 2);
+            let spread = |len| {
+                let cards = LAYOUT.hand_transforms(anchor, len, false);
+                cards[0].translation.distance(cards[len - 1].translation)
+            };
+
+            assert!(spread(3) < spread(7));
+            assert!(spread(7) < spread(20));
+        }
+
+        #[test]
+        fn fans_face_the_middle() {
+            let anchor = LAYOUT.seat_anchor(1, Some(0), 2);
+            let cards = LAYOUT.hand_transforms(anchor, 1, false);
+
+            // The opponent across the table holds its cards upside down
+            let up = cards[0].rotation * Vec3::Y;
+            assert!((up - Vec3::NEG_Y).length() < 0.01);
+            assert!((cards[0].translation.truncate() - anchor).length() < 0.01);
+        }
+    }
+}
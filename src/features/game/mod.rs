@@ -0,0 +1,695 @@
+pub mod challenge;
+pub mod drawing;
+pub mod hot_seat;
+pub mod input;
+pub mod layout;
+pub mod seven_o;
+pub mod summary;
+pub mod turn_timer;
+pub mod uno;
+pub mod view;
+
+use std::str::FromStr;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    features::{
+        ai::Difficulty,
+        deck::{Deck, DeckRecipe},
+        lobby::{MAX_SEATS, MIN_SEATS},
+        network::client::ServerAddress,
+        replay::{viewer::ReplayViewer, Recording, Replay},
+        rules::{projection::PlayerView, Action, GameEvent, GameState, HouseRules, Seat},
+    },
+    CardColor,
+};
+
+/// Online games start in the lobby until the host starts the game, local games are played right away
+#[derive(States, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AppState {
+    #[default]
+    Lobby,
+    Playing,
+}
+
+/// The game being played, every change goes through [`PlayCardRequest`] and [`DrawCardRequest`]
+#[derive(Resource, Debug, Clone)]
+pub struct Table(pub GameState);
+
+/// What the local player is allowed to see of the game, sprites are only spawned from it
+/// so hidden cards never exist on this side. Online games only ever get this part of the game.
+#[derive(Resource, Debug, Clone)]
+pub struct TableView(pub PlayerView);
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeatKind {
+    Human,
+    Bot(Difficulty),
+}
+
+impl FromStr for SeatKind {
+    type Err = String;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "human" => Ok(SeatKind::Human),
+            difficulty => difficulty.parse().map(SeatKind::Bot),
+        }
+    }
+}
+
+/// Who sits around the table when the game starts
+#[derive(Resource, Debug, Clone)]
+pub struct GameConfig {
+    pub seats: Vec<SeatKind>,
+    /// Seed of the game, a random one is picked when `None`
+    pub seed: Option<u64>,
+    pub deck: DeckRecipe,
+    pub rules: HouseRules,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            seats: vec![
+                SeatKind::Human,
+                SeatKind::Bot(Difficulty::Normal),
+                SeatKind::Bot(Difficulty::Normal),
+                SeatKind::Bot(Difficulty::Normal),
+            ],
+            seed: None,
+            deck: DeckRecipe::default(),
+            rules: HouseRules::default(),
+        }
+    }
+}
+
+impl GameConfig {
+    /// ## Reads the config from command line arguments
+    ///
+    /// `--seats human,easy,hard` sets who sits around the table, `--seed 42` fixes the game seed
+    /// and `--target 300` the points needed to win the match. `--stacking same` or `mixed`
+    /// lets draw cards be stacked, see [`crate::features::rules::stacking::Stacking`], and `--jump-in`
+    /// lets identical cards be played out of turn. `--deck flip` plays with the Flip deck
+    /// and `--deck party` with custom cards. `--turn-time 30` gives each player 30 seconds per turn.
+    /// Missing or invalid arguments keep their default value.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut config = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seats" => {
+                    let Some(seats) = args.next() else { break };
+                    match seats
+                        .split(',')
+                        .map(str::parse)
+                        .collect::<Result<Vec<_>, _>>()
+                    {
+                        Ok(kinds) if (MIN_SEATS..=MAX_SEATS).contains(&kinds.len()) => {
+                            config.seats = kinds
+                        }
+                        Ok(_) => warn!(
+                            "Ignored --seats {}: {} to {} seats are needed",
+                            seats, MIN_SEATS, MAX_SEATS
+                        ),
+                        Err(error) => warn!("Ignored --seats {}: {}", seats, error),
+                    }
+                }
+                "--seed" => {
+                    let Some(seed) = args.next() else { break };
+                    match seed.parse() {
+                        Ok(seed) => config.seed = Some(seed),
+                        Err(error) => warn!("Ignored --seed {}: {}", seed, error),
+                    }
+                }
+                "--deck" => {
+                    let Some(deck) = args.next() else { break };
+                    match deck.parse() {
+                        Ok(deck) => config.deck = deck,
+                        Err(error) => warn!("Ignored --deck {}: {}", deck, error),
+                    }
+                }
+                "--jump-in" => config.rules.jump_in = true,
+                "--seven-o" => config.rules.seven_o = true,
+                "--forced-play" => config.rules.forced_play = true,
+                "--turn-time" => {
+                    let Some(seconds) = args.next() else { break };
+                    match seconds.parse() {
+                        Ok(seconds) => config.rules.turn_time = Some(seconds),
+                        Err(error) => warn!("Ignored --turn-time {}: {}", seconds, error),
+                    }
+                }
+                "--draw" => {
+                    let Some(draw) = args.next() else { break };
+                    match draw.parse() {
+                        Ok(draw) => config.rules.draw = draw,
+                        Err(error) => warn!("Ignored --draw {}: {}", draw, error),
+                    }
+                }
+                "--stacking" => {
+                    let Some(stacking) = args.next() else { break };
+                    match stacking.parse() {
+                        Ok(stacking) => config.rules.stacking = stacking,
+                        Err(error) => warn!("Ignored --stacking {}: {}", stacking, error),
+                    }
+                }
+                "--target" => {
+                    let Some(target) = args.next() else { break };
+                    match target.parse() {
+                        Ok(target) => config.rules.target_score = target,
+                        Err(error) => warn!("Ignored --target {}: {}", target, error),
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        config
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Player {
+    pub seat: Seat,
+}
+
+/// The human player using this app
+#[derive(Component, Debug)]
+pub struct LocalPlayer;
+
+/// Ask to play the card at index `card` of `seat` hand, `color` is required for wild cards
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayCardRequest {
+    pub seat: Seat,
+    pub card: usize,
+    pub color: Option<CardColor>,
+}
+
+/// Ask to draw for the turn of `seat`, see [`HouseRules::draw`]
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DrawCardRequest {
+    pub seat: Seat,
+}
+
+/// Ask to keep the playable card `seat` just drew and end its turn
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PassRequest {
+    pub seat: Seat,
+}
+
+/// Ask to call uno for `seat`, see [`GameState::can_call_uno`]
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CallUnoRequest {
+    pub seat: Seat,
+}
+
+/// Ask to challenge the +4 played on `seat`, drawing with a [`DrawCardRequest`] accepts it
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChallengeRequest {
+    pub seat: Seat,
+}
+
+/// Ask to swap the hand of `seat` with the one of `target` after playing a 7
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapHandsRequest {
+    pub seat: Seat,
+    pub target: Seat,
+}
+
+/// Ask to catch `target` who forgot to call uno, `seat` is the one catching it
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CatchUnoRequest {
+    pub seat: Seat,
+    pub target: Seat,
+}
+
+/// Ask to deal the next round of the match once the current one is over
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NextRoundRequest {
+    pub seat: Seat,
+}
+
+/// Sent for everything that happened on the [`Table`] after a request got applied
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableEvent(pub GameEvent);
+
+/// Systems sending requests should run before this set to get applied in the same frame
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ApplyRequestsSet;
+
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayCardRequest>()
+            .add_event::<DrawCardRequest>()
+            .add_event::<PassRequest>()
+            .add_event::<NextRoundRequest>()
+            .add_event::<CallUnoRequest>()
+            .add_event::<CatchUnoRequest>()
+            .add_event::<ChallengeRequest>()
+            .add_event::<SwapHandsRequest>()
+            .add_event::<TableEvent>()
+            .init_resource::<GameConfig>()
+            .init_state::<AppState>()
+            .add_plugins((
+                view::TableViewPlugin,
+                input::InputPlugin,
+                hot_seat::HotSeatPlugin,
+                summary::SummaryPlugin,
+                uno::UnoPlugin,
+                challenge::ChallengePlugin,
+                seven_o::SevenOPlugin,
+                drawing::DrawingPlugin,
+                turn_timer::TurnTimerPlugin,
+            ))
+            .add_systems(
+                Startup,
+                start_game.run_if(is_local_game.and_then(not(resource_exists::<ReplayViewer>))),
+            )
+            .add_systems(
+                Update,
+                (
+                    apply_requests
+                        .in_set(ApplyRequestsSet)
+                        .run_if(resource_exists::<Table>.and_then(is_local_game)),
+                    project_table.after(ApplyRequestsSet).run_if(
+                        resource_exists_and_changed::<Table>
+                            .or_else(resource_exists_and_changed::<hot_seat::HotSeat>),
+                    ),
+                ),
+            );
+    }
+}
+
+/// Online games are dealt and applied by the server instead
+fn is_local_game(address: Option<Res<ServerAddress>>) -> bool {
+    address.is_none()
+}
+
+/// Deal the cards and spawn one player entity per seat
+fn start_game(
+    mut commands: Commands,
+    config: Res<GameConfig>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let deck = Deck::from_recipe(config.deck, seed).into_cards();
+    let state = GameState::with_rules(deck, config.seats.len(), seed, config.rules);
+    commands.insert_resource(Recording(Replay::new(config.seats.clone(), state.clone())));
+    commands.insert_resource(Table(state));
+
+    seat_players(&mut commands, &config.seats);
+    next_state.set(AppState::Playing);
+}
+
+/// Spawn one player entity per seat, several humans play in [`hot_seat::HotSeat`] mode
+pub fn seat_players(commands: &mut Commands, seats: &[SeatKind]) {
+    let humans = seats
+        .iter()
+        .enumerate()
+        .filter(|(_, kind)| **kind == SeatKind::Human)
+        .map(|(seat, _)| seat)
+        .collect::<Vec<_>>();
+    // Humans sharing the screen only see their hand once they lift the curtain
+    let local_seat = match humans.as_slice() {
+        [seat] => Some(*seat),
+        [] => None,
+        _ => {
+            commands.insert_resource(hot_seat::HotSeat::new(humans));
+            None
+        }
+    };
+
+    for (seat, kind) in seats.iter().enumerate() {
+        let mut player = commands.spawn((Player { seat }, *kind));
+
+        if Some(seat) == local_seat {
+            player.insert(LocalPlayer);
+        }
+    }
+}
+
+/// Apply requests to the [`Table`], rejected requests are logged and ignored.
+/// Uno calls go first so they land before the card they announce, and only the first
+/// of the jump-ins sent at the same time gets in
+#[allow(clippy::too_many_arguments)]
+fn apply_requests(
+    mut table: ResMut<Table>,
+    mut recording: Option<ResMut<Recording>>,
+    mut call_uno_reader: EventReader<CallUnoRequest>,
+    mut catch_uno_reader: EventReader<CatchUnoRequest>,
+    mut play_reader: EventReader<PlayCardRequest>,
+    mut draw_reader: EventReader<DrawCardRequest>,
+    mut pass_reader: EventReader<PassRequest>,
+    mut challenge_reader: EventReader<ChallengeRequest>,
+    mut swap_reader: EventReader<SwapHandsRequest>,
+    mut next_round_reader: EventReader<NextRoundRequest>,
+    mut table_writer: EventWriter<TableEvent>,
+) {
+    let calls = call_uno_reader
+        .read()
+        .map(|request| (request.seat, Action::CallUno));
+    let catches = catch_uno_reader.read().map(|request| {
+        (
+            request.seat,
+            Action::CatchUno {
+                target: request.target,
+            },
+        )
+    });
+    // Plays sent in the same frame resolve in turn order, so the closest seat wins a jump-in
+    let mut plays = play_reader
+        .read()
+        .map(|request| {
+            (
+                request.seat,
+                Action::Play {
+                    card: request.card,
+                    color: request.color,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
+    plays.sort_by_key(|(seat, _)| table.0.turn_distance(*seat));
+    let draws = draw_reader
+        .read()
+        .map(|request| (request.seat, Action::Draw));
+    let passes = pass_reader
+        .read()
+        .map(|request| (request.seat, Action::Pass));
+    let challenges = challenge_reader
+        .read()
+        .map(|request| (request.seat, Action::ChallengePlusFour));
+    let swaps = swap_reader.read().map(|request| {
+        (
+            request.seat,
+            Action::SwapHands {
+                target: request.target,
+            },
+        )
+    });
+    let next_rounds = next_round_reader
+        .read()
+        .map(|request| (request.seat, Action::NextRound));
+    let requests = calls
+        .chain(catches)
+        .chain(plays)
+        .chain(draws)
+        .chain(passes)
+        .chain(challenges)
+        .chain(swaps)
+        .chain(next_rounds)
+        .collect::<Vec<_>>();
+
+    let mut jumped_in = false;
+    for (seat, action) in requests {
+        if jumped_in && table.0.can_jump_in(seat, action) {
+            info!("Seat {} jumped in too late", seat);
+            continue;
+        }
+
+        match table.0.apply(seat, action) {
+            Ok(events) => {
+                jumped_in |= events.contains(&GameEvent::JumpedIn { seat });
+                if let Some(recording) = recording.as_mut() {
+                    recording.0.record(seat, action, &events);
+                }
+                table_writer.send_batch(events.into_iter().map(TableEvent));
+            }
+            Err(error) => warn!("Rejected {:?} from seat {}: {}", action, seat, error),
+        }
+    }
+}
+
+/// Show the local player its part of the [`Table`]
+fn project_table(
+    mut commands: Commands,
+    table: Res<Table>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+) {
+    let viewer = local_query.get_single().map(|player| player.seat).ok();
+
+    commands.insert_resource(TableView(table.0.view_for(viewer)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::rules::STARTING_HAND_SIZE;
+
+    mod start_game {
+        use super::*;
+
+        #[test]
+        fn spawns_players_and_table() {
+            let mut app = App::new();
+
+            app.insert_resource(GameConfig {
+                seats: vec![
+                    SeatKind::Human,
+                    SeatKind::Bot(Difficulty::Easy),
+                    SeatKind::Bot(Difficulty::Hard),
+                ],
+                seed: Some(1),
+                deck: DeckRecipe::Classic,
+                rules: HouseRules::default(),
+            })
+            .init_state::<AppState>()
+            .add_systems(Startup, start_game);
+
+            app.update();
+
+            let players = app.world.query::<&Player>().iter(&app.world).count();
+            let local = app
+                .world
+                .query_filtered::<&Player, With<LocalPlayer>>()
+                .single(&app.world);
+            let table = app.world.resource::<Table>();
+
+            assert_eq!(players, 3);
+            assert_eq!(local.seat, 0);
+            assert_eq!(table.0, GameState::from_seed(3, 1));
+            assert_eq!(
+                app.world.resource::<State<AppState>>().get(),
+                &AppState::Playing
+            );
+        }
+
+        #[test]
+        fn no_local_player_before_curtain() {
+            let mut app = App::new();
+
+            app.insert_resource(GameConfig {
+                seats: vec![
+                    SeatKind::Human,
+                    SeatKind::Bot(Difficulty::Easy),
+                    SeatKind::Human,
+                ],
+                seed: Some(1),
+                deck: DeckRecipe::Classic,
+                rules: HouseRules::default(),
+            })
+            .init_state::<AppState>()
+            .add_systems(Startup, start_game);
+
+            app.update();
+
+            let local_players = app
+                .world
+                .query_filtered::<(), With<LocalPlayer>>()
+                .iter(&app.world)
+                .count();
+
+            assert_eq!(local_players, 0);
+            assert_eq!(
+                app.world.get_resource::<hot_seat::HotSeat>(),
+                Some(&hot_seat::HotSeat::new(vec![0, 2]))
+            );
+        }
+    }
+
+    mod from_args {
+        use super::*;
+
+        fn args(args: &str) -> Vec<String> {
+            args.split_whitespace().map(str::to_string).collect()
+        }
+
+        #[test]
+        fn reads_seats_and_seed() {
+            let config = GameConfig::from_args(args("card_game --seats hard,human,Easy --seed 7"));
+
+            assert_eq!(
+                config.seats,
+                vec![
+                    SeatKind::Bot(Difficulty::Hard),
+                    SeatKind::Human,
+                    SeatKind::Bot(Difficulty::Easy)
+                ]
+            );
+            assert_eq!(config.seed, Some(7));
+        }
+
+        #[test]
+        fn reads_target_score() {
+            let config = GameConfig::from_args(args("card_game --target 200"));
+
+            assert_eq!(config.rules.target_score, 200);
+        }
+
+        #[test]
+        fn reads_stacking() {
+            let config =
+                GameConfig::from_args(args("card_game --stacking mixed --jump-in --seven-o"));
+
+            assert!(config.rules.jump_in);
+            assert!(config.rules.seven_o);
+            assert_eq!(
+                config.rules.stacking,
+                crate::features::rules::stacking::Stacking::Mixed
+            );
+        }
+
+        #[test]
+        fn reads_deck() {
+            let config = GameConfig::from_args(args("card_game --deck flip"));
+
+            assert_eq!(config.deck, DeckRecipe::Flip);
+        }
+
+        #[test]
+        fn reads_turn_time() {
+            let config = GameConfig::from_args(args("card_game --turn-time 30"));
+
+            assert_eq!(config.rules.turn_time, Some(30));
+            assert_eq!(
+                GameConfig::from_args(args("card_game")).rules.turn_time,
+                None
+            );
+        }
+
+        #[test]
+        fn reads_draw_rules() {
+            let config =
+                GameConfig::from_args(args("card_game --draw until-playable --forced-play"));
+
+            assert!(config.rules.forced_play);
+            assert_eq!(
+                config.rules.draw,
+                crate::features::rules::drawing::DrawRule::UntilPlayable
+            );
+        }
+
+        #[test]
+        fn invalid_seats_ignored() {
+            let config = GameConfig::from_args(args("card_game --seats human,expert"));
+            let crowded = format!("card_game --seats human{}", ",easy".repeat(MAX_SEATS));
+
+            assert_eq!(config.seats, GameConfig::default().seats);
+            assert_eq!(
+                GameConfig::from_args(args(&crowded)).seats,
+                GameConfig::default().seats
+            );
+        }
+    }
+
+    mod project_table {
+        use super::*;
+
+        #[test]
+        fn local_player_view() {
+            let mut app = App::new();
+
+            app.insert_resource(Table(GameState::from_seed(3, 2)))
+                .add_systems(Update, project_table);
+            app.world.spawn((Player { seat: 2 }, LocalPlayer));
+
+            app.update();
+
+            let table_view = app.world.resource::<TableView>();
+
+            assert_eq!(table_view.0, GameState::from_seed(3, 2).view_for(Some(2)));
+        }
+    }
+
+    mod apply_requests {
+        use super::*;
+
+        fn setup_app() -> App {
+            let mut app = App::new();
+
+            app.add_event::<PlayCardRequest>()
+                .add_event::<DrawCardRequest>()
+                .add_event::<PassRequest>()
+                .add_event::<NextRoundRequest>()
+                .add_event::<CallUnoRequest>()
+                .add_event::<CatchUnoRequest>()
+                .add_event::<ChallengeRequest>()
+                .add_event::<SwapHandsRequest>()
+                .add_event::<TableEvent>()
+                .insert_resource(Table(GameState::from_seed(2, 3)))
+                .add_systems(Update, apply_requests);
+
+            app
+        }
+
+        #[test]
+        fn valid_request_applied() {
+            let mut app = setup_app();
+
+            app.world.send_event(DrawCardRequest { seat: 0 });
+
+            app.update();
+
+            let table = app.world.resource::<Table>();
+            let events = app.world.resource::<Events<TableEvent>>();
+
+            assert_eq!(table.0.hands[0].len(), STARTING_HAND_SIZE + 1);
+            assert_eq!(table.0.current, 1);
+            assert!(!events.is_empty());
+        }
+
+        #[test]
+        fn invalid_request_ignored() {
+            let mut app = setup_app();
+
+            app.world.send_event(DrawCardRequest { seat: 1 });
+
+            app.update();
+
+            let table = app.world.resource::<Table>();
+            let events = app.world.resource::<Events<TableEvent>>();
+
+            assert_eq!(table.0, GameState::from_seed(2, 3));
+            assert!(events.is_empty());
+        }
+
+        #[test]
+        fn closest_jump_in_wins() {
+            let mut app = setup_app();
+            let mut state = GameState::from_seed(4, 3);
+            state.rules.jump_in = true;
+            let top = state.top_card();
+            state.hands[1][0] = top;
+            state.hands[3][0] = top;
+            app.insert_resource(Table(state));
+
+            for seat in [3, 1] {
+                app.world.send_event(PlayCardRequest {
+                    seat,
+                    card: 0,
+                    color: Some(CardColor::Red),
+                });
+            }
+            app.update();
+
+            let table = app.world.resource::<Table>();
+            assert_eq!(table.0.hands[1].len(), STARTING_HAND_SIZE - 1);
+            assert_eq!(table.0.hands[3].len(), STARTING_HAND_SIZE);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+use bevy::prelude::*;
+
+use super::{LocalPlayer, Player, SwapHandsRequest, TableView};
+use crate::features::rules::{projection::PlayerView, Seat};
+
+/// Swaps the hand of the local player with the one of the seat
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+struct SwapTargetButton(Seat);
+
+#[derive(Component)]
+struct SwapPromptMarker;
+
+pub struct SevenOPlugin;
+
+/// This plugin lets the local player pick whom to swap hands with after playing a 7
+impl Plugin for SevenOPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_swap_prompt.run_if(resource_exists_and_changed::<TableView>),
+                press_swap_buttons,
+            ),
+        );
+    }
+}
+
+/// Seats `seat` can swap hands with in `view`, with the size of their hands
+fn swap_targets(view: &PlayerView, seat: Seat) -> Vec<(Seat, usize)> {
+    if view.swapping != Some(seat) {
+        return Vec::new();
+    }
+
+    view.hands
+        .iter()
+        .enumerate()
+        .filter(|(target, _)| *target != seat)
+        .map(|(target, hand)| (target, hand.len()))
+        .collect()
+}
+
+fn show_swap_prompt(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    prompt_query: Query<Entity, With<SwapPromptMarker>>,
+) {
+    prompt_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let targets = local_query
+        .get_single()
+        .map(|player| swap_targets(&table_view.0, player.seat))
+        .unwrap_or_default();
+    if targets.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    top: Val::Percent(30.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                z_index: ZIndex::Global(40),
+                ..default()
+            },
+            SwapPromptMarker,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                "You played a 7: pick a player to swap hands with",
+                TextStyle::default(),
+            ));
+            builder
+                .spawn(NodeBundle {
+                    style: Style {
+                        column_gap: Val::Px(10.),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|builder| {
+                    for (target, len) in targets {
+                        builder
+                            .spawn((
+                                ButtonBundle {
+                                    style: Style {
+                                        padding: UiRect::all(Val::Px(10.)),
+                                        ..default()
+                                    },
+                                    background_color: BackgroundColor(Color::DARK_GRAY),
+                                    ..default()
+                                },
+                                SwapTargetButton(target),
+                            ))
+                            .with_children(|builder| {
+                                builder.spawn(TextBundle::from_section(
+                                    format!("Player {} ({} cards)", target + 1, len),
+                                    TextStyle::default(),
+                                ));
+                            });
+                    }
+                });
+        });
+}
+
+fn press_swap_buttons(
+    buttons_query: Query<(&Interaction, &SwapTargetButton), Changed<Interaction>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut swap_writer: EventWriter<SwapHandsRequest>,
+) {
+    let Ok(&Player { seat }) = local_query.get_single() else {
+        return;
+    };
+
+    for (_, SwapTargetButton(target)) in buttons_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        swap_writer.send(SwapHandsRequest {
+            seat,
+            target: *target,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{Action, GameState, HouseRules},
+        CardColor, CardVariant,
+    };
+
+    mod swap_targets {
+        use super::*;
+
+        #[test]
+        fn only_for_the_player_of_the_seven() {
+            let mut state = GameState::with_rules(
+                crate::features::deck::Deck::default().into_cards(),
+                3,
+                0,
+                HouseRules {
+                    seven_o: true,
+                    ..HouseRules::default()
+                },
+            );
+            state.discard_pile = vec![(CardColor::Red, CardVariant::Number(5))];
+            state.active_color = CardColor::Red;
+            state.hands[0][0] = (CardColor::Red, CardVariant::Number(7));
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            let hand_size = state.hands[1].len();
+            assert_eq!(
+                swap_targets(&state.view_for(Some(0)), 0),
+                vec![(1, hand_size), (2, hand_size)]
+            );
+            assert!(swap_targets(&state.view_for(Some(1)), 1).is_empty());
+        }
+    }
+}
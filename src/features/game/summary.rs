@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+
+use super::{LocalPlayer, NextRoundRequest, Player, TableView};
+use crate::{
+    features::rules::{
+        projection::PlayerView,
+        scoring::{card_points, hand_points},
+        Seat,
+    },
+    CardBundle,
+};
+
+#[derive(Component)]
+struct SummaryMarker;
+
+#[derive(Component)]
+struct NextRoundButton;
+
+pub struct SummaryPlugin;
+
+/// This plugin shows the points of the round once a player emptied its hand, and the winner of the match
+impl Plugin for SummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_summary.run_if(resource_exists_and_changed::<TableView>),
+                press_next_round.run_if(resource_exists::<TableView>),
+            ),
+        );
+    }
+}
+
+/// One line per card left in the hand of `seat` with its points, then the total
+fn hand_breakdown(view: &PlayerView, seat: Seat) -> String {
+    let hand = view.hand(seat);
+    if hand.is_empty() {
+        return format!("Player {}: no card left", seat + 1);
+    }
+
+    let cards = hand
+        .iter()
+        .map(|card| {
+            format!(
+                "{} ({})",
+                CardBundle::name(card.0, card.1),
+                card_points(*card)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "Player {}: {} = {}",
+        seat + 1,
+        cards.join(", "),
+        hand_points(&hand)
+    )
+}
+
+fn title(view: &PlayerView, winner: Seat) -> String {
+    match view.match_winner() {
+        Some(winner) => format!(
+            "Player {} wins the match with {} points!",
+            winner + 1,
+            view.scores[winner]
+        ),
+        None => format!("Player {} wins the round", winner + 1),
+    }
+}
+
+fn show_summary(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    summary_query: Query<Entity, With<SummaryMarker>>,
+) {
+    summary_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let view = &table_view.0;
+    let Some(winner) = view.winner else {
+        return;
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0., 0., 0., 0.8)),
+                z_index: ZIndex::Global(50),
+                ..default()
+            },
+            SummaryMarker,
+        ))
+        .with_children(|builder| {
+            builder.spawn(TextBundle::from_section(
+                title(view, winner),
+                TextStyle::default(),
+            ));
+            for seat in 0..view.players() {
+                builder.spawn(TextBundle::from_section(
+                    hand_breakdown(view, seat),
+                    TextStyle::default(),
+                ));
+            }
+            builder.spawn(TextBundle::from_section(
+                view.scores
+                    .iter()
+                    .enumerate()
+                    .map(|(seat, score)| format!("Player {}: {}", seat + 1, score))
+                    .collect::<Vec<_>>()
+                    .join("   "),
+                TextStyle::default(),
+            ));
+            builder.spawn(TextBundle::from_section(
+                format!("First to {} points wins the match", view.target_score),
+                TextStyle::default(),
+            ));
+
+            if view.match_winner().is_none() {
+                builder
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(10.)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::DARK_GRAY),
+                            ..default()
+                        },
+                        NextRoundButton,
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section("Next round", TextStyle::default()));
+                    });
+            }
+        });
+}
+
+/// Without a local player, as in hot seat games, the request is sent for the seat whose turn it was
+fn press_next_round(
+    table_view: Res<TableView>,
+    buttons_query: Query<&Interaction, (With<NextRoundButton>, Changed<Interaction>)>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut next_round_writer: EventWriter<NextRoundRequest>,
+) {
+    if !buttons_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        return;
+    }
+
+    let seat = local_query
+        .get_single()
+        .map(|player| player.seat)
+        .unwrap_or(table_view.0.current);
+    next_round_writer.send(NextRoundRequest { seat });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{Action, GameState},
+        CardColor, CardVariant,
+    };
+
+    const RED_5: (CardColor, CardVariant) = (CardColor::Red, CardVariant::Number(5));
+    const PLUS_FOUR: (CardColor, CardVariant) = (CardColor::Wild, CardVariant::PlusFour);
+
+    fn round_over() -> PlayerView {
+        let mut state = GameState::from_seed(2, 0);
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![vec![RED_5], vec![RED_5, PLUS_FOUR]];
+        state
+            .apply(
+                0,
+                Action::Play {
+                    card: 0,
+                    color: None,
+                },
+            )
+            .unwrap();
+
+        state.view_for(Some(0))
+    }
+
+    mod hand_breakdown {
+        use super::*;
+
+        #[test]
+        fn points_per_card() {
+            let view = round_over();
+
+            assert_eq!(hand_breakdown(&view, 0), "Player 1: no card left");
+            assert_eq!(
+                hand_breakdown(&view, 1),
+                format!(
+                    "Player 2: {} (5), {} (50) = 55",
+                    CardBundle::name(RED_5.0, RED_5.1),
+                    CardBundle::name(PLUS_FOUR.0, PLUS_FOUR.1)
+                )
+            );
+        }
+    }
+
+    mod show_summary {
+        use super::*;
+
+        #[test]
+        fn only_once_the_round_is_over() {
+            let mut app = App::new();
+            app.insert_resource(TableView(GameState::from_seed(2, 0).view_for(Some(0))))
+                .add_systems(Update, show_summary);
+
+            app.update();
+            let during_round = app
+                .world
+                .query_filtered::<(), With<SummaryMarker>>()
+                .iter(&app.world)
+                .count();
+            app.insert_resource(TableView(round_over()));
+            app.update();
+            let buttons = app
+                .world
+                .query_filtered::<(), With<NextRoundButton>>()
+                .iter(&app.world)
+                .count();
+
+            assert_eq!(during_round, 0);
+            assert_eq!(buttons, 1);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use bevy::prelude::*;
+
+use super::{
+    layout::TableLayout, ApplyRequestsSet, CallUnoRequest, CatchUnoRequest, ChallengeRequest,
+    DrawCardRequest, LocalPlayer, PassRequest, PlayCardRequest, Player, SeatKind, SwapHandsRequest,
+    Table, TableEvent, TableView,
+};
+use crate::features::{
+    ai::{self, Bot, BotSettings},
+    lobby::room::REPLACEMENT_BOT,
+    rules::{turn_timer::Timeouts, GameEvent, Seat},
+};
+
+/// The ring shows between the hand of the current player and the middle of the table
+const TURN_RING_DISTANCE: f32 = 190.;
+const TURN_RING_RADIUS: f32 = 40.;
+/// Part of the turn left when the ring turns red
+const TURN_RING_WARNING: f32 = 0.25;
+
+/// Time left to the current player, see [`crate::features::rules::HouseRules::turn_time`]
+#[derive(Resource, Debug)]
+pub struct TurnClock {
+    pub seat: Seat,
+    pub timer: Timer,
+}
+
+pub struct TurnTimerPlugin;
+
+/// This plugin counts down the turns, shows the time left as a ring next to the current player
+/// and lets a bot play the turns humans of a local game ran out of time on
+impl Plugin for TurnTimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Timeouts>().add_systems(
+            Update,
+            (
+                restart_turn_clock.run_if(resource_exists_and_changed::<TableView>),
+                tick_turn_clock.run_if(resource_exists::<TurnClock>),
+                play_timed_out_turns
+                    .run_if(resource_exists::<TurnClock>.and_then(resource_exists::<Table>)),
+                draw_turn_ring.run_if(resource_exists::<TurnClock>),
+            )
+                .chain()
+                .before(ApplyRequestsSet),
+        );
+    }
+}
+
+/// The current player gets its whole turn time again once the turn moved on,
+/// a player whose turn ended before the clock ran out starts its count of timeouts over.
+/// Rejected requests still change the table, they leave the clock running
+fn restart_turn_clock(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    clock: Option<Res<TurnClock>>,
+    mut table_events: EventReader<TableEvent>,
+    mut timeouts: ResMut<Timeouts>,
+) {
+    let view = &table_view.0;
+    let turn_time = view.turn_time.filter(|_| view.winner.is_none());
+    let events = table_events.read().map(|event| event.0).collect::<Vec<_>>();
+
+    if let Some(clock) = clock {
+        let moved_on =
+            clock.seat != view.current || events.iter().any(|event| ends_turn(event, clock.seat));
+        if !moved_on && turn_time.is_some() {
+            return;
+        }
+        if clock.seat != view.current && !clock.timer.finished() {
+            timeouts.acted(clock.seat);
+        }
+    }
+
+    match turn_time {
+        Some(seconds) => commands.insert_resource(TurnClock {
+            seat: view.current,
+            timer: Timer::new(Duration::from_secs(seconds.into()), TimerMode::Once),
+        }),
+        None => commands.remove_resource::<TurnClock>(),
+    }
+}
+
+/// Whether `event` ends the turn of `seat`, even when `seat` plays again right after
+fn ends_turn(event: &GameEvent, seat: Seat) -> bool {
+    match *event {
+        GameEvent::Played { seat: player, .. }
+        | GameEvent::Drew { seat: player, .. }
+        | GameEvent::Passed { seat: player } => player == seat,
+        GameEvent::Dealt { .. } => true,
+        _ => false,
+    }
+}
+
+fn tick_turn_clock(mut clock: ResMut<TurnClock>, time: Res<Time>) {
+    clock.timer.tick(time.delta());
+}
+
+/// Once the clock of a human runs out, a bot plays its turn,
+/// and keeps its seat after too many turns in a row
+fn play_timed_out_turns(
+    mut commands: Commands,
+    clock: Res<TurnClock>,
+    table: Res<Table>,
+    settings: Res<BotSettings>,
+    mut timeouts: ResMut<Timeouts>,
+    players_query: Query<(Entity, &Player), Without<Bot>>,
+    mut writers: (
+        EventWriter<PlayCardRequest>,
+        EventWriter<DrawCardRequest>,
+        EventWriter<PassRequest>,
+        EventWriter<CallUnoRequest>,
+        EventWriter<CatchUnoRequest>,
+        EventWriter<ChallengeRequest>,
+        EventWriter<SwapHandsRequest>,
+    ),
+) {
+    if !clock.timer.just_finished() {
+        return;
+    }
+    let Some((entity, _)) = players_query
+        .iter()
+        .find(|(_, player)| player.seat == clock.seat)
+    else {
+        return;
+    };
+
+    info!("Seat {} ran out of time", clock.seat);
+    for action in ai::timeout_actions(&table.0, clock.seat, &mut rand::thread_rng()) {
+        ai::send_request(clock.seat, action, &mut writers);
+    }
+
+    if timeouts.timed_out(clock.seat) {
+        info!("Seat {} is given to a bot", clock.seat);
+        commands.entity(entity).insert((
+            SeatKind::Bot(REPLACEMENT_BOT),
+            Bot::new(REPLACEMENT_BOT, settings.think_delay),
+        ));
+    }
+}
+
+/// Where the ring of `seat` shows, from its hand toward the middle of the table
+fn turn_ring_position(
+    layout: &TableLayout,
+    seat: Seat,
+    local_seat: Option<Seat>,
+    players: usize,
+) -> Vec2 {
+    let anchor = layout.seat_anchor(seat, local_seat, players);
+
+    anchor - anchor.normalize_or_zero() * TURN_RING_DISTANCE
+}
+
+/// The ring starts full from the top and empties as the turn goes by
+fn draw_turn_ring(
+    mut gizmos: Gizmos,
+    clock: Res<TurnClock>,
+    table_view: Res<TableView>,
+    layout: Res<TableLayout>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+) {
+    let local_seat = local_query.get_single().map(|player| player.seat).ok();
+    let position = turn_ring_position(&layout, clock.seat, local_seat, table_view.0.players());
+    let left = clock.timer.fraction_remaining();
+    let color = if left < TURN_RING_WARNING {
+        Color::RED
+    } else {
+        Color::WHITE
+    };
+
+    gizmos.circle_2d(position, TURN_RING_RADIUS, Color::DARK_GRAY);
+    gizmos
+        .arc_2d(
+            position,
+            TAU * left / 2.,
+            TAU * left,
+            TURN_RING_RADIUS,
+            color,
+        )
+        .segments(64);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::rules::{GameState, HouseRules};
+
+    mod play_timed_out_turns {
+        use super::*;
+
+        fn setup_app() -> App {
+            let mut state = GameState::with_rules(
+                crate::features::deck::Deck::default().into_cards(),
+                2,
+                0,
+                HouseRules {
+                    turn_time: Some(0),
+                    ..HouseRules::default()
+                },
+            );
+            state.current = 1;
+
+            let mut app = App::new();
+            app.add_plugins(MinimalPlugins)
+                .add_event::<PlayCardRequest>()
+                .add_event::<DrawCardRequest>()
+                .add_event::<PassRequest>()
+                .add_event::<CallUnoRequest>()
+                .add_event::<CatchUnoRequest>()
+                .add_event::<ChallengeRequest>()
+                .add_event::<SwapHandsRequest>()
+                .add_event::<TableEvent>()
+                .init_resource::<Timeouts>()
+                .init_resource::<BotSettings>()
+                .insert_resource(TableView(state.view_for(Some(1))))
+                .insert_resource(Table(state))
+                .add_systems(
+                    Update,
+                    (
+                        restart_turn_clock.run_if(resource_exists_and_changed::<TableView>),
+                        tick_turn_clock.run_if(resource_exists::<TurnClock>),
+                        play_timed_out_turns.run_if(resource_exists::<TurnClock>),
+                    )
+                        .chain(),
+                );
+            app.world
+                .spawn((Player { seat: 1 }, SeatKind::Human, LocalPlayer));
+
+            app
+        }
+
+        fn requests(app: &App) -> usize {
+            app.world.resource::<Events<PlayCardRequest>>().len()
+                + app.world.resource::<Events<DrawCardRequest>>().len()
+        }
+
+        #[test]
+        fn timeout_sends_a_bot_move() {
+            let mut app = setup_app();
+
+            app.update();
+            app.update();
+
+            assert_eq!(requests(&app), 1);
+            // The seat ran out of time once and is still a human one
+            assert_eq!(app.world.resource::<Timeouts>().count(1), 1);
+            let mut bots = app.world.query_filtered::<&Player, With<Bot>>();
+            assert_eq!(bots.iter(&app.world).count(), 0);
+        }
+
+        #[test]
+        fn too_many_timeouts_give_the_seat_away() {
+            let mut app = setup_app();
+            let mut timeouts = Timeouts::default();
+            timeouts.timed_out(1);
+            timeouts.timed_out(1);
+            app.insert_resource(timeouts);
+
+            app.update();
+            app.update();
+
+            let mut bots = app.world.query_filtered::<&Player, With<Bot>>();
+            assert_eq!(
+                bots.iter(&app.world)
+                    .map(|player| player.seat)
+                    .collect::<Vec<_>>(),
+                vec![1]
+            );
+        }
+    }
+    mod restart_turn_clock {
+        use super::*;
+        use crate::features::game::{apply_requests, project_table, NextRoundRequest};
+
+        fn setup_app() -> App {
+            let state = GameState::with_rules(
+                crate::features::deck::Deck::default().into_cards(),
+                2,
+                0,
+                HouseRules {
+                    turn_time: Some(30),
+                    ..HouseRules::default()
+                },
+            );
+
+            let mut app = App::new();
+            app.add_event::<PlayCardRequest>()
+                .add_event::<DrawCardRequest>()
+                .add_event::<PassRequest>()
+                .add_event::<NextRoundRequest>()
+                .add_event::<CallUnoRequest>()
+                .add_event::<CatchUnoRequest>()
+                .add_event::<ChallengeRequest>()
+                .add_event::<SwapHandsRequest>()
+                .add_event::<TableEvent>()
+                .init_resource::<Timeouts>()
+                .insert_resource(TableView(state.view_for(Some(0))))
+                .insert_resource(Table(state))
+                .add_systems(
+                    Update,
+                    (
+                        restart_turn_clock.run_if(resource_exists_and_changed::<TableView>),
+                        apply_requests,
+                        project_table.run_if(resource_exists_and_changed::<Table>),
+                    )
+                        .chain(),
+                );
+            app.world.spawn((Player { seat: 0 }, LocalPlayer));
+            app.update();
+            app.world
+                .resource_mut::<TurnClock>()
+                .timer
+                .tick(Duration::from_secs(10));
+
+            app
+        }
+
+        fn elapsed(app: &App) -> Duration {
+            app.world.resource::<TurnClock>().timer.elapsed()
+        }
+
+        #[test]
+        fn rejected_request_keeps_the_clock_running() {
+            let mut app = setup_app();
+
+            // Seat 1 has to wait for its turn
+            app.world.send_event(PlayCardRequest {
+                seat: 1,
+                card: 0,
+                color: None,
+            });
+            app.update();
+            app.update();
+
+            assert_eq!(app.world.resource::<TurnClock>().seat, 0);
+            assert_eq!(elapsed(&app), Duration::from_secs(10));
+        }
+
+        #[test]
+        fn drawing_gives_the_next_player_its_whole_turn() {
+            let mut app = setup_app();
+
+            app.world.send_event(DrawCardRequest { seat: 0 });
+            app.update();
+            app.update();
+
+            assert_eq!(app.world.resource::<TurnClock>().seat, 1);
+            assert_eq!(elapsed(&app), Duration::ZERO);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use bevy::prelude::*;
+
+use super::{CallUnoRequest, CatchUnoRequest, LocalPlayer, Player, TableView};
+use crate::features::rules::{projection::PlayerView, Seat};
+
+/// Key calling uno, or catching whoever forgot to
+const UNO_KEY: KeyCode = KeyCode::KeyU;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum UnoButton {
+    Call,
+    Catch(Seat),
+}
+
+#[derive(Component)]
+struct UnoButtonsMarker;
+
+pub struct UnoPlugin;
+
+/// This plugin shows the uno call of the local player and lets it catch opponents who forgot theirs
+impl Plugin for UnoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                show_uno_buttons.run_if(resource_exists_and_changed::<TableView>),
+                press_uno_buttons.run_if(resource_exists::<TableView>),
+            ),
+        );
+    }
+}
+
+/// What `seat` can do about uno in `view`, catching others comes first
+fn available_button(view: &PlayerView, seat: Seat) -> Option<UnoButton> {
+    match view.forgot_uno {
+        Some(target) if target != seat => Some(UnoButton::Catch(target)),
+        _ => view.can_call_uno(seat).then_some(UnoButton::Call),
+    }
+}
+
+fn show_uno_buttons(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    buttons_query: Query<Entity, With<UnoButtonsMarker>>,
+) {
+    buttons_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let Some(button) = local_query
+        .get_single()
+        .ok()
+        .and_then(|player| available_button(&table_view.0, player.seat))
+    else {
+        return;
+    };
+    let label = match button {
+        UnoButton::Call => "UNO! (U)".to_string(),
+        UnoButton::Catch(target) => format!("Catch player {}! (U)", target + 1),
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.),
+                    bottom: Val::Px(20.),
+                    ..default()
+                },
+                ..default()
+            },
+            UnoButtonsMarker,
+        ))
+        .with_children(|builder| {
+            builder
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(10.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::MAROON),
+                        ..default()
+                    },
+                    button,
+                ))
+                .with_children(|builder| {
+                    builder.spawn(TextBundle::from_section(label, TextStyle::default()));
+                });
+        });
+}
+
+fn press_uno_buttons(
+    table_view: Res<TableView>,
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    buttons_query: Query<(&Interaction, &UnoButton), Changed<Interaction>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut call_writer: EventWriter<CallUnoRequest>,
+    mut catch_writer: EventWriter<CatchUnoRequest>,
+) {
+    let Ok(&Player { seat }) = local_query.get_single() else {
+        return;
+    };
+
+    let pressed = buttons_query
+        .iter()
+        .find(|(interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, button)| *button)
+        .or_else(|| {
+            keys.filter(|keys| keys.just_pressed(UNO_KEY))
+                .and_then(|_| available_button(&table_view.0, seat))
+        });
+
+    match pressed {
+        Some(UnoButton::Call) => {
+            call_writer.send(CallUnoRequest { seat });
+        }
+        Some(UnoButton::Catch(target)) => {
+            catch_writer.send(CatchUnoRequest { seat, target });
+        }
+        None => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{features::rules::GameState, CardColor, CardVariant};
+
+    const RED_5: (CardColor, CardVariant) = (CardColor::Red, CardVariant::Number(5));
+
+    mod available_button {
+        use super::*;
+
+        #[test]
+        fn catch_before_call() {
+            let mut state = GameState::from_seed(3, 0);
+            state.hands[0] = vec![RED_5; 2];
+
+            assert_eq!(
+                available_button(&state.view_for(Some(0)), 0),
+                Some(UnoButton::Call)
+            );
+            assert_eq!(available_button(&state.view_for(Some(1)), 1), None);
+
+            state.forgot_uno = Some(2);
+            assert_eq!(
+                available_button(&state.view_for(Some(0)), 0),
+                Some(UnoButton::Catch(2))
+            );
+        }
+    }
+
+    mod press_uno_buttons {
+        use super::*;
+
+        #[test]
+        fn key_calls_uno() {
+            let mut state = GameState::from_seed(2, 0);
+            state.hands[0] = vec![RED_5; 2];
+            let mut keys = ButtonInput::<KeyCode>::default();
+            keys.press(UNO_KEY);
+            let mut app = App::new();
+            app.add_event::<CallUnoRequest>()
+                .add_event::<CatchUnoRequest>()
+                .insert_resource(TableView(state.view_for(Some(0))))
+                .insert_resource(keys)
+                .add_systems(Update, press_uno_buttons);
+            app.world.spawn((Player { seat: 0 }, LocalPlayer));
+
+            app.update();
+
+            let events = app.world.resource::<Events<CallUnoRequest>>();
+            assert_eq!(
+                events.get_reader().read(events).collect::<Vec<_>>(),
+                vec![&CallUnoRequest { seat: 0 }]
+            );
+        }
+    }
+}
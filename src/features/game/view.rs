@@ -0,0 +1,382 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{
+    features::{
+        deck::CARD_BACK_PATH,
+        rules::{projection::SeenCard, Direction, GameEvent, Seat},
+    },
+    utils::{
+        mouse::{Hoverable, MouseInteractionBundle},
+        tooltip::Tooltip,
+    },
+    CardBundle,
+};
+
+use super::{
+    layout::{self, TableLayout, DISCARD_PILE_POSITION},
+    LocalPlayer, Player, TableEvent, TableView,
+};
+
+/// A card in the hand of `seat`, `index` is its position in the hand
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InHand {
+    pub seat: Seat,
+    pub index: usize,
+}
+
+#[derive(Component)]
+pub struct DiscardPileMarker;
+
+/// The "+N" of stacked draw cards shows above the discard pile
+const DRAW_STACK_OFFSET: Vec3 = Vec3::new(0., 130., 1.);
+const DRAW_STACK_FONT_SIZE: f32 = 60.;
+
+/// Cards the current player draws unless it stacks another draw card
+#[derive(Component)]
+struct DrawStackCounter;
+
+/// Time a hand takes to slide to the seat it was given to
+const HAND_TRANSFER_DURATION: Duration = Duration::from_millis(500);
+
+/// A card sliding from the seat its hand came from, after hands were swapped or rotated
+#[derive(Component, Debug)]
+struct HandTransfer {
+    from: Vec3,
+    to: Vec3,
+    timer: Timer,
+}
+
+pub struct TableViewPlugin;
+
+/// This plugin keeps the hands and discard pile sprites in sync with the [`TableView`]
+impl Plugin for TableViewPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TableLayout>().add_systems(
+            Update,
+            (
+                layout::fit_layout_to_window,
+                sync_table_view.run_if(resource_exists_and_changed::<TableView>.or_else(
+                    resource_exists::<TableView>.and_then(resource_changed::<TableLayout>),
+                )),
+                slide_hands,
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Seat each hand was held by before `events`, hands only move when they are swapped or rotated
+fn hand_origins<'a>(players: usize, events: impl IntoIterator<Item = &'a GameEvent>) -> Vec<Seat> {
+    let mut origins = (0..players).collect::<Vec<_>>();
+
+    for event in events {
+        match event {
+            GameEvent::HandsSwapped { seat, target } => origins.swap(*seat, *target),
+            GameEvent::HandsRotated { direction } => match direction {
+                Direction::Clockwise => origins.rotate_right(1),
+                Direction::CounterClockwise => origins.rotate_left(1),
+            },
+            GameEvent::Dealt { .. } => origins = (0..players).collect(),
+            _ => {}
+        }
+    }
+
+    origins
+}
+
+/// Respawn every hand and discard pile sprite: local cards face up and clickable, hidden cards face down
+fn sync_table_view(
+    mut commands: Commands,
+    table_view: Res<TableView>,
+    layout: Res<TableLayout>,
+    asset_server: Res<AssetServer>,
+    cards_query: Query<Entity, Or<(With<InHand>, With<DiscardPileMarker>)>>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut table_events: EventReader<TableEvent>,
+) {
+    cards_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    let view = &table_view.0;
+    let local_seat = local_query.get_single().map(|player| player.seat).ok();
+    let players = view.players();
+    let origins = hand_origins(players, table_events.read().map(|event| &event.0));
+
+    for (seat, hand) in view.hands.iter().enumerate() {
+        let len = hand.len();
+        let anchor = layout.seat_anchor(seat, local_seat, players);
+        let transforms = layout.hand_transforms(anchor, len, Some(seat) == local_seat);
+        let origin = origins.get(seat).copied().unwrap_or(seat);
+        let origin_anchor = layout.seat_anchor(origin, local_seat, players);
+
+        for ((index, card), transform) in hand.iter().enumerate().zip(transforms) {
+            let position = transform.translation;
+            let from = position + (origin_anchor - anchor).extend(0.);
+            let transform = transform.with_translation(from);
+
+            let card = match card {
+                SeenCard::FaceUp((color, variant)) => {
+                    let mut card = commands.spawn((
+                        CardBundle {
+                            color: *color,
+                            variant: *variant,
+                        },
+                        SpriteBundle {
+                            texture: asset_server.load(CardBundle::texture_path(*color, *variant)),
+                            transform,
+                            ..default()
+                        },
+                        InHand { seat, index },
+                        Tooltip::from_text(CardBundle::name(*color, *variant)),
+                    ));
+
+                    if Some(seat) == local_seat {
+                        card.insert(MouseInteractionBundle::default());
+                    } else {
+                        card.insert(Hoverable);
+                    }
+                    card.id()
+                }
+                SeenCard::FaceDown => commands
+                    .spawn((
+                        SpriteBundle {
+                            texture: asset_server.load(CARD_BACK_PATH),
+                            transform,
+                            ..default()
+                        },
+                        InHand { seat, index },
+                        Hoverable,
+                        Tooltip::from_text(format!("player {}: {} cards", seat + 1, len)),
+                    ))
+                    .id(),
+            };
+
+            if origin != seat {
+                commands.entity(card).insert(HandTransfer {
+                    from,
+                    to: position,
+                    timer: Timer::new(HAND_TRANSFER_DURATION, TimerMode::Once),
+                });
+            }
+        }
+    }
+
+    let (color, variant) = view.top_card();
+    let mut discard_pile = commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load(CardBundle::texture_path(color, variant)),
+            transform: Transform::from_translation(DISCARD_PILE_POSITION.extend(0.)),
+            ..default()
+        },
+        DiscardPileMarker,
+        Hoverable,
+        Tooltip::new(discard_pile_tooltip_text),
+    ));
+    if view.draw_stack > 0 {
+        discard_pile.with_children(|builder| {
+            builder.spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        format!("+{}", view.draw_stack),
+                        TextStyle {
+                            font_size: DRAW_STACK_FONT_SIZE,
+                            ..default()
+                        },
+                    ),
+                    transform: Transform::from_translation(DRAW_STACK_OFFSET),
+                    ..default()
+                },
+                DrawStackCounter,
+            ));
+        });
+    }
+}
+
+/// Moves the cards of a swapped or rotated hand from their previous seat to their new one
+fn slide_hands(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut cards_query: Query<(Entity, &mut Transform, &mut HandTransfer)>,
+) {
+    for (entity, mut transform, mut transfer) in cards_query.iter_mut() {
+        transfer.timer.tick(time.delta());
+        transform.translation = transfer.from.lerp(transfer.to, transfer.timer.fraction());
+
+        if transfer.timer.finished() {
+            commands.entity(entity).remove::<HandTransfer>();
+        }
+    }
+}
+
+/// Text of the discard pile tooltip: how many cards were played and the color to follow
+fn discard_pile_tooltip_text(_: Entity, world: &World) -> String {
+    let Some(table_view) = world.get_resource::<TableView>() else {
+        return String::new();
+    };
+    let color: String = table_view.0.active_color.into();
+
+    format!(
+        "discarded: {} cards\nactive color: {}",
+        table_view.0.discard_pile.len(),
+        color
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{GameState, STARTING_HAND_SIZE},
+        utils::{mouse::Clickable, test::test_plugins::TestPlugin},
+        CardColor,
+    };
+
+    mod sync_table_view {
+        use super::*;
+
+        #[test]
+        fn only_local_hand_is_clickable() {
+            let mut app = App::new();
+
+            app.add_plugins((MinimalPlugins, TestPlugin))
+                .insert_resource(TableView(GameState::from_seed(3, 0).view_for(Some(1))))
+                .add_event::<TableEvent>()
+                .init_resource::<TableLayout>()
+                .add_systems(Update, sync_table_view);
+            app.world.spawn((Player { seat: 1 }, LocalPlayer));
+
+            app.update();
+
+            let hand_cards = app.world.query::<&InHand>().iter(&app.world).count();
+            let clickable_seats = app
+                .world
+                .query_filtered::<&InHand, With<Clickable>>()
+                .iter(&app.world)
+                .map(|card| card.seat)
+                .collect::<Vec<_>>();
+            let discard = app
+                .world
+                .query_filtered::<(), With<DiscardPileMarker>>()
+                .iter(&app.world)
+                .count();
+
+            assert_eq!(hand_cards, 3 * STARTING_HAND_SIZE);
+            assert_eq!(clickable_seats, vec![1; STARTING_HAND_SIZE]);
+            assert_eq!(discard, 1);
+        }
+
+        #[test]
+        fn hidden_cards_have_no_identity() {
+            let mut app = App::new();
+
+            app.add_plugins((MinimalPlugins, TestPlugin))
+                .insert_resource(TableView(GameState::from_seed(3, 0).view_for(Some(1))))
+                .add_event::<TableEvent>()
+                .init_resource::<TableLayout>()
+                .add_systems(Update, sync_table_view);
+            app.world.spawn((Player { seat: 1 }, LocalPlayer));
+
+            app.update();
+
+            let identified_seats = app
+                .world
+                .query_filtered::<&InHand, With<CardColor>>()
+                .iter(&app.world)
+                .map(|card| card.seat)
+                .collect::<Vec<_>>();
+
+            assert_eq!(identified_seats, vec![1; STARTING_HAND_SIZE]);
+        }
+
+        #[test]
+        fn counts_stacked_draws() {
+            let mut state = GameState::from_seed(2, 0);
+            state.draw_stack = 6;
+            let mut app = App::new();
+
+            app.add_plugins((MinimalPlugins, TestPlugin))
+                .insert_resource(TableView(state.view_for(Some(0))))
+                .add_event::<TableEvent>()
+                .init_resource::<TableLayout>()
+                .add_systems(Update, sync_table_view);
+
+            app.update();
+
+            let counters = app
+                .world
+                .query_filtered::<&Text, With<DrawStackCounter>>()
+                .iter(&app.world)
+                .map(|text| text.sections[0].value.clone())
+                .collect::<Vec<_>>();
+
+            assert_eq!(counters, vec!["+6".to_string()]);
+        }
+    }
+
+    mod hand_origins {
+        use super::*;
+
+        #[test]
+        fn follows_swaps_and_rotations() {
+            let swapped = hand_origins(3, &[GameEvent::HandsSwapped { seat: 0, target: 2 }]);
+            let rotated = hand_origins(
+                3,
+                &[GameEvent::HandsRotated {
+                    direction: Direction::Clockwise,
+                }],
+            );
+
+            assert_eq!(swapped, vec![2, 1, 0]);
+            assert_eq!(rotated, vec![2, 0, 1]);
+        }
+
+        #[test]
+        fn moved_hands_slide_in() {
+            let mut state = GameState::from_seed(3, 0);
+            state.hands.swap(0, 1);
+            let mut app = App::new();
+
+            app.add_plugins((MinimalPlugins, TestPlugin))
+                .insert_resource(TableView(state.view_for(Some(0))))
+                .add_event::<TableEvent>()
+                .init_resource::<TableLayout>()
+                .add_systems(Update, sync_table_view);
+            app.world.spawn((Player { seat: 0 }, LocalPlayer));
+            app.world
+                .send_event(TableEvent(GameEvent::HandsSwapped { seat: 0, target: 1 }));
+
+            app.update();
+
+            let sliding_seats = app
+                .world
+                .query_filtered::<&InHand, With<HandTransfer>>()
+                .iter(&app.world)
+                .map(|card| card.seat)
+                .collect::<std::collections::BTreeSet<_>>();
+
+            assert_eq!(sliding_seats, [0, 1].into());
+        }
+    }
+
+    mod discard_pile_tooltip_text {
+        use super::*;
+
+        #[test]
+        fn shows_active_color() {
+            let mut world = World::new();
+            let mut state = GameState::from_seed(2, 0);
+            state.active_color = CardColor::Green;
+            world.insert_resource(TableView(state.view_for(None)));
+
+            let entity = world.spawn_empty().id();
+
+            assert_eq!(
+                discard_pile_tooltip_text(entity, &world),
+                "discarded: 1 cards\nactive color: green".to_string()
+            );
+        }
+    }
+}
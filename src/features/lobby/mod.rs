@@ -0,0 +1,133 @@
+pub mod room;
+pub mod ui;
+
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    ai::Difficulty,
+    deck::{DeckGenerator, DeckRecipe},
+    rules::{HouseRules, Seat},
+};
+
+pub const MIN_SEATS: usize = 2;
+pub const MAX_SEATS: usize = 10;
+/// Number of letters in a room code
+pub const ROOM_CODE_LENGTH: usize = 4;
+
+/// Everything the host chooses before starting the game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomSettings {
+    pub seats: usize,
+    pub deck: DeckRecipe,
+    pub rules: HouseRules,
+    /// Seed of the game, a random one is picked when `None`
+    pub seed: Option<u64>,
+}
+
+impl Default for RoomSettings {
+    fn default() -> Self {
+        Self {
+            seats: 4,
+            deck: DeckRecipe::default(),
+            rules: HouseRules::default(),
+            seed: None,
+        }
+    }
+}
+
+impl RoomSettings {
+    /// The deck must keep at least one card once every seat got its hand, for the discard pile
+    pub fn check_cards(&self) -> Result<(), LobbyError> {
+        let cards = DeckGenerator::from(self.deck).generate_deck().len();
+
+        if self.seats * self.rules.hand_size >= cards {
+            return Err(LobbyError::NotEnoughCards {
+                seats: self.seats,
+                hand_size: self.rules.hand_size,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A seat of a room as shown in the lobby
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeatInfo {
+    Empty,
+    Human { ready: bool, host: bool, away: bool },
+    Bot(Difficulty),
+}
+
+/// A room as seen by one of its members
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomInfo {
+    /// Code other players type to join
+    pub code: String,
+    pub settings: RoomSettings,
+    pub seats: Vec<SeatInfo>,
+    /// Seat of the member this info was sent to
+    pub seat: Option<Seat>,
+    pub is_host: bool,
+}
+
+/// Room the local player is in, `None` until one is created or joined
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CurrentRoom(pub Option<RoomInfo>);
+
+/// Last request the server refused, shown in the lobby until the room changes
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LobbyNotice(pub Option<String>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LobbyError {
+    NoSuchRoom(String),
+    NotInRoom,
+    NotHost,
+    NotSeated,
+    NoSuchSeat(Seat),
+    SeatTaken(Seat),
+    InvalidSeatCount(usize),
+    /// The deck can't deal a hand of `hand_size` to every seat
+    NotEnoughCards {
+        seats: usize,
+        hand_size: usize,
+    },
+    EmptySeats,
+    NotReady,
+    AlreadyStarted,
+    NotStarted,
+    SessionExpired,
+}
+
+impl fmt::Display for LobbyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LobbyError::NoSuchRoom(code) => write!(f, "there is no room {}", code),
+            LobbyError::NotInRoom => write!(f, "join a room first"),
+            LobbyError::NotHost => write!(f, "only the host can do this"),
+            LobbyError::NotSeated => write!(f, "take a seat first"),
+            LobbyError::NoSuchSeat(seat) => write!(f, "there is no seat {}", seat + 1),
+            LobbyError::SeatTaken(seat) => write!(f, "seat {} is taken", seat + 1),
+            LobbyError::InvalidSeatCount(seats) => write!(
+                f,
+                "a room has {} to {} seats, not {}",
+                MIN_SEATS, MAX_SEATS, seats
+            ),
+            LobbyError::NotEnoughCards { seats, hand_size } => write!(
+                f,
+                "the deck can't deal {} cards to {} seats",
+                hand_size, seats
+            ),
+            LobbyError::EmptySeats => write!(f, "every seat needs a player or a bot"),
+            LobbyError::NotReady => write!(f, "every player must be ready"),
+            LobbyError::AlreadyStarted => write!(f, "the game already started"),
+            LobbyError::NotStarted => write!(f, "the game has not started"),
+            LobbyError::SessionExpired => write!(f, "the session expired, join a room again"),
+        }
+    }
+}
+
+impl std::error::Error for LobbyError {}
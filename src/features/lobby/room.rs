@@ -0,0 +1,575 @@
+use super::{LobbyError, RoomInfo, RoomSettings, SeatInfo, MAX_SEATS, MIN_SEATS};
+use crate::features::{
+    ai::Difficulty,
+    deck::Deck,
+    rules::{GameState, Seat},
+};
+
+/// Identifies a connection to the server
+pub type ClientId = u64;
+
+/// Players leaving a running game are replaced by this bot
+pub const REPLACEMENT_BOT: Difficulty = Difficulty::Normal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Occupant {
+    Empty,
+    Human { client: ClientId, ready: bool },
+    Bot(Difficulty),
+}
+
+/// ## A table players gather around before and during a game
+///
+/// Members may watch without a seat, the host is the only one changing the settings,
+/// filling seats with bots and starting the game.
+/// Disconnected members stay `away` with their seat until they come back or leave for good.
+#[derive(Debug, Clone)]
+pub struct Room {
+    pub code: String,
+    pub host: ClientId,
+    pub members: Vec<ClientId>,
+    pub away: Vec<ClientId>,
+    /// Spectators seeing every hand, with a delay
+    pub god_viewers: Vec<ClientId>,
+    pub seats: Vec<Occupant>,
+    pub settings: RoomSettings,
+    pub game: Option<GameState>,
+}
+
+impl Room {
+    /// A room with default settings, its host sits at the first seat
+    pub fn new(code: String, host: ClientId) -> Self {
+        let settings = RoomSettings::default();
+        let mut seats = vec![Occupant::Empty; settings.seats];
+        seats[0] = Occupant::Human {
+            client: host,
+            ready: false,
+        };
+
+        Self {
+            code,
+            host,
+            members: vec![host],
+            away: Vec::new(),
+            god_viewers: Vec::new(),
+            seats,
+            settings,
+            game: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn seat_of(&self, client: ClientId) -> Option<Seat> {
+        self.seats.iter().position(
+            |occupant| matches!(occupant, Occupant::Human { client: other, .. } if *other == client),
+        )
+    }
+
+    fn check_host(&self, client: ClientId) -> Result<(), LobbyError> {
+        if client == self.host {
+            Ok(())
+        } else {
+            Err(LobbyError::NotHost)
+        }
+    }
+
+    fn check_not_started(&self) -> Result<(), LobbyError> {
+        match self.game {
+            Some(_) => Err(LobbyError::AlreadyStarted),
+            None => Ok(()),
+        }
+    }
+
+    /// Settings changed, everyone has to agree again
+    fn unready_everyone(&mut self) {
+        self.seats.iter_mut().for_each(|occupant| {
+            if let Occupant::Human { ready, .. } = occupant {
+                *ready = false;
+            }
+        });
+    }
+
+    pub fn join(&mut self, client: ClientId) -> Result<(), LobbyError> {
+        self.check_not_started()?;
+
+        if !self.members.contains(&client) {
+            self.members.push(client);
+        }
+        Ok(())
+    }
+
+    /// Watch the room without a seat, even once the game started
+    pub fn spectate(&mut self, client: ClientId, god_view: bool) {
+        if !self.members.contains(&client) {
+            self.members.push(client);
+        }
+        if god_view && !self.god_viewers.contains(&client) {
+            self.god_viewers.push(client);
+        }
+    }
+
+    pub fn is_god_viewer(&self, client: ClientId) -> bool {
+        self.god_viewers.contains(&client)
+    }
+
+    /// Remove `client` from the room, its seat is freed or given to a bot when playing,
+    /// the next member becomes host if needed
+    pub fn leave(&mut self, client: ClientId) {
+        self.members.retain(|member| *member != client);
+        self.away.retain(|member| *member != client);
+        self.god_viewers.retain(|member| *member != client);
+
+        if let Some(seat) = self.seat_of(client) {
+            self.seats[seat] = match self.game {
+                Some(_) => Occupant::Bot(REPLACEMENT_BOT),
+                None => Occupant::Empty,
+            };
+        }
+        if self.host == client {
+            if let Some(member) = self.members.first() {
+                self.host = *member;
+            }
+        }
+    }
+
+    /// A bot plays the seat from now on, its player stays in the room to watch
+    pub fn give_to_bot(&mut self, seat: Seat) {
+        if let Some(occupant) = self.seats.get_mut(seat) {
+            *occupant = Occupant::Bot(REPLACEMENT_BOT);
+        }
+    }
+
+    /// Keep the seat of `client` while waiting for it to reconnect
+    pub fn disconnect(&mut self, client: ClientId) {
+        if self.members.contains(&client) && !self.away.contains(&client) {
+            self.away.push(client);
+        }
+    }
+
+    /// `client` is back as `new_client`, with its seat and role
+    pub fn reconnect(&mut self, client: ClientId, new_client: ClientId) {
+        self.away.retain(|member| *member != client);
+        self.members
+            .iter_mut()
+            .chain(self.god_viewers.iter_mut())
+            .for_each(|member| {
+                if *member == client {
+                    *member = new_client;
+                }
+            });
+        if let Some(seat) = self.seat_of(client) {
+            if let Occupant::Human { client, .. } = &mut self.seats[seat] {
+                *client = new_client;
+            }
+        }
+        if self.host == client {
+            self.host = new_client;
+        }
+    }
+
+    /// Difficulty of the bot playing `seat`, `stand_in` plays for away players
+    pub fn bot_at(&self, seat: Seat, stand_in: Option<Difficulty>) -> Option<Difficulty> {
+        match self.seats.get(seat)? {
+            Occupant::Bot(difficulty) => Some(*difficulty),
+            Occupant::Human { client, .. } if self.away.contains(client) => stand_in,
+            _ => None,
+        }
+    }
+
+    /// Sit `client` at `seat`, leaving its previous one
+    pub fn take_seat(&mut self, client: ClientId, seat: Seat) -> Result<(), LobbyError> {
+        self.check_not_started()?;
+
+        match self.seats.get(seat) {
+            None => return Err(LobbyError::NoSuchSeat(seat)),
+            Some(Occupant::Empty) => {}
+            Some(_) => return Err(LobbyError::SeatTaken(seat)),
+        }
+
+        if let Some(previous) = self.seat_of(client) {
+            self.seats[previous] = Occupant::Empty;
+        }
+        self.seats[seat] = Occupant::Human {
+            client,
+            ready: false,
+        };
+        Ok(())
+    }
+
+    /// Put a bot of `difficulty` at an empty or bot `seat`, `None` empties it
+    pub fn set_bot(
+        &mut self,
+        client: ClientId,
+        seat: Seat,
+        difficulty: Option<Difficulty>,
+    ) -> Result<(), LobbyError> {
+        self.check_host(client)?;
+        self.check_not_started()?;
+
+        match self.seats.get(seat) {
+            None => return Err(LobbyError::NoSuchSeat(seat)),
+            Some(Occupant::Human { .. }) => return Err(LobbyError::SeatTaken(seat)),
+            Some(_) => {}
+        }
+
+        self.seats[seat] = difficulty.map_or(Occupant::Empty, Occupant::Bot);
+        Ok(())
+    }
+
+    /// Apply new settings, players sitting on removed seats stay in the room without a seat
+    pub fn change_settings(
+        &mut self,
+        client: ClientId,
+        settings: RoomSettings,
+    ) -> Result<(), LobbyError> {
+        self.check_host(client)?;
+        self.check_not_started()?;
+
+        if !(MIN_SEATS..=MAX_SEATS).contains(&settings.seats) {
+            return Err(LobbyError::InvalidSeatCount(settings.seats));
+        }
+        settings.check_cards()?;
+
+        self.seats.resize(settings.seats, Occupant::Empty);
+        self.settings = settings;
+        self.unready_everyone();
+        Ok(())
+    }
+
+    pub fn set_ready(&mut self, client: ClientId, ready: bool) -> Result<(), LobbyError> {
+        self.check_not_started()?;
+
+        let seat = self.seat_of(client).ok_or(LobbyError::NotSeated)?;
+        self.seats[seat] = Occupant::Human { client, ready };
+        Ok(())
+    }
+
+    /// Deal the cards once every seat is filled and every player is ready
+    pub fn start(&mut self, client: ClientId, seed: u64) -> Result<&GameState, LobbyError> {
+        self.check_host(client)?;
+        self.check_not_started()?;
+
+        if self.seats.contains(&Occupant::Empty) {
+            return Err(LobbyError::EmptySeats);
+        }
+        if self
+            .seats
+            .iter()
+            .any(|occupant| matches!(occupant, Occupant::Human { ready: false, .. }))
+        {
+            return Err(LobbyError::NotReady);
+        }
+        self.settings.check_cards()?;
+
+        let seed = self.settings.seed.unwrap_or(seed);
+        let deck = Deck::from_recipe(self.settings.deck, seed).into_cards();
+
+        Ok(self.game.insert(GameState::with_rules(
+            deck,
+            self.seats.len(),
+            seed,
+            self.settings.rules,
+        )))
+    }
+
+    /// The room as `client` sees it in the lobby
+    pub fn info_for(&self, client: ClientId) -> RoomInfo {
+        RoomInfo {
+            code: self.code.clone(),
+            settings: self.settings,
+            seats: self
+                .seats
+                .iter()
+                .map(|occupant| match occupant {
+                    Occupant::Empty => SeatInfo::Empty,
+                    Occupant::Human { client, ready } => SeatInfo::Human {
+                        ready: *ready,
+                        host: *client == self.host,
+                        away: self.away.contains(client),
+                    },
+                    Occupant::Bot(difficulty) => SeatInfo::Bot(*difficulty),
+                })
+                .collect(),
+            seat: self.seat_of(client),
+            is_host: client == self.host,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::rules::HouseRules;
+
+    const HOST: ClientId = 1;
+    const GUEST: ClientId = 2;
+
+    fn room() -> Room {
+        let mut room = Room::new("ABCD".to_string(), HOST);
+        room.join(GUEST).unwrap();
+        room
+    }
+
+    /// Host at seat 0 and guest at seat 1, both ready, bots on the other seats
+    fn ready_room() -> Room {
+        let mut room = room();
+        room.take_seat(GUEST, 1).unwrap();
+        room.set_bot(HOST, 2, Some(Difficulty::Easy)).unwrap();
+        room.set_bot(HOST, 3, Some(Difficulty::Hard)).unwrap();
+        room.set_ready(HOST, true).unwrap();
+        room.set_ready(GUEST, true).unwrap();
+        room
+    }
+
+    mod take_seat {
+        use super::*;
+
+        #[test]
+        fn moves_to_new_seat() {
+            let mut room = room();
+
+            room.take_seat(GUEST, 2).unwrap();
+            room.take_seat(GUEST, 3).unwrap();
+
+            assert_eq!(room.seat_of(GUEST), Some(3));
+            assert_eq!(room.seats[2], Occupant::Empty);
+        }
+
+        #[test]
+        fn taken_seat() {
+            let mut room = room();
+            room.set_bot(HOST, 2, Some(Difficulty::Easy)).unwrap();
+
+            assert_eq!(room.take_seat(GUEST, 0), Err(LobbyError::SeatTaken(0)));
+            assert_eq!(room.take_seat(GUEST, 2), Err(LobbyError::SeatTaken(2)));
+            assert_eq!(room.take_seat(GUEST, 9), Err(LobbyError::NoSuchSeat(9)));
+        }
+    }
+
+    mod set_bot {
+        use super::*;
+
+        #[test]
+        fn only_host() {
+            let mut room = room();
+
+            assert_eq!(
+                room.set_bot(GUEST, 1, Some(Difficulty::Easy)),
+                Err(LobbyError::NotHost)
+            );
+            assert_eq!(room.set_bot(HOST, 0, None), Err(LobbyError::SeatTaken(0)));
+        }
+    }
+
+    mod change_settings {
+        use super::*;
+
+        #[test]
+        fn resizes_seats_and_unready() {
+            let mut room = ready_room();
+            let settings = RoomSettings {
+                seats: 2,
+                rules: HouseRules {
+                    hand_size: 5,
+                    ..HouseRules::default()
+                },
+                ..RoomSettings::default()
+            };
+
+            room.change_settings(HOST, settings).unwrap();
+
+            assert_eq!(room.seats.len(), 2);
+            assert_eq!(room.settings, settings);
+            assert_eq!(room.start(HOST, 0).err(), Some(LobbyError::NotReady));
+        }
+
+        #[test]
+        fn invalid_seat_count() {
+            let mut room = room();
+            let settings = RoomSettings {
+                seats: 11,
+                ..RoomSettings::default()
+            };
+
+            assert_eq!(
+                room.change_settings(HOST, settings),
+                Err(LobbyError::InvalidSeatCount(11))
+            );
+        }
+
+        #[test]
+        fn hands_must_fit_the_deck() {
+            let mut room = room();
+            let mut settings = RoomSettings {
+                seats: 10,
+                rules: HouseRules {
+                    hand_size: 11,
+                    ..HouseRules::default()
+                },
+                ..RoomSettings::default()
+            };
+
+            assert_eq!(
+                room.change_settings(HOST, settings),
+                Err(LobbyError::NotEnoughCards {
+                    seats: 10,
+                    hand_size: 11
+                })
+            );
+            settings.rules.hand_size = 10;
+            assert_eq!(room.change_settings(HOST, settings), Ok(()));
+        }
+    }
+
+    mod leave {
+        use super::*;
+
+        #[test]
+        fn host_passed_on() {
+            let mut room = room();
+            room.take_seat(GUEST, 1).unwrap();
+
+            room.leave(HOST);
+
+            assert_eq!(room.host, GUEST);
+            assert_eq!(room.seats[0], Occupant::Empty);
+            assert!(room.info_for(GUEST).is_host);
+        }
+
+        #[test]
+        fn bot_replaces_player_in_game() {
+            let mut room = ready_room();
+            room.start(HOST, 0).unwrap();
+
+            room.leave(GUEST);
+
+            assert_eq!(room.seats[1], Occupant::Bot(REPLACEMENT_BOT));
+        }
+    }
+
+    mod spectate {
+        use super::*;
+
+        #[test]
+        fn running_game() {
+            let mut room = ready_room();
+            room.start(HOST, 0).unwrap();
+
+            room.spectate(3, true);
+            room.spectate(4, false);
+
+            assert_eq!(room.members, vec![HOST, GUEST, 3, 4]);
+            assert!(room.is_god_viewer(3));
+            assert!(!room.is_god_viewer(4));
+            assert_eq!(room.info_for(3).seat, None);
+
+            room.leave(3);
+
+            assert!(!room.is_god_viewer(3));
+        }
+    }
+
+    mod reconnect {
+        use super::*;
+
+        #[test]
+        fn keeps_seat_and_host() {
+            let mut room = ready_room();
+            room.start(HOST, 0).unwrap();
+            room.disconnect(HOST);
+
+            assert_eq!(
+                room.bot_at(0, Some(Difficulty::Easy)),
+                Some(Difficulty::Easy)
+            );
+            assert_eq!(room.bot_at(0, None), None);
+
+            room.reconnect(HOST, 5);
+
+            assert_eq!(room.seat_of(5), Some(0));
+            assert_eq!(room.host, 5);
+            assert!(room.away.is_empty());
+            assert_eq!(room.bot_at(0, Some(Difficulty::Easy)), None);
+            assert_eq!(room.bot_at(3, None), Some(Difficulty::Hard));
+        }
+    }
+
+    mod start {
+        use super::*;
+
+        #[test]
+        fn needs_full_and_ready_table() {
+            let mut room = room();
+            room.take_seat(GUEST, 1).unwrap();
+
+            assert_eq!(room.start(HOST, 0).err(), Some(LobbyError::EmptySeats));
+
+            room.set_bot(HOST, 2, Some(Difficulty::Easy)).unwrap();
+            room.set_bot(HOST, 3, Some(Difficulty::Easy)).unwrap();
+
+            assert_eq!(room.start(HOST, 0).err(), Some(LobbyError::NotReady));
+        }
+
+        #[test]
+        fn deals_with_settings() {
+            let mut room = ready_room();
+            room.settings.seed = Some(3);
+
+            let state = room.start(HOST, 0).unwrap().clone();
+
+            assert_eq!(state, GameState::from_seed(4, 3));
+            assert_eq!(room.start(HOST, 0).err(), Some(LobbyError::AlreadyStarted));
+            assert_eq!(room.join(3), Err(LobbyError::AlreadyStarted));
+        }
+
+        #[test]
+        fn never_deals_more_than_the_deck() {
+            let mut room = ready_room();
+            room.settings.rules.hand_size = 27;
+
+            assert_eq!(
+                room.start(HOST, 0).err(),
+                Some(LobbyError::NotEnoughCards {
+                    seats: 4,
+                    hand_size: 27
+                })
+            );
+            assert!(room.game.is_none());
+        }
+    }
+
+    mod info_for {
+        use super::*;
+
+        #[test]
+        fn seen_by_guest() {
+            let mut room = ready_room();
+            room.disconnect(GUEST);
+
+            let info = room.info_for(GUEST);
+
+            assert_eq!(info.seat, Some(1));
+            assert!(!info.is_host);
+            assert_eq!(
+                info.seats,
+                vec![
+                    SeatInfo::Human {
+                        ready: true,
+                        host: true,
+                        away: false
+                    },
+                    SeatInfo::Human {
+                        ready: true,
+                        host: false,
+                        away: true
+                    },
+                    SeatInfo::Bot(Difficulty::Easy),
+                    SeatInfo::Bot(Difficulty::Hard),
+                ]
+            );
+        }
+    }
+}
@@ -0,0 +1,462 @@
+use bevy::prelude::*;
+
+use super::{
+    CurrentRoom, LobbyNotice, RoomInfo, RoomSettings, SeatInfo, MAX_SEATS, MIN_SEATS,
+    ROOM_CODE_LENGTH,
+};
+use crate::features::{
+    ai::Difficulty,
+    deck::DeckRecipe,
+    game::AppState,
+    network::{client::ServerConnection, ClientMessage},
+    rules::{drawing::DrawRule, stacking::Stacking},
+};
+
+const MIN_HAND_SIZE: usize = 3;
+const MAX_HAND_SIZE: usize = 10;
+const TARGET_SCORE_STEP: u32 = 100;
+const MAX_TARGET_SCORE: u32 = 1000;
+
+const BUTTON_COLOR: Color = Color::DARK_GRAY;
+
+/// Room code typed by the player before joining
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CodeInput(pub String);
+
+#[derive(Component)]
+struct LobbyMarker;
+
+/// Message sent to the server when the button is pressed
+#[derive(Component)]
+struct LobbyButton(ClientMessage);
+
+/// Menus to create or join a room and set it up until the game starts
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CodeInput>()
+            .add_systems(
+                Update,
+                (
+                    type_code,
+                    sync_lobby_ui.run_if(
+                        resource_changed::<CurrentRoom>
+                            .or_else(resource_changed::<CodeInput>)
+                            .or_else(resource_changed::<LobbyNotice>),
+                    ),
+                    press_buttons,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Lobby))
+                    .run_if(resource_exists::<ServerConnection>),
+            )
+            .add_systems(OnExit(AppState::Lobby), despawn_lobby_ui);
+    }
+}
+
+/// Seat content the host switches to when pressing the bot button of a seat
+fn next_bot(current: Option<Difficulty>) -> Option<Difficulty> {
+    match current {
+        None => Some(Difficulty::Easy),
+        Some(Difficulty::Easy) => Some(Difficulty::Normal),
+        Some(Difficulty::Normal) => Some(Difficulty::Hard),
+        Some(Difficulty::Hard) => None,
+    }
+}
+
+fn next_deck(deck: DeckRecipe) -> DeckRecipe {
+    match deck {
+        DeckRecipe::Classic => DeckRecipe::Double,
+        DeckRecipe::Double => DeckRecipe::NumbersOnly,
+        DeckRecipe::NumbersOnly => DeckRecipe::Flip,
+        DeckRecipe::Flip => DeckRecipe::Party,
+        DeckRecipe::Party => DeckRecipe::Classic,
+    }
+}
+
+fn next_hand_size(hand_size: usize) -> usize {
+    if hand_size >= MAX_HAND_SIZE {
+        MIN_HAND_SIZE
+    } else {
+        hand_size + 1
+    }
+}
+
+fn next_target_score(target_score: u32) -> u32 {
+    if target_score >= MAX_TARGET_SCORE {
+        TARGET_SCORE_STEP
+    } else {
+        (target_score / TARGET_SCORE_STEP + 1) * TARGET_SCORE_STEP
+    }
+}
+
+fn next_stacking(stacking: Stacking) -> Stacking {
+    match stacking {
+        Stacking::Off => Stacking::SameType,
+        Stacking::SameType => Stacking::Mixed,
+        Stacking::Mixed => Stacking::Off,
+    }
+}
+
+fn next_draw_rule(draw: DrawRule) -> DrawRule {
+    match draw {
+        DrawRule::DrawOne => DrawRule::UntilPlayable,
+        DrawRule::UntilPlayable => DrawRule::DrawOne,
+    }
+}
+
+/// Seconds per turn the host cycles through, turns are not timed after the longest one
+const TURN_TIMES: [u32; 3] = [15, 30, 60];
+
+fn next_turn_time(turn_time: Option<u32>) -> Option<u32> {
+    match turn_time {
+        None => Some(TURN_TIMES[0]),
+        Some(seconds) => TURN_TIMES.into_iter().find(|next| *next > seconds),
+    }
+}
+
+fn seat_label(seat: SeatInfo) -> String {
+    match seat {
+        SeatInfo::Empty => "empty".to_string(),
+        SeatInfo::Human { ready, host, away } => format!(
+            "player{}{}{}",
+            if host { " (host)" } else { "" },
+            if ready { ", ready" } else { "" },
+            if away { ", away" } else { "" }
+        ),
+        SeatInfo::Bot(difficulty) => format!("{:?} bot", difficulty),
+    }
+}
+
+fn type_code(
+    current_room: Res<CurrentRoom>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut code_input: ResMut<CodeInput>,
+) {
+    if current_room.0.is_some() {
+        characters.clear();
+        return;
+    }
+
+    for event in characters.read() {
+        for letter in event.char.chars().filter(char::is_ascii_alphabetic) {
+            if code_input.0.len() < ROOM_CODE_LENGTH {
+                code_input.0.push(letter.to_ascii_uppercase());
+            }
+        }
+    }
+    if keys.just_pressed(KeyCode::Backspace) {
+        code_input.0.pop();
+    }
+}
+
+fn spawn_text(builder: &mut ChildBuilder, text: impl Into<String>) {
+    builder.spawn(TextBundle::from_section(text, TextStyle::default()));
+}
+
+fn spawn_button(builder: &mut ChildBuilder, label: impl Into<String>, message: ClientMessage) {
+    builder
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    padding: UiRect::all(Val::Px(5.)),
+                    ..default()
+                },
+                background_color: BackgroundColor(BUTTON_COLOR),
+                ..default()
+            },
+            LobbyButton(message),
+        ))
+        .with_children(|builder| spawn_text(builder, label));
+}
+
+fn spawn_row(builder: &mut ChildBuilder, children: impl FnOnce(&mut ChildBuilder)) {
+    builder
+        .spawn(NodeBundle {
+            style: Style {
+                column_gap: Val::Px(10.),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(children);
+}
+
+/// Rebuild the whole menu, it only changes when the room or the typed code does
+fn sync_lobby_ui(
+    mut commands: Commands,
+    current_room: Res<CurrentRoom>,
+    code_input: Res<CodeInput>,
+    notice: Res<LobbyNotice>,
+    lobby_query: Query<Entity, With<LobbyMarker>>,
+) {
+    lobby_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(20.),
+                    top: Val::Px(20.),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            LobbyMarker,
+        ))
+        .with_children(|builder| {
+            match &current_room.0 {
+                None => spawn_menu(builder, &code_input.0),
+                Some(room) => spawn_room(builder, room),
+            }
+            if let Some(reason) = &notice.0 {
+                spawn_text(builder, reason.clone());
+            }
+        });
+}
+
+fn spawn_menu(builder: &mut ChildBuilder, code: &str) {
+    spawn_button(builder, "Create a room", ClientMessage::CreateRoom);
+    spawn_row(builder, |builder| {
+        spawn_text(builder, format!("Room code: {}_", code));
+        spawn_button(builder, "Join", ClientMessage::JoinRoom(code.to_string()));
+        spawn_button(
+            builder,
+            "Watch",
+            ClientMessage::Spectate {
+                code: code.to_string(),
+                god_view: false,
+            },
+        );
+        spawn_button(
+            builder,
+            "Watch every hand (delayed)",
+            ClientMessage::Spectate {
+                code: code.to_string(),
+                god_view: true,
+            },
+        );
+    });
+}
+
+fn spawn_room(builder: &mut ChildBuilder, room: &RoomInfo) {
+    let settings = room.settings;
+    spawn_text(builder, format!("Room {}", room.code));
+
+    spawn_row(builder, |builder| {
+        spawn_text(builder, format!("{} seats", settings.seats));
+        if room.is_host {
+            if settings.seats > MIN_SEATS {
+                let seats = settings.seats - 1;
+                let message = ClientMessage::ChangeSettings(RoomSettings { seats, ..settings });
+                spawn_button(builder, "-", message);
+            }
+            if settings.seats < MAX_SEATS {
+                let seats = settings.seats + 1;
+                let message = ClientMessage::ChangeSettings(RoomSettings { seats, ..settings });
+                spawn_button(builder, "+", message);
+            }
+        }
+    });
+
+    let deck = format!("Deck: {}", String::from(settings.deck));
+    let hand_size = format!("Hand size: {}", settings.rules.hand_size);
+    let target_score = format!("Match to {} points", settings.rules.target_score);
+    let stacking = format!("Stacking: {}", String::from(settings.rules.stacking));
+    let jump_in = format!(
+        "Jump-in: {}",
+        if settings.rules.jump_in { "on" } else { "off" }
+    );
+    let seven_o = format!(
+        "Seven-O: {}",
+        if settings.rules.seven_o { "on" } else { "off" }
+    );
+    let draw = format!("Draw: {}", String::from(settings.rules.draw));
+    let forced_play = format!(
+        "Forced play: {}",
+        if settings.rules.forced_play {
+            "on"
+        } else {
+            "off"
+        }
+    );
+    let turn_time = match settings.rules.turn_time {
+        Some(seconds) => format!("Turn time: {}s", seconds),
+        None => "Turn time: off".to_string(),
+    };
+    if room.is_host {
+        let mut message = settings;
+        message.deck = next_deck(settings.deck);
+        spawn_button(builder, deck, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.hand_size = next_hand_size(settings.rules.hand_size);
+        spawn_button(builder, hand_size, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.target_score = next_target_score(settings.rules.target_score);
+        spawn_button(
+            builder,
+            target_score,
+            ClientMessage::ChangeSettings(message),
+        );
+
+        let mut message = settings;
+        message.rules.stacking = next_stacking(settings.rules.stacking);
+        spawn_button(builder, stacking, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.jump_in = !settings.rules.jump_in;
+        spawn_button(builder, jump_in, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.seven_o = !settings.rules.seven_o;
+        spawn_button(builder, seven_o, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.draw = next_draw_rule(settings.rules.draw);
+        spawn_button(builder, draw, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.forced_play = !settings.rules.forced_play;
+        spawn_button(builder, forced_play, ClientMessage::ChangeSettings(message));
+
+        let mut message = settings;
+        message.rules.turn_time = next_turn_time(settings.rules.turn_time);
+        spawn_button(builder, turn_time, ClientMessage::ChangeSettings(message));
+    } else {
+        spawn_text(builder, deck);
+        spawn_text(builder, hand_size);
+        spawn_text(builder, target_score);
+        spawn_text(builder, stacking);
+        spawn_text(builder, jump_in);
+        spawn_text(builder, seven_o);
+        spawn_text(builder, draw);
+        spawn_text(builder, forced_play);
+        spawn_text(builder, turn_time);
+    }
+
+    for (seat, info) in room.seats.iter().enumerate() {
+        spawn_row(builder, |builder| {
+            let you = if room.seat == Some(seat) {
+                " (you)"
+            } else {
+                ""
+            };
+            spawn_text(
+                builder,
+                format!("Seat {}: {}{}", seat + 1, seat_label(*info), you),
+            );
+
+            if *info == SeatInfo::Empty {
+                spawn_button(builder, "Sit", ClientMessage::TakeSeat(seat));
+            }
+            let bot = match info {
+                SeatInfo::Empty => Some(None),
+                SeatInfo::Bot(difficulty) => Some(Some(*difficulty)),
+                SeatInfo::Human { .. } => None,
+            };
+            if let (true, Some(bot)) = (room.is_host, bot) {
+                let difficulty = next_bot(bot);
+                spawn_button(builder, "Bot", ClientMessage::SetBot { seat, difficulty });
+            }
+        });
+    }
+
+    spawn_row(builder, |builder| {
+        if let Some(SeatInfo::Human { ready, .. }) = room.seat.map(|seat| room.seats[seat]) {
+            let label = if ready { "Not ready" } else { "Ready" };
+            spawn_button(builder, label, ClientMessage::Ready(!ready));
+        }
+        if room.is_host {
+            spawn_button(builder, "Start", ClientMessage::Start);
+        }
+        spawn_button(builder, "Leave", ClientMessage::LeaveRoom);
+    });
+}
+
+fn press_buttons(
+    connection: Res<ServerConnection>,
+    buttons_query: Query<(&Interaction, &LobbyButton), Changed<Interaction>>,
+) {
+    buttons_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .for_each(|(_, LobbyButton(message))| connection.send(message.clone()));
+}
+
+fn despawn_lobby_ui(mut commands: Commands, lobby_query: Query<Entity, With<LobbyMarker>>) {
+    lobby_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn_recursive());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod next_bot {
+        use super::*;
+
+        #[test]
+        fn cycles_back_to_empty() {
+            let mut bot = None;
+            let mut seen = Vec::new();
+
+            for _ in 0..4 {
+                bot = next_bot(bot);
+                seen.push(bot);
+            }
+
+            assert_eq!(
+                seen,
+                vec![
+                    Some(Difficulty::Easy),
+                    Some(Difficulty::Normal),
+                    Some(Difficulty::Hard),
+                    None
+                ]
+            );
+        }
+    }
+
+    mod next_turn_time {
+        use super::*;
+
+        #[test]
+        fn cycles_back_to_off() {
+            assert_eq!(next_turn_time(None), Some(15));
+            assert_eq!(next_turn_time(Some(15)), Some(30));
+            assert_eq!(next_turn_time(Some(60)), None);
+        }
+    }
+
+    mod next_hand_size {
+        use super::*;
+
+        #[test]
+        fn wraps_around() {
+            assert_eq!(next_hand_size(7), 8);
+            assert_eq!(next_hand_size(MAX_HAND_SIZE), MIN_HAND_SIZE);
+        }
+    }
+
+    mod next_target_score {
+        use super::*;
+
+        #[test]
+        fn rounds_up_and_wraps_around() {
+            assert_eq!(next_target_score(500), 600);
+            assert_eq!(next_target_score(250), 300);
+            assert_eq!(next_target_score(MAX_TARGET_SCORE), TARGET_SCORE_STEP);
+        }
+    }
+}
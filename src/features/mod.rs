@@ -1,2 +1,12 @@
+pub mod ai;
 pub mod cards;
 pub mod deck;
+pub mod game;
+pub mod lobby;
+pub mod network;
+pub mod replay;
+pub mod rules;
+pub mod save;
+pub mod settings;
+pub mod simulation;
+pub mod terminal;
@@ -0,0 +1,479 @@
+use std::{
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use bevy::prelude::*;
+
+use super::{ClientMessage, ServerMessage};
+use crate::features::{
+    game::{
+        AppState, ApplyRequestsSet, CallUnoRequest, CatchUnoRequest, ChallengeRequest,
+        DrawCardRequest, LocalPlayer, NextRoundRequest, PassRequest, PlayCardRequest, Player,
+        SwapHandsRequest, TableEvent, TableView,
+    },
+    lobby::{CurrentRoom, LobbyNotice},
+    rules::{projection::PlayerView, Action, Seat},
+};
+
+/// WebSocket url of the game server, the game is played locally when this resource is missing
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ServerAddress(pub String);
+
+impl ServerAddress {
+    /// Reads `--server ws://127.0.0.1:7878` from command line arguments
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut args = args.into_iter();
+
+        args.find(|arg| arg == "--server")
+            .and_then(|_| args.next())
+            .map(ServerAddress)
+    }
+}
+
+/// Time between two attempts to reach the server
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Messages going to and coming from the server, the socket itself lives outside of the ECS.
+/// Lost connections are opened again and resume the session, the app only notices
+/// through the snapshot the server sends back
+#[derive(Resource)]
+pub struct ServerConnection {
+    outgoing: Sender<ClientMessage>,
+    incoming: Mutex<Receiver<ServerMessage>>,
+}
+
+impl ServerConnection {
+    /// Starts connecting to `url` in the background
+    pub fn open(url: &str) -> Self {
+        let (outgoing, outgoing_receiver) = mpsc::channel();
+        let (incoming_sender, incoming) = mpsc::channel();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let url = url.to_string();
+            std::thread::spawn(move || native::run(&url, outgoing_receiver, incoming_sender));
+        }
+        #[cfg(target_arch = "wasm32")]
+        web::open(url, outgoing_receiver, incoming_sender);
+
+        Self {
+            outgoing,
+            incoming: Mutex::new(incoming),
+        }
+    }
+
+    pub fn send(&self, message: ClientMessage) {
+        if let Err(error) = self.outgoing.send(message) {
+            warn!("Lost connection to the server, {:?} not sent", error.0);
+        }
+    }
+
+    pub fn try_receive(&self) -> Option<ServerMessage> {
+        self.incoming
+            .lock()
+            .expect("the connection lock is never poisoned")
+            .try_recv()
+            .ok()
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod native {
+    use std::{
+        net::TcpStream,
+        sync::mpsc::{Receiver, Sender, TryRecvError},
+    };
+
+    use bevy::log::{info, warn};
+    use tungstenite::{stream::MaybeTlsStream, WebSocket};
+
+    use super::{
+        super::{
+            socket::{self, Received},
+            ClientMessage, ServerMessage, POLL_INTERVAL,
+        },
+        RECONNECT_DELAY,
+    };
+
+    /// Why the connection ended
+    enum Ended {
+        AppClosed,
+        ConnectionLost,
+    }
+
+    /// Keeps reconnecting until the app is closed, resuming the session each time
+    pub fn run(url: &str, outgoing: Receiver<ClientMessage>, incoming: Sender<ServerMessage>) {
+        let mut token = None;
+
+        loop {
+            match tungstenite::connect(url) {
+                Ok((mut socket, _)) => {
+                    if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+                        socket::set_nonblocking(stream);
+                    }
+                    if let Some(token) = &token {
+                        info!("Reconnected to {}", url);
+                        socket::send(&mut socket, &ClientMessage::Resume(String::clone(token)));
+                    }
+
+                    match exchange(&mut socket, &outgoing, &incoming, &mut token) {
+                        Ended::AppClosed => return,
+                        Ended::ConnectionLost => warn!("Connection to {} lost", url),
+                    }
+                }
+                Err(error) => warn!("Failed to connect to {}: {}", url, error),
+            }
+            std::thread::sleep(RECONNECT_DELAY);
+        }
+    }
+
+    /// Pass messages both ways until the socket or the app is closed
+    fn exchange(
+        socket: &mut WebSocket<MaybeTlsStream<TcpStream>>,
+        outgoing: &Receiver<ClientMessage>,
+        incoming: &Sender<ServerMessage>,
+        token: &mut Option<String>,
+    ) -> Ended {
+        loop {
+            loop {
+                match outgoing.try_recv() {
+                    Ok(message) => {
+                        if !socket::send(socket, &message) {
+                            return Ended::ConnectionLost;
+                        }
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => {
+                        let _ = socket.close(None);
+                        let _ = socket.flush();
+                        return Ended::AppClosed;
+                    }
+                }
+            }
+            if !socket::flush(socket) {
+                return Ended::ConnectionLost;
+            }
+
+            match socket::receive(socket) {
+                Received::Message(ServerMessage::Session(session)) => *token = Some(session),
+                Received::Message(message) => {
+                    if incoming.send(message).is_err() {
+                        return Ended::AppClosed;
+                    }
+                }
+                Received::Nothing => std::thread::sleep(POLL_INTERVAL),
+                Received::Closed => return Ended::ConnectionLost,
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod web {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::mpsc::{Receiver, Sender},
+        time::Duration,
+    };
+
+    use bevy::log::{info, warn};
+    use wasm_bindgen::{closure::Closure, JsCast};
+    use web_sys::{MessageEvent, WebSocket};
+
+    use super::{
+        super::{ClientMessage, ServerMessage, POLL_INTERVAL},
+        RECONNECT_DELAY,
+    };
+
+    /// Session token shared by the socket callbacks
+    type Token = Rc<RefCell<Option<String>>>;
+
+    fn connect(url: &str, incoming: Sender<ServerMessage>, token: Token) -> Option<WebSocket> {
+        let socket = match WebSocket::new(url) {
+            Ok(socket) => socket,
+            Err(error) => {
+                warn!("Failed to connect to {}: {:?}", url, error);
+                return None;
+            }
+        };
+
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            let Some(text) = event.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str(&text) {
+                Ok(ServerMessage::Session(session)) => *token.borrow_mut() = Some(session),
+                Ok(message) => {
+                    let _ = incoming.send(message);
+                }
+                Err(error) => warn!("Skipped invalid message {:?}: {}", text, error),
+            }
+        });
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        on_message.forget();
+
+        Some(socket)
+    }
+
+    fn send(socket: &WebSocket, message: &ClientMessage) {
+        let text = serde_json::to_string(message).expect("messages are always serializable");
+        if let Err(error) = socket.send_with_str(&text) {
+            warn!("Failed to send {:?}: {:?}", message, error);
+        }
+    }
+
+    /// Browsers own the socket, its callbacks live as long as the page.
+    /// Closed sockets are opened again and resume the session
+    pub fn open(url: &str, outgoing: Receiver<ClientMessage>, incoming: Sender<ServerMessage>) {
+        let url = url.to_string();
+        let token = Token::default();
+        let mut socket = connect(&url, incoming.clone(), token.clone());
+        // Token of the lost session, the new connection gets a fresh one before it can resume
+        let mut resuming: Option<String> = None;
+        let mut waited = Duration::ZERO;
+
+        let send_outgoing = Closure::<dyn FnMut()>::new(move || {
+            let open = socket.as_ref().map(WebSocket::ready_state);
+            if open != Some(WebSocket::OPEN) {
+                if open.is_none() || open == Some(WebSocket::CLOSED) {
+                    waited += POLL_INTERVAL;
+                    if waited >= RECONNECT_DELAY {
+                        waited = Duration::ZERO;
+                        resuming = token.borrow().clone();
+                        socket = connect(&url, incoming.clone(), token.clone());
+                    }
+                }
+                return;
+            }
+            let Some(socket) = &socket else {
+                return;
+            };
+
+            if let Some(token) = resuming.take() {
+                info!("Reconnected to {}", url);
+                send(socket, &ClientMessage::Resume(token));
+            }
+            while let Ok(message) = outgoing.try_recv() {
+                send(socket, &message);
+            }
+        });
+        web_sys::window()
+            .expect("the game runs in a browser window")
+            .set_interval_with_callback_and_timeout_and_arguments_0(
+                send_outgoing.as_ref().unchecked_ref(),
+                POLL_INTERVAL.as_millis() as i32,
+            )
+            .expect("intervals can always be set");
+        send_outgoing.forget();
+    }
+}
+
+/// Plays on a remote server when a [`ServerAddress`] is inserted: requests of the [`LocalPlayer`]
+/// are sent to the server and the [`TableView`] only changes with what the server sends back
+pub struct ClientPlugin;
+
+impl Plugin for ClientPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PlayCardRequest>()
+            .add_event::<DrawCardRequest>()
+            .add_event::<PassRequest>()
+            .add_event::<NextRoundRequest>()
+            .add_event::<CallUnoRequest>()
+            .add_event::<CatchUnoRequest>()
+            .add_event::<ChallengeRequest>()
+            .add_event::<SwapHandsRequest>()
+            .add_event::<TableEvent>()
+            .init_state::<AppState>()
+            .init_resource::<CurrentRoom>()
+            .init_resource::<LobbyNotice>()
+            .add_systems(Startup, connect.run_if(resource_exists::<ServerAddress>))
+            .add_systems(
+                Update,
+                (
+                    send_requests.in_set(ApplyRequestsSet),
+                    receive_messages.after(ApplyRequestsSet),
+                )
+                    .run_if(resource_exists::<ServerConnection>),
+            );
+    }
+}
+
+fn connect(mut commands: Commands, address: Res<ServerAddress>) {
+    info!("Connecting to {}", address.0);
+    commands.insert_resource(ServerConnection::open(&address.0));
+}
+
+/// The server knows which seat the connection plays, requests for other seats are dropped
+#[allow(clippy::too_many_arguments)]
+fn send_requests(
+    connection: Res<ServerConnection>,
+    local_query: Query<&Player, With<LocalPlayer>>,
+    mut call_uno_reader: EventReader<CallUnoRequest>,
+    mut catch_uno_reader: EventReader<CatchUnoRequest>,
+    mut play_reader: EventReader<PlayCardRequest>,
+    mut draw_reader: EventReader<DrawCardRequest>,
+    mut pass_reader: EventReader<PassRequest>,
+    mut challenge_reader: EventReader<ChallengeRequest>,
+    mut swap_reader: EventReader<SwapHandsRequest>,
+    mut next_round_reader: EventReader<NextRoundRequest>,
+) {
+    let Ok(local) = local_query.get_single() else {
+        call_uno_reader.clear();
+        catch_uno_reader.clear();
+        play_reader.clear();
+        draw_reader.clear();
+        pass_reader.clear();
+        challenge_reader.clear();
+        swap_reader.clear();
+        next_round_reader.clear();
+        return;
+    };
+
+    let calls = call_uno_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|_| Action::CallUno);
+    let catches = catch_uno_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|request| Action::CatchUno {
+            target: request.target,
+        });
+    let plays = play_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|request| Action::Play {
+            card: request.card,
+            color: request.color,
+        });
+    let draws = draw_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|_| Action::Draw);
+    let passes = pass_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|_| Action::Pass);
+    let challenges = challenge_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|_| Action::ChallengePlusFour);
+    let swaps = swap_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|request| Action::SwapHands {
+            target: request.target,
+        });
+    let next_rounds = next_round_reader
+        .read()
+        .filter(|request| request.seat == local.seat)
+        .map(|_| Action::NextRound);
+
+    for action in calls
+        .chain(catches)
+        .chain(plays)
+        .chain(draws)
+        .chain(passes)
+        .chain(challenges)
+        .chain(swaps)
+        .chain(next_rounds)
+    {
+        connection.send(ClientMessage::Act(action));
+    }
+}
+
+fn receive_messages(
+    mut commands: Commands,
+    connection: Res<ServerConnection>,
+    players_query: Query<Entity, With<Player>>,
+    mut current_room: ResMut<CurrentRoom>,
+    mut notice: ResMut<LobbyNotice>,
+    mut next_state: ResMut<NextState<AppState>>,
+    mut table_writer: EventWriter<TableEvent>,
+) {
+    while let Some(message) = connection.try_receive() {
+        match message {
+            ServerMessage::Room(info) => {
+                current_room.0 = Some(info);
+                notice.0 = None;
+            }
+            ServerMessage::Left => {
+                current_room.0 = None;
+                next_state.set(AppState::Lobby);
+            }
+            // Kept by the connection itself
+            ServerMessage::Session(_) => {}
+            ServerMessage::Welcome { seat, view } => {
+                welcome(&mut commands, &players_query, seat, view);
+                next_state.set(AppState::Playing);
+                // Players are spawned at the end of the system
+                return;
+            }
+            ServerMessage::Updated { events, view } => {
+                table_writer.send_batch(events.into_iter().map(TableEvent));
+                commands.insert_resource(TableView(view));
+            }
+            ServerMessage::Rejected(reason) => {
+                warn!("The server refused: {}", reason);
+                notice.0 = Some(reason);
+            }
+        }
+    }
+}
+
+/// Replace the players with the ones of the server
+fn welcome(
+    commands: &mut Commands,
+    players_query: &Query<Entity, With<Player>>,
+    local_seat: Option<Seat>,
+    view: PlayerView,
+) {
+    match local_seat {
+        Some(seat) => info!("Playing seat {}", seat),
+        None => info!("Watching the game"),
+    }
+
+    players_query
+        .iter()
+        .for_each(|entity| commands.entity(entity).despawn());
+    for seat in 0..view.players() {
+        let mut player = commands.spawn(Player { seat });
+
+        if Some(seat) == local_seat {
+            player.insert(LocalPlayer);
+        }
+    }
+    commands.insert_resource(TableView(view));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod from_args {
+        use super::*;
+
+        fn args(args: &str) -> Vec<String> {
+            args.split_whitespace().map(str::to_string).collect()
+        }
+
+        #[test]
+        fn reads_server() {
+            assert_eq!(
+                ServerAddress::from_args(args("card_game --seed 3 --server ws://localhost:7878")),
+                Some(ServerAddress("ws://localhost:7878".to_string()))
+            );
+        }
+
+        #[test]
+        fn local_game_without_server() {
+            assert_eq!(ServerAddress::from_args(args("card_game --seed 3")), None);
+            assert_eq!(ServerAddress::from_args(args("card_game --server")), None);
+        }
+    }
+}
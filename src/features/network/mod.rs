@@ -0,0 +1,134 @@
+pub mod client;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod server;
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    ai::Difficulty,
+    lobby::{RoomInfo, RoomSettings},
+    rules::{projection::PlayerView, Action, GameEvent, Seat},
+};
+
+pub const DEFAULT_PORT: u16 = 7878;
+
+/// How long sockets wait before checking again for new messages
+pub const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Intent of a client, always applied to the room and seat the connection is in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Sent first by a new connection to take back the seat of a lost one
+    Resume(String),
+    CreateRoom,
+    JoinRoom(String),
+    /// Watch a room without a seat, `god_view` shows every hand with a delay
+    Spectate {
+        code: String,
+        god_view: bool,
+    },
+    LeaveRoom,
+    TakeSeat(Seat),
+    /// Host only, `None` empties the seat
+    SetBot {
+        seat: Seat,
+        difficulty: Option<Difficulty>,
+    },
+    /// Host only
+    ChangeSettings(RoomSettings),
+    Ready(bool),
+    /// Host only
+    Start,
+    Act(Action),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ServerMessage {
+    /// Token to resume the session from a new connection
+    Session(String),
+    /// Sent to every member whenever the room changes before the game starts
+    Room(RoomInfo),
+    /// The client is not in any room anymore
+    Left,
+    /// Full snapshot sent when the game starts or when resuming,
+    /// `seat` is `None` for members without a seat
+    Welcome {
+        seat: Option<Seat>,
+        view: PlayerView,
+    },
+    /// An action was applied, each client only gets the view of its own seat
+    /// so hidden cards never leave the server
+    Updated {
+        events: Vec<GameEvent>,
+        view: PlayerView,
+    },
+    /// The last intent of this client was refused
+    Rejected(String),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+mod socket {
+    use std::{
+        io::{self, Read, Write},
+        net::TcpStream,
+    };
+
+    use serde::{de::DeserializeOwned, Serialize};
+    use tungstenite::{Error, Message, WebSocket};
+
+    pub enum Received<T> {
+        Message(T),
+        /// Nothing to read for now
+        Nothing,
+        Closed,
+    }
+
+    fn would_block(error: &Error) -> bool {
+        matches!(error, Error::Io(error) if error.kind() == io::ErrorKind::WouldBlock)
+    }
+
+    /// Put a socket in non blocking mode once the handshake is done
+    pub fn set_nonblocking(stream: &TcpStream) {
+        stream
+            .set_read_timeout(None)
+            .and_then(|_| stream.set_nonblocking(true))
+            .expect("connected sockets can be made non blocking");
+    }
+
+    /// Queue a JSON message, returns `false` when the socket is closed
+    pub fn send<S: Read + Write, T: Serialize>(socket: &mut WebSocket<S>, message: &T) -> bool {
+        let text = serde_json::to_string(message).expect("messages are always serializable");
+
+        match socket.send(Message::Text(text)) {
+            Ok(()) => true,
+            // The message is buffered and gets sent by a later flush
+            Err(error) => would_block(&error),
+        }
+    }
+
+    /// Send what is still buffered, returns `false` when the socket is closed
+    pub fn flush<S: Read + Write>(socket: &mut WebSocket<S>) -> bool {
+        match socket.flush() {
+            Ok(()) => true,
+            Err(error) => would_block(&error),
+        }
+    }
+
+    /// Read the next JSON message, messages that can't be parsed are skipped
+    pub fn receive<S: Read + Write, T: DeserializeOwned>(socket: &mut WebSocket<S>) -> Received<T> {
+        match socket.read() {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(message) => Received::Message(message),
+                Err(error) => {
+                    bevy::log::warn!("Skipped invalid message {:?}: {}", text, error);
+                    Received::Nothing
+                }
+            },
+            Ok(_) => Received::Nothing,
+            Err(error) if would_block(&error) => Received::Nothing,
+            Err(_) => Received::Closed,
+        }
+    }
+}
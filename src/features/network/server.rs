@@ -0,0 +1,799 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    io,
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tungstenite::{
+    handshake::{
+        server::{NoCallback, ServerHandshake},
+        HandshakeError, MidHandshake,
+    },
+    WebSocket,
+};
+
+use super::{
+    socket::{self, Received},
+    ClientMessage, ServerMessage,
+};
+use crate::features::{
+    ai::{self, Difficulty, SearchBudget},
+    lobby::{
+        room::{ClientId, Room, REPLACEMENT_BOT},
+        LobbyError, ROOM_CODE_LENGTH,
+    },
+    rules::{projection::PlayerView, turn_timer::Timeouts, Action, GameEvent, Seat},
+};
+
+/// Clients taking longer than this to finish the WebSocket handshake are dropped
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Letters of room codes, without the ones easily mistaken for digits
+const ROOM_CODE_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+/// A connection still in the WebSocket handshake, dropped once `until` passes
+struct Handshake {
+    handshake: MidHandshake<ServerHandshake<TcpStream, NoCallback>>,
+    until: Instant,
+}
+
+struct Client {
+    id: ClientId,
+    socket: WebSocket<TcpStream>,
+    /// Code of the room the client is in
+    room: Option<String>,
+}
+
+/// A disconnected client whose seat is kept until `until`
+struct Away {
+    id: ClientId,
+    room: String,
+    until: Instant,
+}
+
+/// Default delay of the god view, long enough for streams to never help anyone still playing
+const GOD_VIEW_DELAY: Duration = Duration::from_secs(30);
+
+/// God views of a room waiting for the delay to pass before being sent
+#[derive(Default)]
+struct DelayedGodViews {
+    pending: VecDeque<(Instant, Vec<GameEvent>, PlayerView)>,
+    /// Last god view sent, late spectators start from it
+    released: Option<PlayerView>,
+}
+
+/// Deadline of the current turn of a room, see [`crate::features::rules::HouseRules::turn_time`]
+struct TurnClock {
+    deadline: Instant,
+    timeouts: Timeouts,
+}
+
+/// How long the server waits for disconnected players to come back
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GracePeriod {
+    pub duration: Duration,
+    /// Bot playing the turns of away players, the game waits for them when `None`
+    pub stand_in: Option<Difficulty>,
+}
+
+impl Default for GracePeriod {
+    fn default() -> Self {
+        Self {
+            duration: Duration::from_secs(60),
+            stand_in: Some(REPLACEMENT_BOT),
+        }
+    }
+}
+
+/// ## Authoritative game server
+///
+/// Clients create or join rooms by code, the host of a room sets it up and starts the game.
+/// Bot seats are played by the server and every client only ever gets its own view of the game.
+/// Each connection gets a session token, sending it back from a new connection after a disconnect
+/// resumes the seat if it happens within the [`GracePeriod`]. Players running out of time on their
+/// turn get it played by a bot, and lose their seat to one after
+/// [`crate::features::rules::turn_timer::TIMEOUTS_BEFORE_BOT`] turns in a row.
+#[derive(Resource)]
+pub struct Server {
+    listener: TcpListener,
+    handshakes: Vec<Handshake>,
+    clients: Vec<Client>,
+    away: Vec<Away>,
+    /// Client of each session token
+    sessions: HashMap<String, ClientId>,
+    rooms: BTreeMap<String, Room>,
+    next_client: ClientId,
+    god_views: BTreeMap<String, DelayedGodViews>,
+    turn_clocks: BTreeMap<String, TurnClock>,
+    rng: StdRng,
+    budget: SearchBudget,
+    grace_period: GracePeriod,
+    god_view_delay: Duration,
+}
+
+impl Server {
+    pub fn bind(address: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+
+        Ok(Self {
+            listener,
+            handshakes: Vec::new(),
+            clients: Vec::new(),
+            away: Vec::new(),
+            sessions: HashMap::new(),
+            rooms: BTreeMap::new(),
+            god_views: BTreeMap::new(),
+            turn_clocks: BTreeMap::new(),
+            next_client: 0,
+            rng: StdRng::from_entropy(),
+            budget: SearchBudget::default(),
+            grace_period: GracePeriod::default(),
+            god_view_delay: GOD_VIEW_DELAY,
+        })
+    }
+
+    pub fn with_god_view_delay(mut self, delay: Duration) -> Self {
+        self.god_view_delay = delay;
+        self
+    }
+
+    pub fn with_grace_period(mut self, grace_period: GracePeriod) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    pub fn rooms(&self) -> impl Iterator<Item = &Room> {
+        self.rooms.values()
+    }
+
+    /// Accept new clients, handle their messages and let bots play, never blocks for long
+    pub fn poll(&mut self) {
+        self.accept_clients();
+
+        let mut messages = Vec::new();
+        let mut disconnected = Vec::new();
+        self.clients.retain_mut(|client| loop {
+            match socket::receive::<_, ClientMessage>(&mut client.socket) {
+                Received::Message(message) => messages.push((client.id, message)),
+                Received::Nothing => break socket::flush(&mut client.socket),
+                Received::Closed => {
+                    info!("Client {} disconnected", client.id);
+                    disconnected.push((client.id, client.room.take()));
+                    break false;
+                }
+            }
+        });
+
+        for (client, room) in disconnected {
+            self.disconnect(client, room);
+        }
+        self.expire_sessions();
+        self.release_god_views();
+        self.expire_turns();
+        for (client, message) in messages {
+            if let Err(error) = self.handle(client, message) {
+                self.send(client, &ServerMessage::Rejected(error));
+            }
+        }
+    }
+
+    /// Start the handshake of new connections and go on with the pending ones,
+    /// a client sending its handshake slowly never holds the other ones up
+    fn accept_clients(&mut self) {
+        let now = Instant::now();
+        let mut attempts = Vec::new();
+
+        loop {
+            match self.listener.accept() {
+                Ok((stream, _)) => {
+                    socket::set_nonblocking(&stream);
+                    attempts.push((now + HANDSHAKE_TIMEOUT, tungstenite::accept(stream)));
+                }
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                Err(error) => {
+                    warn!("Failed to accept a client: {}", error);
+                    break;
+                }
+            }
+        }
+        for pending in std::mem::take(&mut self.handshakes) {
+            if pending.until <= now {
+                warn!("Dropped a client too slow to finish the WebSocket handshake");
+                continue;
+            }
+            attempts.push((pending.until, pending.handshake.handshake()));
+        }
+
+        for (until, attempt) in attempts {
+            match attempt {
+                Ok(socket) => self.connect(socket),
+                Err(HandshakeError::Interrupted(handshake)) => {
+                    self.handshakes.push(Handshake { handshake, until })
+                }
+                Err(HandshakeError::Failure(error)) => {
+                    warn!("Failed WebSocket handshake: {}", error)
+                }
+            }
+        }
+    }
+
+    /// Give a session to a client that finished its handshake
+    fn connect(&mut self, socket: WebSocket<TcpStream>) {
+        let id = self.next_client;
+        self.next_client += 1;
+        info!("Client {} connected", id);
+        let token = format!("{:032x}", self.rng.gen::<u128>());
+        self.sessions.insert(token.clone(), id);
+        self.clients.push(Client {
+            id,
+            socket,
+            room: None,
+        });
+        self.send(id, &ServerMessage::Session(token));
+    }
+
+    /// Keep the seat of a client that lost its connection for the grace period
+    fn disconnect(&mut self, client: ClientId, room: Option<String>) {
+        let Some(room) = room.filter(|code| self.rooms.contains_key(code)) else {
+            self.sessions.retain(|_, id| *id != client);
+            return;
+        };
+
+        if let Some(joined) = self.rooms.get_mut(&room) {
+            joined.disconnect(client);
+        }
+        self.away.push(Away {
+            id: client,
+            room: room.clone(),
+            until: Instant::now() + self.grace_period.duration,
+        });
+
+        if self.rooms[&room].game.is_some() {
+            self.play_bots(&room);
+        } else {
+            self.send_room_info(&room);
+        }
+    }
+
+    /// Away clients not back in time leave their room for good
+    fn expire_sessions(&mut self) {
+        let now = Instant::now();
+        let (expired, away) = std::mem::take(&mut self.away)
+            .into_iter()
+            .partition::<Vec<_>, _>(|away| away.until <= now);
+        self.away = away;
+
+        for away in expired {
+            info!("Session of client {} expired", away.id);
+            self.sessions.retain(|_, id| *id != away.id);
+            self.leave_room(away.id, &away.room);
+        }
+    }
+
+    /// Give the seat and session of the away client of `token` to `client`,
+    /// then send it everything it missed
+    fn resume(&mut self, client: ClientId, token: String) -> Result<(), String> {
+        // The server may not have noticed yet that the previous connection is lost
+        let previous = self.sessions.get(&token).and_then(|id| {
+            let index = self.clients.iter().position(|other| other.id == *id)?;
+            Some(self.clients.remove(index))
+        });
+        if let Some(mut previous) = previous {
+            self.disconnect(previous.id, previous.room.take());
+        }
+
+        let Some(index) = self
+            .sessions
+            .get(&token)
+            .and_then(|id| self.away.iter().position(|away| away.id == *id))
+        else {
+            self.send(client, &ServerMessage::Left);
+            return Err(LobbyError::SessionExpired.to_string());
+        };
+        let away = self.away.remove(index);
+        info!("Client {} resumed as client {}", away.id, client);
+
+        self.sessions.retain(|_, id| *id != client);
+        self.sessions.insert(token.clone(), client);
+        self.send(client, &ServerMessage::Session(token));
+
+        let Some(room) = self.rooms.get_mut(&away.room) else {
+            return Ok(());
+        };
+        room.reconnect(away.id, client);
+        if let Some(client) = self.client_mut(client) {
+            client.room = Some(away.room.clone());
+        }
+
+        let room = &self.rooms[&away.room];
+        match (&room.game, room.seat_of(client)) {
+            (Some(state), Some(seat)) => {
+                let view = state.view_for(Some(seat));
+                let seat = Some(seat);
+                self.send(client, &ServerMessage::Welcome { seat, view });
+            }
+            (Some(_), None) => self.welcome_spectator(client, &away.room),
+            (None, _) => self.send_room_info(&away.room),
+        }
+        Ok(())
+    }
+
+    fn client_mut(&mut self, id: ClientId) -> Option<&mut Client> {
+        self.clients.iter_mut().find(|client| client.id == id)
+    }
+
+    fn send(&mut self, id: ClientId, message: &ServerMessage) {
+        if let Some(client) = self.client_mut(id) {
+            socket::send(&mut client.socket, message);
+        }
+    }
+
+    /// Code of the room the client is in
+    fn room_of(&self, id: ClientId) -> Result<String, String> {
+        self.clients
+            .iter()
+            .find(|client| client.id == id)
+            .and_then(|client| client.room.clone())
+            .ok_or_else(|| LobbyError::NotInRoom.to_string())
+    }
+
+    fn room_mut(&mut self, code: &str) -> Result<&mut Room, String> {
+        self.rooms
+            .get_mut(code)
+            .ok_or_else(|| LobbyError::NoSuchRoom(code.to_string()).to_string())
+    }
+
+    /// Apply one message of `client`, errors are sent back to it
+    fn handle(&mut self, client: ClientId, message: ClientMessage) -> Result<(), String> {
+        if let ClientMessage::CreateRoom
+        | ClientMessage::JoinRoom(_)
+        | ClientMessage::Spectate { .. } = message
+        {
+            if let Ok(code) = self.room_of(client) {
+                self.leave_room(client, &code);
+            }
+        }
+
+        let code = match message {
+            ClientMessage::Resume(token) => return self.resume(client, token),
+            ClientMessage::CreateRoom => {
+                let code = self.new_room_code();
+                info!("Client {} created room {}", client, code);
+                self.rooms
+                    .insert(code.clone(), Room::new(code.clone(), client));
+                code
+            }
+            ClientMessage::JoinRoom(code) => {
+                let code = code.trim().to_uppercase();
+                self.rooms
+                    .get_mut(&code)
+                    .ok_or_else(|| LobbyError::NoSuchRoom(code.clone()))
+                    .and_then(|room| room.join(client))
+                    .map_err(|error| error.to_string())?;
+                code
+            }
+            ClientMessage::Spectate { code, god_view } => {
+                let code = code.trim().to_uppercase();
+                self.room_mut(&code)?.spectate(client, god_view);
+                if let Some(joined) = self.client_mut(client) {
+                    joined.room = Some(code.clone());
+                }
+                if self.rooms[&code].game.is_some() {
+                    self.welcome_spectator(client, &code);
+                    return Ok(());
+                }
+                code
+            }
+            ClientMessage::LeaveRoom => {
+                let code = self.room_of(client)?;
+                self.leave_room(client, &code);
+                self.send(client, &ServerMessage::Left);
+                return Ok(());
+            }
+            ClientMessage::Start => {
+                let code = self.room_of(client)?;
+                let seed = self.rng.gen();
+                self.room_mut(&code)?
+                    .start(client, seed)
+                    .map_err(|error| error.to_string())?;
+                self.welcome(&code);
+                self.restart_turn_clock(&code);
+                self.play_bots(&code);
+                return Ok(());
+            }
+            ClientMessage::Act(action) => {
+                let code = self.room_of(client)?;
+                let seat = self
+                    .room_mut(&code)?
+                    .seat_of(client)
+                    .ok_or_else(|| LobbyError::NotSeated.to_string())?;
+                let current = self.current_seat(&code);
+                self.act(&code, seat, action)?;
+                // Like in local games, only ending its own turn in time starts the count over
+                let ended_turn = current == Some(seat) && self.current_seat(&code) != Some(seat);
+                if let Some(clock) = self.turn_clocks.get_mut(&code).filter(|_| ended_turn) {
+                    clock.timeouts.acted(seat);
+                }
+                self.play_bots(&code);
+                return Ok(());
+            }
+            ClientMessage::TakeSeat(seat) => {
+                self.update_room(client, |room| room.take_seat(client, seat))?
+            }
+            ClientMessage::SetBot { seat, difficulty } => {
+                self.update_room(client, |room| room.set_bot(client, seat, difficulty))?
+            }
+            ClientMessage::ChangeSettings(settings) => {
+                self.update_room(client, |room| room.change_settings(client, settings))?
+            }
+            ClientMessage::Ready(ready) => {
+                self.update_room(client, |room| room.set_ready(client, ready))?
+            }
+        };
+
+        if let Some(client) = self.client_mut(client) {
+            client.room = Some(code.clone());
+        }
+        self.send_room_info(&code);
+        Ok(())
+    }
+
+    /// Change the room of `client` with `update`, returns the room code
+    fn update_room(
+        &mut self,
+        client: ClientId,
+        update: impl FnOnce(&mut Room) -> Result<(), LobbyError>,
+    ) -> Result<String, String> {
+        let code = self.room_of(client)?;
+        update(self.room_mut(&code)?).map_err(|error| error.to_string())?;
+        Ok(code)
+    }
+
+    fn new_room_code(&mut self) -> String {
+        loop {
+            let code = (0..ROOM_CODE_LENGTH)
+                .map(|_| ROOM_CODE_LETTERS[self.rng.gen_range(0..ROOM_CODE_LETTERS.len())] as char)
+                .collect::<String>();
+
+            if !self.rooms.contains_key(&code) {
+                return code;
+            }
+        }
+    }
+
+    /// Remove `client` from the room, closing it once empty
+    fn leave_room(&mut self, client: ClientId, code: &str) {
+        if let Some(room) = self.rooms.get_mut(code) {
+            room.leave(client);
+
+            if room.is_empty() {
+                info!("Room {} closed", code);
+                self.rooms.remove(code);
+                self.god_views.remove(code);
+                self.turn_clocks.remove(code);
+            } else if room.game.is_some() {
+                self.play_bots(code);
+            } else {
+                self.send_room_info(code);
+            }
+        }
+        if let Some(client) = self.client_mut(client) {
+            client.room = None;
+        }
+    }
+
+    /// Send every member of the room how it looks in the lobby
+    fn send_room_info(&mut self, code: &str) {
+        let Some(room) = self.rooms.get(code) else {
+            return;
+        };
+
+        for client in self.clients.iter_mut() {
+            if room.members.contains(&client.id) {
+                socket::send(
+                    &mut client.socket,
+                    &ServerMessage::Room(room.info_for(client.id)),
+                );
+            }
+        }
+    }
+
+    /// Send every member its view of the game that just started
+    fn welcome(&mut self, code: &str) {
+        let Some(room) = self.rooms.get(code) else {
+            return;
+        };
+        let Some(state) = &room.game else {
+            return;
+        };
+
+        for client in self.clients.iter_mut() {
+            if room.members.contains(&client.id) {
+                let seat = room.seat_of(client.id);
+                socket::send(
+                    &mut client.socket,
+                    &ServerMessage::Welcome {
+                        seat,
+                        view: state.view_for(seat),
+                    },
+                );
+            }
+        }
+        self.delay_god_view(code, Vec::new());
+    }
+
+    /// Send a spectator joining a running game what it may see right now
+    fn welcome_spectator(&mut self, client: ClientId, code: &str) {
+        let room = &self.rooms[code];
+        let Some(state) = &room.game else {
+            return;
+        };
+
+        let released = self
+            .god_views
+            .get(code)
+            .and_then(|god_views| god_views.released.clone())
+            .filter(|_| room.is_god_viewer(client));
+        let view = released.unwrap_or_else(|| state.view_for(None));
+        self.send(client, &ServerMessage::Welcome { seat: None, view });
+    }
+
+    /// Keep the god view after `events` until the delay passed
+    fn delay_god_view(&mut self, code: &str, events: Vec<GameEvent>) {
+        let Some(state) = self.rooms.get(code).and_then(|room| room.game.as_ref()) else {
+            return;
+        };
+
+        self.god_views
+            .entry(code.to_string())
+            .or_default()
+            .pending
+            .push_back((Instant::now(), events, state.god_view()));
+    }
+
+    /// Send god viewers every god view older than the delay
+    fn release_god_views(&mut self) {
+        let now = Instant::now();
+
+        for (code, god_views) in self.god_views.iter_mut() {
+            let Some(room) = self.rooms.get(code) else {
+                continue;
+            };
+
+            while god_views
+                .pending
+                .front()
+                .is_some_and(|(at, _, _)| *at + self.god_view_delay <= now)
+            {
+                let Some((_, events, view)) = god_views.pending.pop_front() else {
+                    break;
+                };
+                let message = ServerMessage::Updated {
+                    events,
+                    view: view.clone(),
+                };
+                for client in self.clients.iter_mut() {
+                    if room.is_god_viewer(client.id) {
+                        socket::send(&mut client.socket, &message);
+                    }
+                }
+                god_views.released = Some(view);
+            }
+        }
+    }
+
+    fn act(&mut self, code: &str, seat: Seat, action: Action) -> Result<(), String> {
+        let events = self
+            .room_mut(code)?
+            .game
+            .as_mut()
+            .ok_or_else(|| LobbyError::NotStarted.to_string())?
+            .apply(seat, action)
+            .map_err(|error| error.to_string())?;
+
+        self.broadcast(code, &events);
+        self.delay_god_view(code, events);
+        self.restart_turn_clock(code);
+        Ok(())
+    }
+
+    /// Seat playing in the game of the room, `None` before it starts
+    fn current_seat(&self, code: &str) -> Option<Seat> {
+        self.rooms
+            .get(code)
+            .and_then(|room| room.game.as_ref())
+            .map(|state| state.current)
+    }
+
+    /// Give the player of the room the whole turn time again, when turns are timed
+    fn restart_turn_clock(&mut self, code: &str) {
+        let Some(duration) = self
+            .rooms
+            .get(code)
+            .and_then(|room| room.game.as_ref())
+            .and_then(|state| state.rules.turn_duration())
+        else {
+            return;
+        };
+
+        let deadline = Instant::now() + duration;
+        self.turn_clocks
+            .entry(code.to_string())
+            .and_modify(|clock| clock.deadline = deadline)
+            .or_insert(TurnClock {
+                deadline,
+                timeouts: Timeouts::default(),
+            });
+    }
+
+    /// A bot plays the turn of every human past its deadline, the seat is given to it
+    /// once the player ran out of time too many turns in a row
+    fn expire_turns(&mut self) {
+        let now = Instant::now();
+        let expired = self
+            .turn_clocks
+            .iter()
+            .filter(|(_, clock)| clock.deadline <= now)
+            .map(|(code, _)| code.clone())
+            .collect::<Vec<_>>();
+
+        for code in expired {
+            let Some(room) = self.rooms.get(&code) else {
+                continue;
+            };
+            let Some(state) = room.game.as_ref().filter(|state| !state.is_over()) else {
+                continue;
+            };
+            let seat = state.current;
+            if room.bot_at(seat, self.grace_period.stand_in).is_some() {
+                continue;
+            }
+
+            info!("Seat {} of room {} ran out of time", seat, code);
+            for action in ai::timeout_actions(state, seat, &mut self.rng) {
+                if let Err(error) = self.act(&code, seat, action) {
+                    error!("Bot of seat {} failed to play in time: {}", seat, error);
+                    break;
+                }
+            }
+            self.restart_turn_clock(&code);
+
+            let replaced = self
+                .turn_clocks
+                .get_mut(&code)
+                .is_some_and(|clock| clock.timeouts.timed_out(seat));
+            if let (true, Some(room)) = (replaced, self.rooms.get_mut(&code)) {
+                info!("Seat {} of room {} is given to a bot", seat, code);
+                room.give_to_bot(seat);
+            }
+            self.play_bots(&code);
+        }
+    }
+
+    fn play_bots(&mut self, code: &str) {
+        loop {
+            let Some(room) = self.rooms.get(code) else {
+                return;
+            };
+            let Some(state) = room.game.as_ref().filter(|state| !state.is_over()) else {
+                return;
+            };
+            let stand_in = self.grace_period.stand_in;
+            if let Some((seat, action)) =
+                ai::headless_jump_in(state, |seat| room.bot_at(seat, stand_in))
+            {
+                if let Err(error) = self.act(code, seat, action) {
+                    error!("Bot of seat {} failed to jump in: {}", seat, error);
+                    return;
+                }
+                continue;
+            }
+
+            let seat = state.current;
+            let Some(difficulty) = room.bot_at(seat, stand_in) else {
+                return;
+            };
+
+            let uno = ai::uno_action(difficulty, state, seat, &mut self.rng);
+            if let Some(action) = uno {
+                if let Err(error) = self.act(code, seat, action) {
+                    error!("Bot of seat {} failed to call uno: {}", seat, error);
+                    return;
+                }
+            }
+
+            let Some(state) = self.rooms.get(code).and_then(|room| room.game.as_ref()) else {
+                return;
+            };
+            let action = ai::choose_action(difficulty, state, seat, &self.budget, &mut self.rng);
+            if let Err(error) = self.act(code, seat, action) {
+                error!("Bot of seat {} failed to play: {}", seat, error);
+                return;
+            }
+        }
+    }
+
+    /// Send every member its own view of the game after `events`, god viewers wait for theirs
+    fn broadcast(&mut self, code: &str, events: &[GameEvent]) {
+        let Some(room) = self.rooms.get(code) else {
+            return;
+        };
+        let Some(state) = &room.game else {
+            return;
+        };
+
+        for client in self.clients.iter_mut() {
+            if room.members.contains(&client.id) && !room.is_god_viewer(client.id) {
+                let message = ServerMessage::Updated {
+                    events: events.to_vec(),
+                    view: state.view_for(room.seat_of(client.id)),
+                };
+                socket::send(&mut client.socket, &message);
+            }
+        }
+    }
+}
+
+/// Runs the [`Server`] resource inserted in the app
+pub struct ServerPlugin;
+
+impl Plugin for ServerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, poll_server.run_if(resource_exists::<Server>));
+    }
+}
+
+fn poll_server(mut server: ResMut<Server>) {
+    server.poll();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod new_room_code {
+        use super::*;
+
+        #[test]
+        fn unique_codes() {
+            let mut server = Server::bind("127.0.0.1:0").unwrap();
+
+            for client in 0..50 {
+                let code = server.new_room_code();
+                assert_eq!(code.len(), ROOM_CODE_LENGTH);
+                assert!(code
+                    .bytes()
+                    .all(|letter| ROOM_CODE_LETTERS.contains(&letter)));
+                server.rooms.insert(code.clone(), Room::new(code, client));
+            }
+
+            assert_eq!(server.rooms().count(), 50);
+        }
+    }
+
+    mod handle {
+        use super::*;
+
+        #[test]
+        fn needs_a_room() {
+            let mut server = Server::bind("127.0.0.1:0").unwrap();
+
+            assert_eq!(
+                server.handle(0, ClientMessage::Ready(true)),
+                Err(LobbyError::NotInRoom.to_string())
+            );
+            assert_eq!(
+                server.handle(0, ClientMessage::JoinRoom("nope".to_string())),
+                Err(LobbyError::NoSuchRoom("NOPE".to_string()).to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,213 @@
+pub mod viewer;
+
+use std::fmt;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    game::{SeatKind, Table},
+    rules::{Action, GameEvent, GameState, RuleError, Seat},
+    save::{self, storage, SaveError, SaveFormat},
+};
+
+/// Name of the replay of the last local game in [`storage`]
+pub const LAST_REPLAY: &str = "replay";
+
+/// One applied action with what it did, so replays double as fixtures for the rules
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayStep {
+    pub seat: Seat,
+    pub action: Action,
+    pub events: Vec<GameEvent>,
+}
+
+/// ## A whole game, action by action
+///
+/// `start` holds the deal and the seed of the game, every later state is rebuilt
+/// by applying the steps to it again.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Replay {
+    pub seats: Vec<SeatKind>,
+    pub start: GameState,
+    pub steps: Vec<ReplayStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    /// The rules refused the action of the step
+    Rejected { step: usize, error: RuleError },
+    /// The action did not lead to the recorded events
+    Diverged { step: usize },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Rejected { step, error } => {
+                write!(f, "step {} was rejected: {}", step + 1, error)
+            }
+            ReplayError::Diverged { step } => {
+                write!(f, "step {} did not give the recorded events", step + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    pub fn new(seats: Vec<SeatKind>, start: GameState) -> Self {
+        Self {
+            seats,
+            start,
+            steps: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, seat: Seat, action: Action, events: &[GameEvent]) {
+        self.steps.push(ReplayStep {
+            seat,
+            action,
+            events: events.to_vec(),
+        });
+    }
+
+    /// Every state of the game, from the deal to the last step, checked against the recorded events
+    pub fn states(&self) -> Result<Vec<GameState>, ReplayError> {
+        let mut state = self.start.clone();
+        let mut states = vec![state.clone()];
+
+        for (
+            step,
+            ReplayStep {
+                seat,
+                action,
+                events,
+            },
+        ) in self.steps.iter().enumerate()
+        {
+            let applied = state
+                .apply(*seat, *action)
+                .map_err(|error| ReplayError::Rejected { step, error })?;
+            if applied != *events {
+                return Err(ReplayError::Diverged { step });
+            }
+            states.push(state.clone());
+        }
+
+        Ok(states)
+    }
+
+    /// Winner of the last round played
+    pub fn winner(&self) -> Option<Seat> {
+        self.steps
+            .iter()
+            .rev()
+            .flat_map(|step| step.events.iter())
+            .find_map(|event| match event {
+                GameEvent::Won { seat } => Some(*seat),
+                _ => None,
+            })
+    }
+
+    pub fn encode(&self, format: SaveFormat) -> Result<String, SaveError> {
+        save::encode(self, format)
+    }
+
+    pub fn decode(text: &str, format: SaveFormat) -> Result<Self, SaveError> {
+        let mut replay: Self = save::decode(text, format)?;
+        replay.start.fill_scores();
+
+        Ok(replay)
+    }
+}
+
+/// Replay of the local game being played, stored as [`LAST_REPLAY`] once the match is over
+#[derive(Resource, Debug, Clone)]
+pub struct Recording(pub Replay);
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(viewer::ReplayViewerPlugin).add_systems(
+            Update,
+            store_recording.run_if(
+                resource_exists::<Recording>.and_then(resource_exists_and_changed::<Table>),
+            ),
+        );
+    }
+}
+
+fn store_recording(mut commands: Commands, table: Res<Table>, recording: Res<Recording>) {
+    if !table.0.is_match_over() {
+        return;
+    }
+
+    match recording
+        .0
+        .encode(storage::STORAGE_FORMAT)
+        .and_then(|text| storage::write(LAST_REPLAY, &text))
+    {
+        Ok(()) => info!("Stored the replay of the game"),
+        Err(error) => warn!("Could not store the replay: {}", error),
+    }
+    commands.remove_resource::<Recording>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::ai::Difficulty;
+
+    fn replay() -> Replay {
+        let mut state = GameState::from_seed(2, 5);
+        let mut replay = Replay::new(
+            vec![SeatKind::Human, SeatKind::Bot(Difficulty::Easy)],
+            state.clone(),
+        );
+
+        for _ in 0..6 {
+            let seat = state.current;
+            let action = state.legal_actions(seat)[0];
+            let events = state.apply(seat, action).unwrap();
+            replay.record(seat, action, &events);
+        }
+
+        replay
+    }
+
+    mod states {
+        use super::*;
+
+        #[test]
+        fn one_state_per_step() {
+            let replay = replay();
+
+            let states = replay.states().unwrap();
+
+            assert_eq!(states.len(), 7);
+            assert_eq!(states[0], replay.start);
+        }
+
+        #[test]
+        fn changed_events_diverge() {
+            let mut replay = replay();
+            replay.steps[3].events.clear();
+
+            assert_eq!(replay.states(), Err(ReplayError::Diverged { step: 3 }));
+        }
+
+        #[test]
+        fn illegal_steps_rejected() {
+            let mut replay = replay();
+            replay.steps[2].seat = (replay.steps[2].seat + 1) % 2;
+
+            assert!(matches!(
+                replay.states(),
+                Err(ReplayError::Rejected { step: 2, .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,341 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use super::{Replay, ReplayError, LAST_REPLAY};
+use crate::features::{
+    game::{AppState, TableView},
+    rules::GameState,
+    save::{storage, SaveError, SaveFormat},
+};
+
+/// Time between two steps at normal speed
+const STEP_DELAY: Duration = Duration::from_millis(800);
+const MIN_SPEED: f32 = 0.25;
+const MAX_SPEED: f32 = 8.;
+
+const BUTTON_COLOR: Color = Color::DARK_GRAY;
+
+/// ## Watches a recorded game instead of playing one
+///
+/// Every hand is shown face up. The game is rebuilt once when the viewer is created,
+/// stepping back and forth then only moves between the rebuilt states.
+#[derive(Resource, Debug, Clone)]
+pub struct ReplayViewer {
+    states: Vec<GameState>,
+    step: usize,
+    playing: bool,
+    speed: f32,
+    timer: Timer,
+}
+
+impl ReplayViewer {
+    pub fn new(replay: &Replay) -> Result<Self, ReplayError> {
+        Ok(Self {
+            states: replay.states()?,
+            step: 0,
+            playing: false,
+            speed: 1.,
+            timer: Timer::new(STEP_DELAY, TimerMode::Repeating),
+        })
+    }
+
+    /// ## Reads `--replay` from command line arguments
+    ///
+    /// `--replay game.ron` opens a replay file, RON or JSON from its extension, while `--replay last`
+    /// opens the last local game. Replays that can't be read are logged and ignored.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Option<Self> {
+        let mut args = args.into_iter();
+        let source = args
+            .find(|arg| arg == "--replay")
+            .and_then(|_| args.next())?;
+
+        let replay = if source == "last" {
+            storage::read(LAST_REPLAY)
+                .and_then(|text| Replay::decode(&text, storage::STORAGE_FORMAT))
+        } else {
+            read_file(&source)
+        };
+
+        match replay
+            .map_err(|error| error.to_string())
+            .and_then(|replay| Self::new(&replay).map_err(|error| error.to_string()))
+        {
+            Ok(viewer) => Some(viewer),
+            Err(error) => {
+                warn!("Ignored replay {}: {}", source, error);
+                None
+            }
+        }
+    }
+
+    pub fn state(&self) -> &GameState {
+        &self.states[self.step]
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// Number of actions in the replay
+    pub fn steps(&self) -> usize {
+        self.states.len() - 1
+    }
+
+    pub fn step_forward(&mut self) {
+        self.step = (self.step + 1).min(self.steps());
+    }
+
+    pub fn step_back(&mut self) {
+        self.step = self.step.saturating_sub(1);
+    }
+
+    pub fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+    }
+
+    pub fn faster(&mut self) {
+        self.set_speed(self.speed * 2.);
+    }
+
+    pub fn slower(&mut self) {
+        self.set_speed(self.speed / 2.);
+    }
+
+    fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self.timer.set_duration(STEP_DELAY.div_f32(self.speed));
+    }
+
+    /// Moves forward while playing, it pauses by itself on the last step
+    pub fn tick(&mut self, delta: Duration) {
+        if !self.playing {
+            return;
+        }
+
+        let steps = self.timer.tick(delta).times_finished_this_tick();
+        (0..steps).for_each(|_| self.step_forward());
+        if self.step == self.steps() {
+            self.playing = false;
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_file(path: &str) -> Result<Replay, SaveError> {
+    let path = std::path::Path::new(path);
+
+    std::fs::read_to_string(path)
+        .map_err(|error| SaveError::Storage(error.to_string()))
+        .and_then(|text| Replay::decode(&text, SaveFormat::from_path(path)))
+}
+
+/// Only the local storage is reachable from the browser
+#[cfg(target_arch = "wasm32")]
+fn read_file(path: &str) -> Result<Replay, SaveError> {
+    Err(SaveError::Storage(format!(
+        "{} can't be opened from the browser",
+        path
+    )))
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewerButton {
+    Back,
+    PlayPause,
+    Forward,
+    Slower,
+    Faster,
+}
+
+#[derive(Component)]
+struct ViewerStatusText;
+
+/// This plugin shows the game of a [`ReplayViewer`] with its controls, it does nothing without one
+pub struct ReplayViewerPlugin;
+
+impl Plugin for ReplayViewerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Startup,
+            start_viewer.run_if(resource_exists::<ReplayViewer>),
+        )
+        .add_systems(
+            Update,
+            (
+                press_viewer_buttons,
+                play_replay,
+                show_step.run_if(resource_changed::<ReplayViewer>),
+            )
+                .chain()
+                .run_if(resource_exists::<ReplayViewer>),
+        );
+    }
+}
+
+fn start_viewer(mut commands: Commands, mut next_state: ResMut<NextState<AppState>>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.),
+                top: Val::Px(20.),
+                column_gap: Val::Px(10.),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            for (button, label) in [
+                (ViewerButton::Back, "<"),
+                (ViewerButton::PlayPause, "Play / Pause"),
+                (ViewerButton::Forward, ">"),
+                (ViewerButton::Slower, "Slower"),
+                (ViewerButton::Faster, "Faster"),
+            ] {
+                builder
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(5.)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(BUTTON_COLOR),
+                            ..default()
+                        },
+                        button,
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section(label, TextStyle::default()));
+                    });
+            }
+            builder.spawn((
+                TextBundle::from_section("", TextStyle::default()),
+                ViewerStatusText,
+            ));
+        });
+    next_state.set(AppState::Playing);
+}
+
+fn press_viewer_buttons(
+    mut viewer: ResMut<ReplayViewer>,
+    buttons_query: Query<(&Interaction, &ViewerButton), Changed<Interaction>>,
+) {
+    buttons_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .for_each(|(_, button)| match button {
+            ViewerButton::Back => viewer.step_back(),
+            ViewerButton::PlayPause => viewer.toggle_playing(),
+            ViewerButton::Forward => viewer.step_forward(),
+            ViewerButton::Slower => viewer.slower(),
+            ViewerButton::Faster => viewer.faster(),
+        });
+}
+
+/// The view is rebuilt whenever the viewer changes, so it is only marked changed on a new step
+fn play_replay(mut viewer: ResMut<ReplayViewer>, time: Res<Time>) {
+    let before = (viewer.step, viewer.playing);
+
+    viewer.bypass_change_detection().tick(time.delta());
+    if (viewer.step, viewer.playing) != before {
+        viewer.set_changed();
+    }
+}
+
+/// Show the current step with every hand face up
+fn show_step(
+    mut commands: Commands,
+    viewer: Res<ReplayViewer>,
+    mut text_query: Query<&mut Text, With<ViewerStatusText>>,
+) {
+    commands.insert_resource(TableView(viewer.state().god_view()));
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = format!(
+            "Step {}/{}, speed x{}{}",
+            viewer.step(),
+            viewer.steps(),
+            viewer.speed,
+            if viewer.playing { "" } else { ", paused" }
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{
+        ai::Difficulty,
+        game::SeatKind,
+        rules::{Action, GameState},
+    };
+
+    fn viewer(steps: usize) -> ReplayViewer {
+        let mut state = GameState::from_seed(2, 8);
+        let mut replay = Replay::new(
+            vec![SeatKind::Bot(Difficulty::Easy), SeatKind::Human],
+            state.clone(),
+        );
+        for _ in 0..steps {
+            let seat = state.current;
+            let events = state.apply(seat, Action::Draw).unwrap();
+            replay.record(seat, Action::Draw, &events);
+        }
+
+        ReplayViewer::new(&replay).unwrap()
+    }
+
+    mod step_back {
+        use super::*;
+
+        #[test]
+        fn returns_to_previous_state() {
+            let mut viewer = viewer(3);
+            let start = viewer.state().clone();
+
+            viewer.step_forward();
+            viewer.step_back();
+            viewer.step_back();
+
+            assert_eq!(viewer.step(), 0);
+            assert_eq!(viewer.state(), &start);
+        }
+    }
+
+    mod tick {
+        use super::*;
+
+        #[test]
+        fn faster_plays_more_steps() {
+            let mut viewer = viewer(10);
+            viewer.toggle_playing();
+            viewer.faster();
+
+            viewer.tick(STEP_DELAY);
+
+            assert_eq!(viewer.step(), 2);
+        }
+
+        #[test]
+        fn pauses_on_last_step() {
+            let mut viewer = viewer(3);
+            viewer.toggle_playing();
+
+            viewer.tick(STEP_DELAY * 5);
+
+            assert_eq!(viewer.step(), 3);
+            assert!(!viewer.playing);
+        }
+
+        #[test]
+        fn paused_stays_still() {
+            let mut viewer = viewer(3);
+
+            viewer.tick(STEP_DELAY * 5);
+
+            assert_eq!(viewer.step(), 0);
+        }
+    }
+}
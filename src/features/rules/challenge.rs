@@ -0,0 +1,213 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GameEvent, GameState, Seat};
+use crate::CardColor;
+
+/// Cards drawn by a challenger who was wrong, instead of the 4 of the card
+pub const FAILED_CHALLENGE_PENALTY: usize = 6;
+pub const PLUS_FOUR_PENALTY: usize = 4;
+
+/// ## A +4 waiting for its victim to accept it or challenge it
+///
+/// A +4 may only be played without any card of the active color. Nothing stops a bluff,
+/// but the victim may challenge it: drawing accepts the 4 cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlusFourChallenge {
+    pub offender: Seat,
+    pub victim: Seat,
+    /// Active color the +4 was played on
+    pub previous_color: CardColor,
+    /// Cards the offender kept when playing, uno penalties drawn since are left out
+    pub held: usize,
+}
+
+/// Hand of `seat` shown to `to` after a challenge, until `to` plays or draws again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RevealedHand {
+    pub seat: Seat,
+    pub to: Seat,
+}
+
+impl GameState {
+    /// Whether the offender of `challenge` held a card of the color it replaced
+    pub fn is_bluff(&self, challenge: &PlusFourChallenge) -> bool {
+        self.hands[challenge.offender]
+            .iter()
+            .take(challenge.held)
+            .any(|(color, _)| *color == challenge.previous_color)
+    }
+
+    pub(super) fn accept_plus_four(&mut self, challenge: PlusFourChallenge) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        self.plus_four = None;
+        self.draw_cards(challenge.victim, PLUS_FOUR_PENALTY, &mut events);
+        self.skip_current(&mut events);
+
+        events
+    }
+
+    /// A bluffing offender draws the 4 cards and the victim plays, otherwise the victim draws 6 and is skipped
+    pub(super) fn challenge_plus_four(&mut self, challenge: PlusFourChallenge) -> Vec<GameEvent> {
+        let bluffed = self.is_bluff(&challenge);
+        let mut events = vec![GameEvent::Challenged {
+            seat: challenge.offender,
+            by: challenge.victim,
+            bluffed,
+        }];
+
+        self.plus_four = None;
+        self.revealed = Some(RevealedHand {
+            seat: challenge.offender,
+            to: challenge.victim,
+        });
+        if bluffed {
+            self.draw_cards(challenge.offender, PLUS_FOUR_PENALTY, &mut events);
+        } else {
+            self.draw_cards(challenge.victim, FAILED_CHALLENGE_PENALTY, &mut events);
+            self.skip_current(&mut events);
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::CardInfo,
+            rules::{Action, RuleError},
+        },
+        CardVariant,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const PLUS_FOUR: CardInfo = (CardColor::Wild, CardVariant::PlusFour);
+
+    const PLAY_PLUS_FOUR: Action = Action::Play {
+        card: 0,
+        color: Some(CardColor::Green),
+    };
+
+    /// Seat 0 plays a +4 on a red 5 on seat 1, holding `held` besides
+    fn state(held: CardInfo) -> GameState {
+        let mut state = GameState::from_seed(3, 0);
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![
+            vec![PLUS_FOUR, held, BLUE_7],
+            vec![BLUE_7; 3],
+            vec![BLUE_7; 3],
+        ];
+        state.apply(0, PLAY_PLUS_FOUR).unwrap();
+        state
+    }
+
+    mod accept_plus_four {
+        use super::*;
+
+        #[test]
+        fn victim_waits_to_answer() {
+            let mut state = state(BLUE_7);
+
+            assert_eq!(state.current, 1);
+            assert_eq!(
+                state.legal_actions(1),
+                vec![Action::ChallengePlusFour, Action::Draw]
+            );
+            assert_eq!(
+                state.apply(
+                    1,
+                    Action::Play {
+                        card: 0,
+                        color: None
+                    }
+                ),
+                Err(RuleError::PlusFourPending)
+            );
+        }
+
+        #[test]
+        fn drawing_takes_four_and_skips() {
+            let mut state = state(BLUE_7);
+
+            state.apply(1, Action::Draw).unwrap();
+
+            assert_eq!(state.hands[1].len(), 3 + PLUS_FOUR_PENALTY);
+            assert_eq!(state.current, 2);
+            assert_eq!(state.plus_four, None);
+        }
+    }
+
+    mod challenge_plus_four {
+        use super::*;
+
+        #[test]
+        fn caught_bluff_draws_four() {
+            let mut state = state(RED_5);
+
+            let events = state.apply(1, Action::ChallengePlusFour).unwrap();
+
+            assert_eq!(
+                events[0],
+                GameEvent::Challenged {
+                    seat: 0,
+                    by: 1,
+                    bluffed: true
+                }
+            );
+            assert_eq!(state.hands[0].len(), 2 + PLUS_FOUR_PENALTY);
+            assert_eq!(state.hands[1].len(), 3);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn wrong_challenge_draws_six() {
+            let mut state = state(BLUE_7);
+
+            state.apply(1, Action::ChallengePlusFour).unwrap();
+
+            assert_eq!(state.hands[0].len(), 2);
+            assert_eq!(state.hands[1].len(), 3 + FAILED_CHALLENGE_PENALTY);
+            assert_eq!(state.current, 2);
+        }
+
+        #[test]
+        fn hand_shown_until_challenger_acts() {
+            let mut state = state(BLUE_7);
+            state.apply(1, Action::ChallengePlusFour).unwrap();
+
+            let shown = state.view_for(Some(1)).hand(0);
+            state.apply(2, Action::Draw).unwrap();
+            state.apply(0, Action::Draw).unwrap();
+            let still_shown = state.view_for(Some(1)).hand(0);
+            state.apply(1, Action::Draw).unwrap();
+
+            assert_eq!(shown, vec![BLUE_7, BLUE_7]);
+            assert_eq!(still_shown.len(), 3);
+            assert!(state.view_for(Some(1)).hand(0).is_empty());
+            assert!(state.view_for(Some(2)).hand(0).is_empty());
+        }
+
+        #[test]
+        fn nothing_to_challenge() {
+            let mut state = GameState::from_seed(2, 0);
+
+            assert_eq!(
+                state.apply(0, Action::ChallengePlusFour),
+                Err(RuleError::NothingToChallenge)
+            );
+        }
+
+        #[test]
+        fn uno_penalties_do_not_count() {
+            let mut state = state(BLUE_7);
+            state.hands[0].push(RED_5);
+
+            assert!(!state.is_bluff(&state.plus_four.unwrap()));
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameEvent, GameState, RuleError, Seat};
+
+/// ## House rule for a turn spent drawing
+///
+/// Unless drawing one card and passing, a playable card drawn keeps the turn: it can be
+/// played or kept with [`super::Action::Pass`], see [`super::HouseRules::forced_play`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DrawRule {
+    /// Draw a single card, then the turn passes
+    #[default]
+    DrawOne,
+    /// Keep drawing until a playable card comes up or both piles run out
+    UntilPlayable,
+}
+
+impl FromStr for DrawRule {
+    type Err = String;
+
+    fn from_str(draw: &str) -> Result<Self, Self::Err> {
+        match draw.to_lowercase().as_str() {
+            "one" => Ok(DrawRule::DrawOne),
+            "until-playable" => Ok(DrawRule::UntilPlayable),
+            _ => Err(format!("unknown draw rule {:?}", draw)),
+        }
+    }
+}
+
+impl From<DrawRule> for String {
+    fn from(draw: DrawRule) -> Self {
+        match draw {
+            DrawRule::DrawOne => "draw one".to_string(),
+            DrawRule::UntilPlayable => "until playable".to_string(),
+        }
+    }
+}
+
+impl GameState {
+    /// The current player draws for its turn, and keeps it while the card drawn last can be played
+    pub(super) fn draw_turn(&mut self, seat: Seat) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        let mut count = 0;
+        let mut playable = false;
+        while let Some(card) = self.draw_card(seat, &mut events) {
+            count += 1;
+            playable = self.is_playable(card);
+            if playable || self.rules.draw == DrawRule::DrawOne {
+                break;
+            }
+        }
+        events.push(GameEvent::Drew { seat, count });
+
+        if playable && (self.rules.forced_play || self.rules.draw == DrawRule::UntilPlayable) {
+            self.drawn = Some(self.hands[seat].len() - 1);
+        } else {
+            self.current = self.next_seat();
+        }
+
+        events
+    }
+
+    /// The current player keeps the playable card it just drew and ends its turn
+    pub(super) fn pass(&mut self, seat: Seat) -> Result<Vec<GameEvent>, RuleError> {
+        if self.drawn.is_none() {
+            return Err(RuleError::NothingToPass);
+        }
+        if self.rules.forced_play {
+            return Err(RuleError::ForcedPlay);
+        }
+
+        self.drawn = None;
+        self.current = self.next_seat();
+
+        Ok(vec![GameEvent::Passed { seat }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::CardInfo,
+            rules::{Action, HouseRules},
+        },
+        CardColor, CardVariant,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_9: CardInfo = (CardColor::Red, CardVariant::Number(9));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+
+    /// Seat 0 holds a blue 7 on a red 5, the last card of `draw_pile` is drawn first
+    fn state(draw: DrawRule, forced_play: bool, draw_pile: Vec<CardInfo>) -> GameState {
+        let mut state = GameState::with_rules(
+            crate::features::deck::Deck::default().into_cards(),
+            2,
+            0,
+            HouseRules {
+                draw,
+                forced_play,
+                ..HouseRules::default()
+            },
+        );
+        state.draw_pile = draw_pile;
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![vec![BLUE_7], vec![BLUE_7; 2]];
+        state
+    }
+
+    mod draw_turn {
+        use super::*;
+
+        #[test]
+        fn draw_one_passes() {
+            let mut state = state(DrawRule::DrawOne, false, vec![RED_9]);
+
+            state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(state.hands[0], vec![BLUE_7, RED_9]);
+            assert_eq!(state.drawn, None);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn until_playable() {
+            let mut state = state(
+                DrawRule::UntilPlayable,
+                false,
+                vec![BLUE_7, RED_9, BLUE_7, BLUE_7],
+            );
+
+            let events = state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(events, vec![GameEvent::Drew { seat: 0, count: 3 }]);
+            assert_eq!(state.drawn, Some(3));
+            assert_eq!(state.current, 0);
+            assert_eq!(
+                state.legal_actions(0),
+                vec![
+                    Action::Play {
+                        card: 3,
+                        color: None
+                    },
+                    Action::Pass
+                ]
+            );
+        }
+
+        #[test]
+        fn reshuffles_while_drawing() {
+            let mut state = state(DrawRule::UntilPlayable, false, vec![BLUE_7]);
+            state.discard_pile = vec![RED_9, RED_5];
+
+            let events = state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(
+                events,
+                vec![GameEvent::Reshuffled, GameEvent::Drew { seat: 0, count: 2 }]
+            );
+            assert_eq!(state.drawn, Some(2));
+        }
+
+        #[test]
+        fn empty_piles_pass() {
+            let mut state = state(DrawRule::UntilPlayable, true, vec![BLUE_7; 2]);
+
+            let events = state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(events, vec![GameEvent::Drew { seat: 0, count: 2 }]);
+            assert_eq!(state.drawn, None);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn forced_play_of_one_card() {
+            let mut state = state(DrawRule::DrawOne, true, vec![RED_9]);
+            state.hands[0].push(RED_5);
+
+            state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(state.apply(0, Action::Pass), Err(RuleError::ForcedPlay));
+            assert_eq!(
+                state.apply(0, Action::Draw),
+                Err(RuleError::DrawnCardPending)
+            );
+            assert_eq!(
+                state.apply(
+                    0,
+                    Action::Play {
+                        card: 1,
+                        color: None
+                    }
+                ),
+                Err(RuleError::DrawnCardPending)
+            );
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 2,
+                        color: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(state.drawn, None);
+            assert_eq!(state.current, 1);
+        }
+    }
+
+    mod pass {
+        use super::*;
+
+        #[test]
+        fn keeps_the_drawn_card() {
+            let mut state = state(DrawRule::UntilPlayable, false, vec![RED_9]);
+            state.apply(0, Action::Draw).unwrap();
+
+            let events = state.apply(0, Action::Pass).unwrap();
+
+            assert_eq!(events, vec![GameEvent::Passed { seat: 0 }]);
+            assert_eq!(state.hands[0], vec![BLUE_7, RED_9]);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn only_after_drawing() {
+            let mut state = state(DrawRule::UntilPlayable, false, vec![RED_9]);
+
+            assert_eq!(state.apply(0, Action::Pass), Err(RuleError::NothingToPass));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, LazyLock, RwLock},
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameEvent, GameState, Seat};
+use crate::{features::deck::CardInfo, CardColor};
+
+/// Identifies a registered [`CardEffect`], every peer of a game must register the same effects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct EffectId(pub u16);
+
+/// Every other card of its color leaves the hand of the player with it
+pub const DISCARD_ALL: EffectId = EffectId(1);
+/// Every other player draws one card
+pub const EVERYONE_DRAWS: EffectId = EffectId(2);
+
+/// ## Behaviour of a custom action card
+///
+/// Cards of an effect are [`crate::CardVariant::Custom`] with the id it was registered with,
+/// see [`register`]. Only [`CardEffect::key`], [`CardEffect::name`] and [`CardEffect::apply`]
+/// are required, the other methods default to those of a colored action card.
+pub trait CardEffect: Send + Sync {
+    /// Name of the card in texture paths and terminal labels, e.g. "discard_all"
+    fn key(&self) -> &str;
+
+    /// Human readable name of the card without its color, e.g. "discard all"
+    fn name(&self) -> &str;
+
+    /// Wild cards are dealt without a color, one is picked when playing them
+    fn is_wild(&self) -> bool {
+        false
+    }
+
+    /// Points the card gives to the winner of a round when left in a hand
+    fn points(&self) -> u32 {
+        20
+    }
+
+    /// Path of the texture of the card of `color`, next to the cards of that color by default
+    fn texture_path(&self, color: CardColor) -> String {
+        let color_str: String = color.into();
+
+        format!("cards/{}/{}_{}.png", color_str, self.key(), color_str)
+    }
+
+    /// Whether `card` can be played on `state`, on its color or on another card of the effect by default
+    fn is_playable(&self, state: &GameState, (color, variant): CardInfo) -> bool {
+        color == CardColor::Wild || color == state.active_color || variant == state.top_card().1
+    }
+
+    /// Takes effect once `seat` played the card with `color` as the active color, then gives the turn
+    /// to the seat playing next
+    fn apply(
+        &self,
+        state: &mut GameState,
+        seat: Seat,
+        color: CardColor,
+        events: &mut Vec<GameEvent>,
+    );
+}
+
+static REGISTRY: LazyLock<RwLock<BTreeMap<EffectId, Arc<dyn CardEffect>>>> = LazyLock::new(|| {
+    let builtins: [(EffectId, Arc<dyn CardEffect>); 2] = [
+        (DISCARD_ALL, Arc::new(DiscardAll)),
+        (EVERYONE_DRAWS, Arc::new(EveryoneDraws)),
+    ];
+
+    RwLock::new(BTreeMap::from(builtins))
+});
+
+/// Makes `effect` available to cards of `id`, replacing the effect registered with it before
+pub fn register(id: EffectId, effect: impl CardEffect + 'static) {
+    REGISTRY
+        .write()
+        .expect("the effect registry is never poisoned")
+        .insert(id, Arc::new(effect));
+}
+
+/// Effect registered with `id`, cards of an unknown effect can't be played
+pub fn effect(id: EffectId) -> Option<Arc<dyn CardEffect>> {
+    REGISTRY
+        .read()
+        .expect("the effect registry is never poisoned")
+        .get(&id)
+        .cloned()
+}
+
+/// Every other card of the color played goes under it on the discard pile
+struct DiscardAll;
+
+impl CardEffect for DiscardAll {
+    fn key(&self) -> &str {
+        "discard_all"
+    }
+
+    fn name(&self) -> &str {
+        "discard all"
+    }
+
+    fn apply(
+        &self,
+        state: &mut GameState,
+        seat: Seat,
+        color: CardColor,
+        events: &mut Vec<GameEvent>,
+    ) {
+        let (discarded, kept) = std::mem::take(&mut state.hands[seat])
+            .into_iter()
+            .partition::<Vec<_>, _>(|card| card.0 == color);
+        state.hands[seat] = kept;
+
+        let top = state.discard_pile.len() - 1;
+        let count = discarded.len();
+        state.discard_pile.splice(top..top, discarded);
+        if count > 0 {
+            events.push(GameEvent::Discarded { seat, count });
+        }
+        state.current = state.next_seat();
+    }
+}
+
+/// Every other player draws a card, in turn order
+struct EveryoneDraws;
+
+impl CardEffect for EveryoneDraws {
+    fn key(&self) -> &str {
+        "everyone_draws"
+    }
+
+    fn name(&self) -> &str {
+        "everyone draws"
+    }
+
+    fn apply(&self, state: &mut GameState, seat: Seat, _: CardColor, events: &mut Vec<GameEvent>) {
+        (1..state.players()).for_each(|steps| {
+            state.draw_cards(state.seat_after(seat, steps), 1, events);
+        });
+        state.current = state.next_seat();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{features::rules::Action, CardVariant};
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_9: CardInfo = (CardColor::Red, CardVariant::Number(9));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const RED_DISCARD_ALL: CardInfo = (CardColor::Red, CardVariant::Custom(DISCARD_ALL));
+    const BLUE_EVERYONE_DRAWS: CardInfo = (CardColor::Blue, CardVariant::Custom(EVERYONE_DRAWS));
+
+    const PLAY_FIRST: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+
+    /// Seat 0 plays on a red 5
+    fn state(hands: Vec<Vec<CardInfo>>) -> GameState {
+        let mut state = GameState::from_seed(hands.len(), 0);
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = hands;
+        state
+    }
+
+    /// Skips the next player, only playable on a number from 1 to 4 whatever its color
+    struct LowSkip;
+
+    impl CardEffect for LowSkip {
+        fn key(&self) -> &str {
+            "low_skip"
+        }
+
+        fn name(&self) -> &str {
+            "low_skip"
+        }
+
+        fn is_playable(&self, state: &GameState, _: CardInfo) -> bool {
+            matches!(state.top_card().1, CardVariant::Number(1..=4))
+        }
+
+        fn apply(&self, state: &mut GameState, _: Seat, _: CardColor, _: &mut Vec<GameEvent>) {
+            state.current = state.seat_after(state.current, 2);
+        }
+    }
+
+    mod register {
+        use super::*;
+
+        #[test]
+        fn plays_a_custom_card() {
+            let id = EffectId(1000);
+            register(id, LowSkip);
+            let card = (CardColor::Green, CardVariant::Custom(id));
+            let mut state = state(vec![vec![card, card], vec![BLUE_7], vec![BLUE_7]]);
+
+            assert!(!state.is_playable(card));
+            state.discard_pile = vec![(CardColor::Red, CardVariant::Number(2))];
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.top_card(), card);
+            assert_eq!(state.active_color, CardColor::Green);
+            assert_eq!(state.current, 2);
+            assert_eq!(
+                crate::CardBundle::texture_path(card.0, card.1),
+                "cards/green/low_skip_green.png"
+            );
+        }
+
+        #[test]
+        fn unknown_effects_are_not_playable() {
+            let state = state(vec![vec![RED_5], vec![BLUE_7]]);
+
+            assert!(!state.is_playable((CardColor::Red, CardVariant::Custom(EffectId(999)))));
+        }
+    }
+
+    mod builtins {
+        use super::*;
+
+        #[test]
+        fn discard_all_of_its_color() {
+            let mut state = state(vec![
+                vec![RED_DISCARD_ALL, RED_9, BLUE_7, RED_5],
+                vec![BLUE_7],
+            ]);
+
+            let events = state.apply(0, PLAY_FIRST).unwrap();
+
+            assert!(events.contains(&GameEvent::Discarded { seat: 0, count: 2 }));
+            assert_eq!(state.hands[0], vec![BLUE_7]);
+            assert_eq!(
+                state.discard_pile,
+                vec![RED_5, RED_9, RED_5, RED_DISCARD_ALL]
+            );
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn everyone_draws_one() {
+            let mut state = state(vec![
+                vec![BLUE_EVERYONE_DRAWS, BLUE_7],
+                vec![BLUE_7],
+                vec![BLUE_7],
+            ]);
+            state.active_color = CardColor::Blue;
+
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(
+                state.hands.iter().map(Vec::len).collect::<Vec<_>>(),
+                vec![1, 2, 2]
+            );
+            assert_eq!(state.current, 1);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+
+use super::{GameEvent, GameState, Seat, PLAYABLE_COLORS};
+use crate::{features::deck::CardInfo, CardColor};
+
+/// Colors a wild card can be turned into on the dark side
+pub const DARK_COLORS: [CardColor; 4] = [
+    CardColor::Orange,
+    CardColor::Pink,
+    CardColor::Teal,
+    CardColor::Purple,
+];
+
+/// ## Side of the cards facing up in a Flip game
+///
+/// Every card has a light side and a dark side with its own colors and actions, a
+/// [`crate::CardVariant::Flip`] card turns the whole game over. Each light card has a fixed
+/// dark side, see [`flipped`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Side {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Side {
+    pub fn flipped(self) -> Self {
+        match self {
+            Side::Light => Side::Dark,
+            Side::Dark => Side::Light,
+        }
+    }
+
+    /// Colors a wild card can be turned into on this side
+    pub fn colors(self) -> [CardColor; 4] {
+        match self {
+            Side::Light => PLAYABLE_COLORS,
+            Side::Dark => DARK_COLORS,
+        }
+    }
+}
+
+/// The other side of `card`
+pub fn flipped((color, variant): CardInfo) -> CardInfo {
+    (color.flipped(), variant.flipped())
+}
+
+impl GameState {
+    /// Colors a wild card can be turned into on the side facing up
+    pub fn playable_colors(&self) -> [CardColor; 4] {
+        self.side.colors()
+    }
+
+    /// Every card of the piles and hands is turned over in place, the active color follows
+    pub(super) fn flip(&mut self, events: &mut Vec<GameEvent>) {
+        self.draw_pile
+            .iter_mut()
+            .chain(self.discard_pile.iter_mut())
+            .chain(self.hands.iter_mut().flatten())
+            .for_each(|card| *card = flipped(*card));
+        self.active_color = self.active_color.flipped();
+        self.side = self.side.flipped();
+
+        events.push(GameEvent::Flipped { side: self.side });
+    }
+
+    /// Every player but `seat` is skipped, which plays again
+    pub(super) fn skip_everyone(&mut self, seat: Seat, events: &mut Vec<GameEvent>) {
+        events.extend((1..self.players()).map(|steps| GameEvent::Skipped {
+            seat: self.seat_after(seat, steps),
+        }));
+        self.current = seat;
+    }
+
+    /// The next player draws until it gets a card of `color` or both piles run out, then is skipped
+    pub(super) fn draw_color(&mut self, color: CardColor, events: &mut Vec<GameEvent>) {
+        let seat = self.next_seat();
+
+        let mut count = 0;
+        while let Some(card) = self.draw_card(seat, events) {
+            count += 1;
+            if card.0 == color {
+                break;
+            }
+        }
+        events.push(GameEvent::Drew { seat, count });
+        self.skip_next(events);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::rules::{Action, RuleError},
+        CardVariant,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_FLIP: CardInfo = (CardColor::Red, CardVariant::Flip);
+    const BLUE_PLUS_TWO: CardInfo = (CardColor::Blue, CardVariant::PlusTwo);
+    const PINK_5: CardInfo = (CardColor::Pink, CardVariant::Number(5));
+    const TEAL_DRAW_FIVE: CardInfo = (CardColor::Teal, CardVariant::DrawFive);
+    const ORANGE_2: CardInfo = (CardColor::Orange, CardVariant::Number(2));
+    const ORANGE_SKIP_EVERYONE: CardInfo = (CardColor::Orange, CardVariant::SkipEveryone);
+    const DRAW_COLOR: CardInfo = (CardColor::Wild, CardVariant::WildDrawColor);
+
+    const PLAY_FIRST: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+
+    /// Seat 0 plays on a red 5, or on its pink dark side
+    fn state(side: Side, hands: Vec<Vec<CardInfo>>) -> GameState {
+        let top = match side {
+            Side::Light => RED_5,
+            Side::Dark => PINK_5,
+        };
+        let mut state = GameState::from_seed(hands.len(), 0);
+        state.side = side;
+        state.discard_pile = vec![top];
+        state.active_color = top.0;
+        state.hands = hands;
+        state
+    }
+
+    mod flip {
+        use super::*;
+
+        #[test]
+        fn turns_everything_over() {
+            let mut state = state(
+                Side::Light,
+                vec![vec![RED_FLIP, RED_5], vec![BLUE_PLUS_TWO]],
+            );
+            let next_draw = *state.draw_pile.last().unwrap();
+
+            let events = state.apply(0, PLAY_FIRST).unwrap();
+
+            assert!(events.contains(&GameEvent::Flipped { side: Side::Dark }));
+            assert_eq!(state.side, Side::Dark);
+            assert_eq!(state.hands, vec![vec![PINK_5], vec![TEAL_DRAW_FIVE]]);
+            assert_eq!(state.top_card(), (CardColor::Pink, CardVariant::Flip));
+            assert_eq!(state.active_color, CardColor::Pink);
+            assert_eq!(*state.draw_pile.last().unwrap(), flipped(next_draw));
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn flips_back() {
+            let card = (CardColor::Wild, CardVariant::PlusFour);
+
+            assert_eq!(flipped(card), DRAW_COLOR);
+            assert_eq!(flipped(flipped(card)), card);
+        }
+
+        #[test]
+        fn wild_colors_follow_the_side() {
+            let mut state = state(Side::Dark, vec![vec![DRAW_COLOR; 2], vec![ORANGE_2]]);
+            state.hands[0][0] = (CardColor::Wild, CardVariant::Wild);
+
+            assert_eq!(
+                state.apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: Some(CardColor::Red)
+                    }
+                ),
+                Err(RuleError::InvalidColor(CardColor::Red))
+            );
+            assert!(state.legal_actions(0).contains(&Action::Play {
+                card: 0,
+                color: Some(CardColor::Teal)
+            }));
+        }
+    }
+
+    mod dark_actions {
+        use super::*;
+
+        #[test]
+        fn draw_five_skips() {
+            let mut state = state(
+                Side::Dark,
+                vec![vec![TEAL_DRAW_FIVE, PINK_5], vec![PINK_5], vec![PINK_5]],
+            );
+            state.active_color = CardColor::Teal;
+
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.hands[1].len(), 1 + 5);
+            assert_eq!(state.current, 2);
+        }
+
+        #[test]
+        fn skip_everyone_plays_again() {
+            let mut state = state(
+                Side::Dark,
+                vec![
+                    vec![ORANGE_SKIP_EVERYONE, ORANGE_2],
+                    vec![PINK_5],
+                    vec![PINK_5],
+                ],
+            );
+            state.active_color = CardColor::Orange;
+
+            let events = state.apply(0, PLAY_FIRST).unwrap();
+
+            assert!(events.contains(&GameEvent::Skipped { seat: 1 }));
+            assert!(events.contains(&GameEvent::Skipped { seat: 2 }));
+            assert_eq!(state.current, 0);
+        }
+
+        #[test]
+        fn draw_color_until_the_color() {
+            let mut state = state(Side::Dark, vec![vec![DRAW_COLOR, PINK_5], vec![PINK_5]]);
+            state.draw_pile = vec![ORANGE_2, PINK_5, TEAL_DRAW_FIVE, TEAL_DRAW_FIVE];
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: Some(CardColor::Pink),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.hands[1].len(), 1 + 3);
+            assert_eq!(state.draw_pile, vec![ORANGE_2]);
+            assert_eq!(state.current, 0);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use super::{Action, GameEvent, GameState, RuleError, Seat};
+use crate::CardColor;
+
+impl GameState {
+    /// ## Whether `seat` may play `action` out of turn
+    ///
+    /// With [`super::HouseRules::jump_in`], a card identical to the top card can be played by anyone
+    /// until the next action, except on a +4, draw cards, a 7 or a drawn card waiting to be answered.
+    pub fn can_jump_in(&self, seat: Seat, action: Action) -> bool {
+        let Action::Play { card, .. } = action else {
+            return false;
+        };
+
+        self.rules.jump_in
+            && seat != self.current
+            && !self.is_over()
+            && self.plus_four.is_none()
+            && self.draw_stack == 0
+            && self.swapping.is_none()
+            && self.drawn.is_none()
+            && self.hands[seat].get(card) == Some(&self.top_card())
+    }
+
+    /// Out of turn plays `seat` could jump in with, wild cards get one action per color
+    pub fn jump_in_actions(&self, seat: Seat) -> Vec<Action> {
+        (0..self.hands[seat].len())
+            .map(|card| Action::Play { card, color: None })
+            .filter(|action| self.can_jump_in(seat, *action))
+            .flat_map(|action| match (action, self.top_card().0) {
+                (Action::Play { card, .. }, CardColor::Wild) => self
+                    .playable_colors()
+                    .iter()
+                    .map(|color| Action::Play {
+                        card,
+                        color: Some(*color),
+                    })
+                    .collect(),
+                _ => vec![action],
+            })
+            .collect()
+    }
+
+    /// Turns before `seat` plays from the current player, which is 0 turns away
+    pub fn turn_distance(&self, seat: Seat) -> usize {
+        (0..self.players())
+            .find(|steps| self.seat_after(self.current, *steps) == seat)
+            .expect("seats are around the table")
+    }
+
+    /// Play continues from the seat jumping in, the turn is given back if the play is refused
+    pub(super) fn jump_in(
+        &mut self,
+        seat: Seat,
+        card: usize,
+        color: Option<CardColor>,
+    ) -> Result<Vec<GameEvent>, RuleError> {
+        let current = std::mem::replace(&mut self.current, seat);
+
+        match self.play(seat, card, color) {
+            Ok(played) => Ok([GameEvent::JumpedIn { seat }]
+                .into_iter()
+                .chain(played)
+                .collect()),
+            Err(error) => {
+                self.current = current;
+                Err(error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{deck::CardInfo, rules::HouseRules},
+        CardVariant,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_5: CardInfo = (CardColor::Blue, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+
+    const PLAY_FIRST: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+
+    /// Seat 0 is playing on a red 5, seat 2 holds another one
+    fn state(jump_in: bool) -> GameState {
+        let mut state = GameState::with_rules(
+            crate::features::deck::Deck::default().into_cards(),
+            4,
+            0,
+            HouseRules {
+                jump_in,
+                ..HouseRules::default()
+            },
+        );
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![
+            vec![BLUE_7; 2],
+            vec![BLUE_7; 2],
+            vec![RED_5, BLUE_7],
+            vec![BLUE_5; 2],
+        ];
+        state
+    }
+
+    mod jump_in {
+        use super::*;
+
+        #[test]
+        fn play_continues_from_the_jumper() {
+            let mut state = state(true);
+
+            let events = state.apply(2, PLAY_FIRST).unwrap();
+
+            assert_eq!(events[0], GameEvent::JumpedIn { seat: 2 });
+            assert_eq!(state.hands[2], vec![BLUE_7]);
+            assert_eq!(state.current, 3);
+        }
+
+        #[test]
+        fn only_identical_cards() {
+            let mut state = state(true);
+
+            assert_eq!(
+                state.apply(3, PLAY_FIRST),
+                Err(RuleError::NotYourTurn {
+                    seat: 3,
+                    current: 0
+                })
+            );
+            assert!(state.jump_in_actions(3).is_empty());
+            assert_eq!(state.jump_in_actions(2), vec![PLAY_FIRST]);
+        }
+
+        #[test]
+        fn needs_the_house_rule() {
+            let mut state = state(false);
+
+            assert!(state.apply(2, PLAY_FIRST).is_err());
+            assert_eq!(state.current, 0);
+        }
+    }
+
+    mod turn_distance {
+        use super::*;
+
+        #[test]
+        fn follows_the_direction() {
+            let mut state = state(true);
+            state.current = 1;
+
+            assert_eq!(state.turn_distance(1), 0);
+            assert_eq!(state.turn_distance(0), 3);
+            state.direction = state.direction.reversed();
+            assert_eq!(state.turn_distance(0), 1);
+        }
+    }
+}
@@ -0,0 +1,1084 @@
+pub mod challenge;
+pub mod drawing;
+pub mod effects;
+pub mod flip;
+pub mod jump_in;
+pub mod projection;
+pub mod scoring;
+pub mod seven_o;
+pub mod stacking;
+pub mod turn_timer;
+pub mod uno;
+
+use std::fmt;
+
+use bevy::prelude::Resource;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use self::{
+    challenge::{PlusFourChallenge, RevealedHand},
+    drawing::DrawRule,
+    flip::Side,
+    stacking::Stacking,
+};
+use crate::{
+    features::deck::{CardInfo, Deck},
+    CardColor, CardVariant,
+};
+
+/// Optional rules a table agrees on before starting, the default plays the official game
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HouseRules {
+    /// Cards dealt to each player
+    pub hand_size: usize,
+    /// Points a player needs to win the match
+    #[serde(default = "scoring::default_target_score")]
+    pub target_score: u32,
+    #[serde(default)]
+    pub stacking: Stacking,
+    /// Anyone holding a card identical to the top card may play it out of turn
+    #[serde(default)]
+    pub jump_in: bool,
+    /// A 7 swaps hands with a chosen opponent and a 0 passes every hand along
+    #[serde(default)]
+    pub seven_o: bool,
+    #[serde(default)]
+    pub draw: DrawRule,
+    /// A playable card drawn must be played right away
+    #[serde(default)]
+    pub forced_play: bool,
+    /// Seconds a player has for its turn before a bot plays it, see [`turn_timer`]
+    #[serde(default)]
+    pub turn_time: Option<u32>,
+}
+
+impl Default for HouseRules {
+    fn default() -> Self {
+        Self {
+            hand_size: STARTING_HAND_SIZE,
+            target_score: scoring::TARGET_SCORE,
+            stacking: Stacking::default(),
+            jump_in: false,
+            seven_o: false,
+            draw: DrawRule::default(),
+            forced_play: false,
+            turn_time: None,
+        }
+    }
+}
+
+/// Index of a player around the table
+pub type Seat = usize;
+
+pub const STARTING_HAND_SIZE: usize = 7;
+
+/// Colors a wild card can be turned into
+pub const PLAYABLE_COLORS: [CardColor; 4] = [
+    CardColor::Yellow,
+    CardColor::Red,
+    CardColor::Blue,
+    CardColor::Green,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Direction {
+    #[default]
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Direction {
+    pub fn reversed(self) -> Self {
+        match self {
+            Direction::Clockwise => Direction::CounterClockwise,
+            Direction::CounterClockwise => Direction::Clockwise,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Action {
+    /// Play the card at index `card` of the hand, `color` is required for wild cards
+    Play {
+        card: usize,
+        color: Option<CardColor>,
+    },
+    /// Draw for the turn, see [`HouseRules::draw`]
+    Draw,
+    /// Keep the playable card just drawn and end the turn, see [`GameState::drawn`]
+    Pass,
+    /// Deal the next round once the current one is over, any seat can ask for it
+    NextRound,
+    /// Announce going down to one card, see [`GameState::can_call_uno`]
+    CallUno,
+    /// Catch `target` who went down to one card without calling uno, it draws a penalty
+    CatchUno { target: Seat },
+    /// Challenge the +4 just played on us, see [`challenge::PlusFourChallenge`]
+    ChallengePlusFour,
+    /// Swap hands with `target` after playing a 7, see [`HouseRules::seven_o`]
+    SwapHands { target: Seat },
+}
+
+/// What happened on the table after an [`Action`] got applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameEvent {
+    Played {
+        seat: Seat,
+        card: CardInfo,
+        color: CardColor,
+    },
+    Drew {
+        seat: Seat,
+        count: usize,
+    },
+    Skipped {
+        seat: Seat,
+    },
+    Reversed {
+        direction: Direction,
+    },
+    Reshuffled,
+    Won {
+        seat: Seat,
+    },
+    /// The winner of the round got the points of every card left in the other hands
+    Scored {
+        seat: Seat,
+        points: u32,
+    },
+    Dealt {
+        round: u64,
+    },
+    UnoCalled {
+        seat: Seat,
+    },
+    /// `seat` forgot to call uno and was caught by `by`
+    UnoCaught {
+        seat: Seat,
+        by: Seat,
+    },
+    /// `seat` played out of turn with a card identical to the top card, see [`GameState::can_jump_in`]
+    JumpedIn {
+        seat: Seat,
+    },
+    /// `seat` kept the playable card it drew
+    Passed {
+        seat: Seat,
+    },
+    HandsSwapped {
+        seat: Seat,
+        target: Seat,
+    },
+    /// Every card was turned over, `side` is now facing up
+    Flipped {
+        side: Side,
+    },
+    /// `seat` got rid of `count` cards along with the one it played
+    Discarded {
+        seat: Seat,
+        count: usize,
+    },
+    /// Every hand moved to the next seat in `direction`
+    HandsRotated {
+        direction: Direction,
+    },
+    /// The +4 of `seat` was challenged by `by`, who saw its hand
+    Challenged {
+        seat: Seat,
+        by: Seat,
+        bluffed: bool,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleError {
+    GameOver,
+    NotYourTurn { seat: Seat, current: Seat },
+    NoSuchCard(usize),
+    NotPlayable(CardInfo),
+    MissingColor,
+    InvalidColor(CardColor),
+    RoundNotOver,
+    UnoNotAllowed,
+    NothingToCatch(Seat),
+    NothingToChallenge,
+    PlusFourPending,
+    NothingToSwap,
+    SwapPending,
+    InvalidTarget(Seat),
+    DrawnCardPending,
+    ForcedPlay,
+    NothingToPass,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::GameOver => write!(f, "the game is over"),
+            RuleError::NotYourTurn { seat, current } => {
+                write!(f, "seat {} played during seat {} turn", seat, current)
+            }
+            RuleError::NoSuchCard(index) => write!(f, "no card at index {} in hand", index),
+            RuleError::NotPlayable((color, variant)) => {
+                write!(f, "{:?} {:?} can't be played now", color, variant)
+            }
+            RuleError::MissingColor => write!(f, "a wild card needs a color"),
+            RuleError::InvalidColor(color) => write!(f, "{:?} can't be chosen", color),
+            RuleError::RoundNotOver => write!(f, "the round is not over"),
+            RuleError::UnoNotAllowed => {
+                write!(
+                    f,
+                    "uno is called right before playing the second to last card"
+                )
+            }
+            RuleError::NothingToCatch(seat) => write!(f, "seat {} can't be caught", seat),
+            RuleError::NothingToChallenge => write!(f, "there is no +4 to challenge"),
+            RuleError::NothingToSwap => write!(f, "hands are only swapped after playing a 7"),
+            RuleError::SwapPending => write!(f, "a hand to swap with must be picked first"),
+            RuleError::InvalidTarget(seat) => write!(f, "seat {} can't be picked", seat),
+            RuleError::DrawnCardPending => {
+                write!(
+                    f,
+                    "only the card just drawn can be played, or kept by passing"
+                )
+            }
+            RuleError::ForcedPlay => write!(f, "the card just drawn must be played"),
+            RuleError::NothingToPass => {
+                write!(f, "passing is only allowed after drawing a playable card")
+            }
+            RuleError::PlusFourPending => {
+                write!(f, "the +4 must be challenged or accepted by drawing")
+            }
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+/// Headless state of a game, every rule is enforced by [`GameState::apply`].
+///
+/// Piles are stored bottom first: the last card of `draw_pile` is the next one drawn
+/// and the last card of `discard_pile` is the one cards are played on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameState {
+    pub draw_pile: Vec<CardInfo>,
+    pub discard_pile: Vec<CardInfo>,
+    pub hands: Vec<Vec<CardInfo>>,
+    pub current: Seat,
+    pub direction: Direction,
+    pub active_color: CardColor,
+    pub winner: Option<Seat>,
+    /// Seed used to shuffle the discard pile back into the draw pile
+    pub seed: u64,
+    pub reshuffles: u64,
+    pub rules: HouseRules,
+    /// Points of each seat over the rounds of the match, see [`GameState::fill_scores`]
+    #[serde(default)]
+    pub scores: Vec<u32>,
+    /// Rounds dealt before this one
+    #[serde(default)]
+    pub round: u64,
+    /// The current player called uno before playing its second to last card
+    #[serde(default)]
+    pub uno_called: Option<Seat>,
+    /// Seat left with one card without calling uno, until the next player acts
+    #[serde(default)]
+    pub forgot_uno: Option<Seat>,
+    /// +4 the current player has to answer before anything else
+    #[serde(default)]
+    pub plus_four: Option<PlusFourChallenge>,
+    #[serde(default)]
+    pub revealed: Option<RevealedHand>,
+    /// Cards stacked +2 and +4 make the current player draw, see [`Stacking`]
+    #[serde(default)]
+    pub draw_stack: usize,
+    /// Seat that played a 7 and picks whom to swap hands with before the turn passes
+    #[serde(default)]
+    pub swapping: Option<Seat>,
+    /// Index of the playable card the current player just drew, the only one it may still play
+    #[serde(default)]
+    pub drawn: Option<usize>,
+    /// Side of the cards facing up, only the Flip deck ever turns to the dark side
+    #[serde(default)]
+    pub side: Side,
+}
+
+impl GameState {
+    /// Starts a game with the official rules, see [`GameState::with_rules`]
+    pub fn new(deck: Vec<CardInfo>, players: usize, seed: u64) -> Self {
+        Self::with_rules(deck, players, seed, HouseRules::default())
+    }
+
+    /// ## Starts a game
+    ///
+    /// Deals [`HouseRules::hand_size`] cards to each of the `players` from the top of `deck`,
+    /// then flips the first non wild card to start the discard pile.
+    ///
+    /// Panics if there are less than two players or not enough cards to deal.
+    pub fn with_rules(
+        mut deck: Vec<CardInfo>,
+        players: usize,
+        seed: u64,
+        rules: HouseRules,
+    ) -> Self {
+        assert!(players >= 2, "a game needs at least two players");
+        assert!(
+            deck.len() > players * rules.hand_size,
+            "not enough cards to deal"
+        );
+
+        let mut hands = vec![Vec::with_capacity(rules.hand_size); players];
+        (0..rules.hand_size).for_each(|_| {
+            hands.iter_mut().for_each(|hand| hand.extend(deck.pop()));
+        });
+
+        // Wild cards go back under the deck until a colored card is flipped
+        let mut first = deck.pop().expect("not enough cards to deal");
+        for _ in 0..deck.len() {
+            if first.0 != CardColor::Wild {
+                break;
+            }
+            deck.insert(0, first);
+            first = deck.pop().expect("not enough cards to deal");
+        }
+
+        Self {
+            draw_pile: deck,
+            discard_pile: vec![first],
+            hands,
+            current: 0,
+            direction: Direction::default(),
+            active_color: first.0,
+            winner: None,
+            seed,
+            reshuffles: 0,
+            rules,
+            scores: vec![0; players],
+            round: 0,
+            uno_called: None,
+            forgot_uno: None,
+            plus_four: None,
+            revealed: None,
+            draw_stack: 0,
+            swapping: None,
+            drawn: None,
+            side: Side::default(),
+        }
+    }
+
+    /// Starts a game with the default deck shuffled from `seed`
+    pub fn from_seed(players: usize, seed: u64) -> Self {
+        Self::new(Deck::from_seed(seed).into_cards(), players, seed)
+    }
+
+    pub fn players(&self) -> usize {
+        self.hands.len()
+    }
+
+    pub fn top_card(&self) -> CardInfo {
+        *self
+            .discard_pile
+            .last()
+            .expect("the discard pile is never empty")
+    }
+
+    /// The round is over, see [`GameState::is_match_over`] for the whole match
+    pub fn is_over(&self) -> bool {
+        self.winner.is_some()
+    }
+
+    /// Seat playing `steps` turns after `seat` in the current direction
+    pub fn seat_after(&self, seat: Seat, steps: usize) -> Seat {
+        let players = self.players();
+        let steps = steps % players;
+
+        match self.direction {
+            Direction::Clockwise => (seat + steps) % players,
+            Direction::CounterClockwise => (seat + players - steps) % players,
+        }
+    }
+
+    /// Seat playing after the current one
+    pub fn next_seat(&self) -> Seat {
+        self.seat_after(self.current, 1)
+    }
+
+    /// A wild card can always be played, others must match the active color or the top card variant.
+    /// Only draw cards can be stacked on a [`GameState::draw_stack`], custom cards follow
+    /// [`effects::CardEffect::is_playable`]
+    pub fn is_playable(&self, (color, variant): CardInfo) -> bool {
+        if self.draw_stack > 0 {
+            return self.rules.stacking.can_stack(self.top_card().1, variant);
+        }
+        if let CardVariant::Custom(id) = variant {
+            return effects::effect(id)
+                .is_some_and(|effect| effect.is_playable(self, (color, variant)));
+        }
+
+        color == CardColor::Wild || color == self.active_color || variant == self.top_card().1
+    }
+
+    /// Indexes of the cards `seat` could play on the current top card, only the card
+    /// just drawn once the current player drew
+    pub fn playable_cards(&self, seat: Seat) -> Vec<usize> {
+        let drawn = self.drawn.filter(|_| seat == self.current);
+
+        self.hands[seat]
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| drawn.is_none() || drawn == Some(*index))
+            .filter(|(_, card)| self.is_playable(**card))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Every action `seat` is allowed to take, empty when it isn't its turn
+    pub fn legal_actions(&self, seat: Seat) -> Vec<Action> {
+        if self.is_over() && !self.is_match_over() {
+            return vec![Action::NextRound];
+        }
+        if self.is_over() || seat != self.current {
+            return Vec::new();
+        }
+        if self.plus_four.is_some() {
+            return vec![Action::ChallengePlusFour, Action::Draw];
+        }
+        if self.swapping.is_some() {
+            return self
+                .swap_targets(seat)
+                .into_iter()
+                .map(|target| Action::SwapHands { target })
+                .collect();
+        }
+
+        let mut actions: Vec<Action> = self
+            .playable_cards(seat)
+            .into_iter()
+            .flat_map(|card| match self.hands[seat][card].0 {
+                CardColor::Wild => self
+                    .playable_colors()
+                    .iter()
+                    .map(|color| Action::Play {
+                        card,
+                        color: Some(*color),
+                    })
+                    .collect::<Vec<_>>(),
+                _ => vec![Action::Play { card, color: None }],
+            })
+            .collect();
+        match self.drawn {
+            Some(_) if self.rules.forced_play => {}
+            Some(_) => actions.push(Action::Pass),
+            None => actions.push(Action::Draw),
+        }
+
+        actions
+    }
+
+    /// Apply the `action` of `seat`, returning what happened on the table.
+    /// Uno calls, catches and jump-ins can happen out of turn, every other action waits for its turn
+    pub fn apply(&mut self, seat: Seat, action: Action) -> Result<Vec<GameEvent>, RuleError> {
+        if self.is_match_over() {
+            return Err(RuleError::GameOver);
+        }
+        match action {
+            Action::NextRound => {
+                if !self.is_over() {
+                    return Err(RuleError::RoundNotOver);
+                }
+                let mut events = Vec::new();
+                self.next_round(&mut events);
+
+                return Ok(events);
+            }
+            Action::CallUno => return self.call_uno(seat),
+            Action::CatchUno { target } => return self.catch_uno(seat, target),
+            Action::SwapHands { target } => return self.swap_hands(seat, target),
+            Action::Play { .. } | Action::Draw | Action::Pass | Action::ChallengePlusFour => {}
+        }
+        if self.is_over() {
+            return Err(RuleError::GameOver);
+        }
+        if self.swapping.is_some() {
+            return Err(RuleError::SwapPending);
+        }
+        let jumping_in = self.can_jump_in(seat, action);
+        if seat != self.current && !jumping_in {
+            return Err(RuleError::NotYourTurn {
+                seat,
+                current: self.current,
+            });
+        }
+
+        // The next player acting closes the window to catch a forgotten uno,
+        // and a revealed hand is hidden again once its viewer acts
+        let forgot_uno = self.forgot_uno.take();
+        let revealed = self.revealed;
+        if revealed.is_some_and(|revealed| revealed.to == seat) {
+            self.revealed = None;
+        }
+        let result = match (action, self.plus_four) {
+            (Action::Play { .. } | Action::Pass, Some(_)) => Err(RuleError::PlusFourPending),
+            (Action::Draw, Some(challenge)) => Ok(self.accept_plus_four(challenge)),
+            (Action::ChallengePlusFour, Some(challenge)) => Ok(self.challenge_plus_four(challenge)),
+            (Action::ChallengePlusFour, None) => Err(RuleError::NothingToChallenge),
+            (Action::Play { card, color }, None) if jumping_in => self.jump_in(seat, card, color),
+            (Action::Play { card, color }, None) => self.play(seat, card, color),
+            (Action::Draw, None) if self.draw_stack > 0 => Ok(self.draw_stacked()),
+            (Action::Draw, None) if self.drawn.is_some() => Err(RuleError::DrawnCardPending),
+            (Action::Draw, None) => Ok(self.draw_turn(seat)),
+            (Action::Pass, None) => self.pass(seat),
+            _ => unreachable!("applied before checking the turn"),
+        };
+        match result {
+            Ok(_) => self.uno_called = None,
+            Err(_) => {
+                self.forgot_uno = forgot_uno;
+                self.revealed = revealed;
+            }
+        }
+
+        result
+    }
+
+    fn play(
+        &mut self,
+        seat: Seat,
+        index: usize,
+        color: Option<CardColor>,
+    ) -> Result<Vec<GameEvent>, RuleError> {
+        let card = *self.hands[seat]
+            .get(index)
+            .ok_or(RuleError::NoSuchCard(index))?;
+
+        if self.drawn.is_some_and(|drawn| drawn != index) {
+            return Err(RuleError::DrawnCardPending);
+        }
+        if !self.is_playable(card) {
+            return Err(RuleError::NotPlayable(card));
+        }
+
+        let color = match (card.0, color) {
+            (CardColor::Wild, None) => return Err(RuleError::MissingColor),
+            (CardColor::Wild, Some(color)) if !self.playable_colors().contains(&color) => {
+                return Err(RuleError::InvalidColor(color))
+            }
+            (CardColor::Wild, Some(color)) => color,
+            (color, _) => color,
+        };
+
+        self.hands[seat].remove(index);
+        self.drawn = None;
+        self.discard_pile.push(card);
+        let previous_color = std::mem::replace(&mut self.active_color, color);
+        if self.hands[seat].len() == 1 && self.uno_called != Some(seat) {
+            self.forgot_uno = Some(seat);
+        }
+
+        let mut events = vec![GameEvent::Played { seat, card, color }];
+
+        match card.1 {
+            CardVariant::PlusTwo | CardVariant::PlusFour | CardVariant::DrawFive
+                if self.rules.stacking != Stacking::Off =>
+            {
+                let penalty = stacking::draw_penalty(card.1).expect("draw cards have a penalty");
+                self.stack_draw(seat, penalty, &mut events);
+            }
+            CardVariant::Number(7) if self.rules.seven_o && !self.hands[seat].is_empty() => {
+                self.swapping = Some(seat);
+            }
+            CardVariant::Number(0) if self.rules.seven_o && !self.hands[seat].is_empty() => {
+                self.rotate_hands(&mut events);
+                self.current = self.next_seat();
+            }
+            CardVariant::Block => self.skip_next(&mut events),
+            CardVariant::Invert => {
+                self.direction = self.direction.reversed();
+                events.push(GameEvent::Reversed {
+                    direction: self.direction,
+                });
+                // With two players reversing gives the turn back, like a block
+                if self.players() == 2 {
+                    self.skip_next(&mut events);
+                } else {
+                    self.current = self.next_seat();
+                }
+            }
+            CardVariant::PlusTwo | CardVariant::DrawFive => {
+                let penalty = stacking::draw_penalty(card.1).expect("draw cards have a penalty");
+                self.draw_cards(self.next_seat(), penalty, &mut events);
+                self.skip_next(&mut events);
+            }
+            CardVariant::SkipEveryone => self.skip_everyone(seat, &mut events),
+            CardVariant::WildDrawColor => self.draw_color(color, &mut events),
+            CardVariant::Flip => {
+                self.flip(&mut events);
+                self.current = self.next_seat();
+            }
+            // The last card of a round can't be challenged anymore
+            CardVariant::PlusFour if self.hands[seat].is_empty() => {
+                self.draw_cards(self.next_seat(), challenge::PLUS_FOUR_PENALTY, &mut events);
+                self.skip_next(&mut events);
+            }
+            CardVariant::PlusFour => {
+                self.current = self.next_seat();
+                self.plus_four = Some(PlusFourChallenge {
+                    offender: seat,
+                    victim: self.current,
+                    previous_color,
+                    held: self.hands[seat].len(),
+                });
+            }
+            CardVariant::Custom(id) => match effects::effect(id) {
+                Some(effect) => effect.apply(self, seat, color, &mut events),
+                None => self.current = self.next_seat(),
+            },
+            CardVariant::Number(_) | CardVariant::Wild => self.current = self.next_seat(),
+        }
+
+        if self.hands[seat].is_empty() {
+            self.winner = Some(seat);
+            events.push(GameEvent::Won { seat });
+            self.score_round(seat, &mut events);
+        }
+
+        Ok(events)
+    }
+
+    fn skip_next(&mut self, events: &mut Vec<GameEvent>) {
+        events.push(GameEvent::Skipped {
+            seat: self.next_seat(),
+        });
+        self.current = self.seat_after(self.current, 2);
+    }
+
+    /// The current player loses its turn after drawing a penalty
+    fn skip_current(&mut self, events: &mut Vec<GameEvent>) {
+        events.push(GameEvent::Skipped { seat: self.current });
+        self.current = self.next_seat();
+    }
+
+    /// Draw up to `count` cards for `seat`, reshuffling the discard pile when the draw pile runs out
+    pub fn draw_cards(&mut self, seat: Seat, count: usize, events: &mut Vec<GameEvent>) -> usize {
+        let drawn = (0..count)
+            .map_while(|_| self.draw_card(seat, events))
+            .count();
+
+        events.push(GameEvent::Drew { seat, count: drawn });
+        drawn
+    }
+
+    /// Move the top card of the draw pile to the hand of `seat`, `None` once both piles run out
+    fn draw_card(&mut self, seat: Seat, events: &mut Vec<GameEvent>) -> Option<CardInfo> {
+        if self.draw_pile.is_empty() && self.reshuffle() {
+            events.push(GameEvent::Reshuffled);
+        }
+        let card = self.draw_pile.pop()?;
+        self.hands[seat].push(card);
+
+        Some(card)
+    }
+
+    /// Shuffle every discarded card but the top one back into the draw pile
+    fn reshuffle(&mut self) -> bool {
+        if self.discard_pile.len() < 2 {
+            return false;
+        }
+
+        let top = self.discard_pile.pop().expect("checked above");
+        let mut cards = std::mem::replace(&mut self.discard_pile, vec![top]);
+
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(self.reshuffles));
+        cards.shuffle(&mut rng);
+        cards.append(&mut self.draw_pile);
+        self.draw_pile = cards;
+        self.reshuffles += 1;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED_1: CardInfo = (CardColor::Red, CardVariant::Number(1));
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_5: CardInfo = (CardColor::Blue, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const WILD: CardInfo = (CardColor::Wild, CardVariant::Wild);
+
+    /// Game where seat 0 plays on a red 5 with the given hands
+    fn state(hands: Vec<Vec<CardInfo>>) -> GameState {
+        GameState {
+            scores: vec![0; hands.len()],
+            round: 0,
+            uno_called: None,
+            forgot_uno: None,
+            plus_four: None,
+            revealed: None,
+            draw_stack: 0,
+            swapping: None,
+            drawn: None,
+            side: Side::Light,
+            draw_pile: vec![BLUE_7; 10],
+            discard_pile: vec![RED_5],
+            hands,
+            current: 0,
+            direction: Direction::Clockwise,
+            active_color: CardColor::Red,
+            winner: None,
+            seed: 0,
+            reshuffles: 0,
+            rules: HouseRules::default(),
+        }
+    }
+
+    mod new {
+        use super::*;
+
+        #[test]
+        fn deals_starting_hands() {
+            let deck = Deck::default().into_cards();
+            let deck_size = deck.len();
+
+            let state = GameState::new(deck, 4, 0);
+
+            assert!(state
+                .hands
+                .iter()
+                .all(|hand| hand.len() == STARTING_HAND_SIZE));
+            assert_eq!(state.discard_pile.len(), 1);
+            assert_eq!(
+                state.draw_pile.len(),
+                deck_size - 4 * STARTING_HAND_SIZE - 1
+            );
+        }
+
+        #[test]
+        fn deals_house_rules_hand_size() {
+            let rules = HouseRules {
+                hand_size: 5,
+                ..HouseRules::default()
+            };
+
+            let state = GameState::with_rules(Deck::default().into_cards(), 3, 0, rules);
+
+            assert!(state.hands.iter().all(|hand| hand.len() == 5));
+            assert_eq!(state.rules, rules);
+        }
+
+        #[test]
+        fn never_starts_on_wild() {
+            // Cards are dealt from the end of the deck, the wild is flipped first
+            let mut deck = vec![RED_1; 5];
+            deck.extend([BLUE_5, WILD]);
+            deck.extend(vec![RED_1; 2 * STARTING_HAND_SIZE]);
+
+            let state = GameState::new(deck, 2, 0);
+
+            assert_eq!(state.top_card(), BLUE_5);
+            assert_eq!(state.active_color, CardColor::Blue);
+            assert_eq!(state.draw_pile.first(), Some(&WILD));
+        }
+
+        #[test]
+        fn same_seed_same_game() {
+            assert_eq!(GameState::from_seed(4, 42), GameState::from_seed(4, 42));
+        }
+    }
+
+    mod is_playable {
+        use super::*;
+
+        #[test]
+        fn matching_color_variant_or_wild() {
+            let state = state(vec![vec![], vec![]]);
+
+            assert!(state.is_playable(RED_1));
+            assert!(state.is_playable(BLUE_5));
+            assert!(state.is_playable(WILD));
+            assert!(!state.is_playable(BLUE_7));
+        }
+    }
+
+    mod apply {
+        use super::*;
+
+        #[test]
+        fn play_moves_card_and_passes_turn() {
+            let mut state = state(vec![vec![BLUE_5, BLUE_7], vec![RED_1]]);
+
+            let events = state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.top_card(), BLUE_5);
+            assert_eq!(state.active_color, CardColor::Blue);
+            assert_eq!(state.hands[0], vec![BLUE_7]);
+            assert_eq!(state.current, 1);
+            assert_eq!(
+                events,
+                vec![GameEvent::Played {
+                    seat: 0,
+                    card: BLUE_5,
+                    color: CardColor::Blue
+                }]
+            );
+        }
+
+        #[test]
+        fn not_your_turn() {
+            let mut state = state(vec![vec![RED_1], vec![RED_1]]);
+
+            assert_eq!(
+                state.apply(1, Action::Draw),
+                Err(RuleError::NotYourTurn {
+                    seat: 1,
+                    current: 0
+                })
+            );
+        }
+
+        #[test]
+        fn not_playable() {
+            let mut state = state(vec![vec![BLUE_7], vec![RED_1]]);
+
+            assert_eq!(
+                state.apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None
+                    }
+                ),
+                Err(RuleError::NotPlayable(BLUE_7))
+            );
+        }
+
+        #[test]
+        fn wild_needs_a_color() {
+            let mut state = state(vec![vec![WILD, RED_1], vec![RED_1]]);
+
+            assert_eq!(
+                state.apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None
+                    }
+                ),
+                Err(RuleError::MissingColor)
+            );
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: Some(CardColor::Green),
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.active_color, CardColor::Green);
+        }
+
+        #[test]
+        fn block_skips_next() {
+            let mut state = state(vec![
+                vec![(CardColor::Red, CardVariant::Block), RED_1],
+                vec![RED_1],
+                vec![RED_1],
+            ]);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.current, 2);
+        }
+
+        #[test]
+        fn invert_reverses_direction() {
+            let mut state = state(vec![
+                vec![(CardColor::Red, CardVariant::Invert), RED_1],
+                vec![RED_1],
+                vec![RED_1],
+            ]);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.direction, Direction::CounterClockwise);
+            assert_eq!(state.current, 2);
+        }
+
+        #[test]
+        fn invert_with_two_players_replays() {
+            let mut state = state(vec![
+                vec![(CardColor::Red, CardVariant::Invert), RED_1],
+                vec![RED_1],
+            ]);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.current, 0);
+        }
+
+        #[test]
+        fn plus_two_draws_and_skips() {
+            let mut state = state(vec![
+                vec![(CardColor::Red, CardVariant::PlusTwo), RED_1],
+                vec![RED_1],
+                vec![RED_1],
+            ]);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.hands[1].len(), 3);
+            assert_eq!(state.current, 2);
+        }
+
+        #[test]
+        fn plus_four_draws_and_skips() {
+            let mut state = state(vec![
+                vec![(CardColor::Wild, CardVariant::PlusFour), RED_1],
+                vec![RED_1],
+                vec![RED_1],
+            ]);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: Some(CardColor::Blue),
+                    },
+                )
+                .unwrap();
+
+            state.apply(1, Action::Draw).unwrap();
+
+            assert_eq!(state.hands[1].len(), 5);
+            assert_eq!(state.current, 2);
+            assert_eq!(state.active_color, CardColor::Blue);
+        }
+
+        #[test]
+        fn draw_passes_turn() {
+            let mut state = state(vec![vec![BLUE_7], vec![RED_1]]);
+
+            state.apply(0, Action::Draw).unwrap();
+
+            assert_eq!(state.hands[0].len(), 2);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn last_card_wins() {
+            let mut state = state(vec![vec![RED_1], vec![RED_1]]);
+
+            let events = state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.winner, Some(0));
+            assert!(events.contains(&GameEvent::Won { seat: 0 }));
+            assert_eq!(state.apply(1, Action::Draw), Err(RuleError::GameOver));
+        }
+    }
+
+    mod draw_cards {
+        use super::*;
+
+        #[test]
+        fn reshuffles_discard_pile() {
+            let mut state = state(vec![vec![], vec![]]);
+            state.draw_pile.clear();
+            state.discard_pile = vec![RED_1, BLUE_7, RED_5];
+
+            let mut events = Vec::new();
+            let drawn = state.draw_cards(0, 1, &mut events);
+
+            assert_eq!(drawn, 1);
+            assert_eq!(state.discard_pile, vec![RED_5]);
+            assert_eq!(state.draw_pile.len(), 1);
+            assert!(events.contains(&GameEvent::Reshuffled));
+        }
+
+        #[test]
+        fn empty_piles_draw_nothing() {
+            let mut state = state(vec![vec![], vec![]]);
+            state.draw_pile.clear();
+
+            let drawn = state.draw_cards(0, 2, &mut Vec::new());
+
+            assert_eq!(drawn, 0);
+            assert!(state.hands[0].is_empty());
+        }
+    }
+
+    mod legal_actions {
+        use super::*;
+
+        #[test]
+        fn wild_has_one_action_per_color() {
+            let state = state(vec![vec![WILD, BLUE_7], vec![]]);
+
+            let actions = state.legal_actions(0);
+
+            assert_eq!(actions.len(), PLAYABLE_COLORS.len() + 1);
+            assert_eq!(actions.last(), Some(&Action::Draw));
+        }
+
+        #[test]
+        fn none_out_of_turn() {
+            let state = state(vec![vec![RED_1], vec![RED_1]]);
+
+            assert!(state.legal_actions(1).is_empty());
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use serde::{Deserialize, Serialize};
+
+use super::{challenge::PlusFourChallenge, flip::Side, Direction, GameState, Seat};
+use crate::{features::deck::CardInfo, CardColor};
+
+/// A card as seen by one player, face down cards keep their identity secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SeenCard {
+    FaceUp(CardInfo),
+    FaceDown,
+}
+
+impl SeenCard {
+    pub fn info(self) -> Option<CardInfo> {
+        match self {
+            SeenCard::FaceUp(card) => Some(card),
+            SeenCard::FaceDown => None,
+        }
+    }
+}
+
+/// ## Everything one player is allowed to know about a [`GameState`]
+///
+/// Only the hand of `viewer` is face up, other hands and the draw pile are face down
+/// placeholders so they can be counted but never identified. Played cards are public.
+/// Spectators have no `viewer` and see no hand, except in a [`GameState::god_view`].
+/// Every hand is shown once the round is over, to count its points, and a challenged
+/// +4 shows the hand of its offender to the challenger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerView {
+    /// Seat whose hand is face up, `None` when watching without playing
+    pub viewer: Option<Seat>,
+    pub draw_pile: Vec<SeenCard>,
+    pub discard_pile: Vec<CardInfo>,
+    pub hands: Vec<Vec<SeenCard>>,
+    pub current: Seat,
+    pub direction: Direction,
+    pub active_color: CardColor,
+    pub winner: Option<Seat>,
+    pub scores: Vec<u32>,
+    pub target_score: u32,
+    pub uno_called: Option<Seat>,
+    pub forgot_uno: Option<Seat>,
+    pub plus_four: Option<PlusFourChallenge>,
+    pub draw_stack: usize,
+    pub swapping: Option<Seat>,
+    /// Playable card the current player just drew, see [`GameState::drawn`]
+    pub drawn: Option<usize>,
+    pub forced_play: bool,
+    /// Seconds each turn lasts, see [`super::HouseRules::turn_time`]
+    pub turn_time: Option<u32>,
+    pub side: Side,
+}
+
+impl PlayerView {
+    pub fn players(&self) -> usize {
+        self.hands.len()
+    }
+
+    pub fn top_card(&self) -> CardInfo {
+        *self
+            .discard_pile
+            .last()
+            .expect("the discard pile is never empty")
+    }
+
+    /// Seat that won the match with its last round
+    pub fn match_winner(&self) -> Option<Seat> {
+        self.winner
+            .filter(|winner| self.scores[*winner] >= self.target_score)
+    }
+
+    /// Same as [`GameState::can_call_uno`]
+    pub fn can_call_uno(&self, seat: Seat) -> bool {
+        if self.forgot_uno == Some(seat) {
+            return true;
+        }
+
+        self.winner.is_none()
+            && self.plus_four.is_none()
+            && seat == self.current
+            && self.hands[seat].len() == 2
+            && self.uno_called != Some(seat)
+    }
+
+    /// Whether `seat` may keep the card it just drew and end its turn
+    pub fn can_pass(&self, seat: Seat) -> bool {
+        seat == self.current && self.drawn.is_some() && !self.forced_play
+    }
+
+    /// Face up cards in the hand of `seat`, empty while it is hidden from the viewer
+    pub fn hand(&self, seat: Seat) -> Vec<CardInfo> {
+        self.hands
+            .get(seat)
+            .map(|hand| hand.iter().filter_map(|card| card.info()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl GameState {
+    /// What `viewer` sees of the game, see [`PlayerView`]
+    pub fn view_for(&self, viewer: Option<Seat>) -> PlayerView {
+        PlayerView {
+            viewer,
+            draw_pile: vec![SeenCard::FaceDown; self.draw_pile.len()],
+            discard_pile: self.discard_pile.clone(),
+            hands: self
+                .hands
+                .iter()
+                .enumerate()
+                .map(|(seat, hand)| {
+                    let revealed = self.revealed.is_some_and(|revealed| {
+                        revealed.seat == seat && Some(revealed.to) == viewer
+                    });
+                    hand.iter()
+                        .map(|card| {
+                            if Some(seat) == viewer || self.is_over() || revealed {
+                                SeenCard::FaceUp(*card)
+                            } else {
+                                SeenCard::FaceDown
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+            current: self.current,
+            direction: self.direction,
+            active_color: self.active_color,
+            winner: self.winner,
+            scores: self.scores.clone(),
+            target_score: self.rules.target_score,
+            uno_called: self.uno_called,
+            forgot_uno: self.forgot_uno,
+            plus_four: self.plus_four,
+            draw_stack: self.draw_stack,
+            swapping: self.swapping,
+            drawn: self.drawn,
+            forced_play: self.rules.forced_play,
+            turn_time: self.rules.turn_time,
+            side: self.side,
+        }
+    }
+
+    /// What a spectator allowed to see every hand sees, the draw pile stays face down.
+    /// Only sent with a delay so it can't help anyone still playing
+    pub fn god_view(&self) -> PlayerView {
+        PlayerView {
+            hands: self
+                .hands
+                .iter()
+                .map(|hand| hand.iter().copied().map(SeenCard::FaceUp).collect())
+                .collect(),
+            ..self.view_for(None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardVariant;
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const GREEN_2: CardInfo = (CardColor::Green, CardVariant::PlusTwo);
+    const YELLOW_BLOCK: CardInfo = (CardColor::Yellow, CardVariant::Block);
+
+    /// Every color is only known by one seat or lies in the draw pile
+    fn state() -> GameState {
+        let mut state = GameState::from_seed(3, 0);
+        state.draw_pile = vec![GREEN_2; 10];
+        state.discard_pile = vec![RED_5];
+        state.hands = vec![vec![BLUE_7, BLUE_7], vec![YELLOW_BLOCK], vec![]];
+        state
+    }
+
+    mod view_for {
+        use super::*;
+
+        #[test]
+        fn only_viewer_hand_face_up() {
+            let view = state().view_for(Some(0));
+
+            assert_eq!(view.hand(0), vec![BLUE_7, BLUE_7]);
+            assert_eq!(view.hands[1], vec![SeenCard::FaceDown]);
+            assert_eq!(view.draw_pile, vec![SeenCard::FaceDown; 10]);
+            assert_eq!(view.top_card(), RED_5);
+        }
+
+        #[test]
+        fn hidden_cards_never_serialized() {
+            let state = state();
+
+            for (viewer, hidden) in [(Some(0), "Yellow"), (Some(1), "Blue"), (None, "Blue")] {
+                let json = serde_json::to_string(&state.view_for(viewer)).unwrap();
+
+                assert!(!json.contains(hidden), "{} leaked in {}", hidden, json);
+                assert!(!json.contains("Green"), "draw pile leaked in {}", json);
+            }
+        }
+
+        #[test]
+        fn god_view_shows_every_hand() {
+            let view = state().god_view();
+
+            assert_eq!(view.viewer, None);
+            assert_eq!(
+                view.hands[0],
+                vec![SeenCard::FaceUp(BLUE_7), SeenCard::FaceUp(BLUE_7)]
+            );
+            assert_eq!(view.hands[1], vec![SeenCard::FaceUp(YELLOW_BLOCK)]);
+            assert_eq!(view.draw_pile, vec![SeenCard::FaceDown; 10]);
+        }
+
+        #[test]
+        fn round_end_shows_every_hand() {
+            let mut state = state();
+            state.winner = Some(2);
+
+            let view = state.view_for(Some(0));
+
+            assert_eq!(view.hand(1), vec![YELLOW_BLOCK]);
+            assert_eq!(view.draw_pile, vec![SeenCard::FaceDown; 10]);
+        }
+
+        #[test]
+        fn spectators_see_no_hand() {
+            let view = state().view_for(None);
+
+            assert!(view.hand(0).is_empty());
+            assert!(view
+                .hands
+                .iter()
+                .flatten()
+                .all(|card| *card == SeenCard::FaceDown));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::{
+    flip::{self, Side},
+    Direction, GameEvent, GameState, Seat,
+};
+use crate::{features::deck::CardInfo, CardVariant};
+
+/// Points a match is played to with the official rules
+pub const TARGET_SCORE: u32 = 500;
+
+pub(super) fn default_target_score() -> u32 {
+    TARGET_SCORE
+}
+
+/// Points a card left in a hand gives to the winner of the round
+pub fn card_points((_, variant): CardInfo) -> u32 {
+    match variant {
+        CardVariant::Number(number) => number as u32,
+        CardVariant::Block
+        | CardVariant::Invert
+        | CardVariant::PlusTwo
+        | CardVariant::Flip
+        | CardVariant::DrawFive => 20,
+        CardVariant::SkipEveryone => 30,
+        CardVariant::Wild | CardVariant::PlusFour => 50,
+        CardVariant::WildDrawColor => 60,
+        CardVariant::Custom(id) => super::effects::effect(id).map_or(0, |effect| effect.points()),
+    }
+}
+
+pub fn hand_points(hand: &[CardInfo]) -> u32 {
+    hand.iter().copied().map(card_points).sum()
+}
+
+impl GameState {
+    /// Seat that reached [`super::HouseRules::target_score`], the match ends with its round
+    pub fn match_winner(&self) -> Option<Seat> {
+        self.winner
+            .filter(|winner| self.scores[*winner] >= self.rules.target_score)
+    }
+
+    /// Saves from before scores were kept have none, every seat starts the match at 0
+    pub fn fill_scores(&mut self) {
+        if self.scores.is_empty() {
+            self.scores = vec![0; self.players()];
+        }
+    }
+
+    pub fn is_match_over(&self) -> bool {
+        self.match_winner().is_some()
+    }
+
+    /// The winner of the round scores every card left in the other hands
+    pub(super) fn score_round(&mut self, winner: Seat, events: &mut Vec<GameEvent>) {
+        let points = self.hands.iter().map(|hand| hand_points(hand)).sum();
+
+        self.scores[winner] += points;
+        events.push(GameEvent::Scored {
+            seat: winner,
+            points,
+        });
+    }
+
+    /// ## Deals the next round of the match
+    ///
+    /// Every card is gathered, turned back to its light side and shuffled again, scores are kept
+    /// and the first turn moves to the next seat each round.
+    pub(super) fn next_round(&mut self, events: &mut Vec<GameEvent>) {
+        let mut cards = std::mem::take(&mut self.draw_pile);
+        cards.append(&mut self.discard_pile);
+        self.hands.iter_mut().for_each(|hand| cards.append(hand));
+        if self.side == Side::Dark {
+            cards
+                .iter_mut()
+                .for_each(|card| *card = flip::flipped(*card));
+        }
+
+        // Reshuffles only use the low bits of the seed offset, so rounds never share their shuffle
+        let round = self.round + 1;
+        let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(round << 32));
+        cards.shuffle(&mut rng);
+
+        let players = self.players();
+        let dealt = GameState::with_rules(cards, players, self.seed, self.rules);
+        *self = GameState {
+            current: round as usize % players,
+            direction: Direction::default(),
+            scores: std::mem::take(&mut self.scores),
+            round,
+            reshuffles: self.reshuffles,
+            ..dealt
+        };
+        events.push(GameEvent::Dealt { round });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::{Deck, DeckRecipe},
+            rules::{Action, HouseRules, RuleError, PLAYABLE_COLORS},
+        },
+        CardColor,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_BLOCK: CardInfo = (CardColor::Red, CardVariant::Block);
+    const PLUS_FOUR: CardInfo = (CardColor::Wild, CardVariant::PlusFour);
+
+    /// Seat 0 wins the round by playing its last card
+    fn winning_state(target_score: u32) -> GameState {
+        let mut state = GameState::with_rules(
+            Deck::default().into_cards(),
+            3,
+            0,
+            HouseRules {
+                target_score,
+                ..HouseRules::default()
+            },
+        );
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![vec![RED_5], vec![RED_BLOCK, RED_5], vec![PLUS_FOUR]];
+        state
+    }
+
+    mod card_points {
+        use super::*;
+
+        #[test]
+        fn official_values() {
+            assert_eq!(card_points(RED_5), 5);
+            assert_eq!(card_points(RED_BLOCK), 20);
+            assert_eq!(card_points((CardColor::Blue, CardVariant::Invert)), 20);
+            assert_eq!(card_points((CardColor::Green, CardVariant::PlusTwo)), 20);
+            assert_eq!(card_points((CardColor::Wild, CardVariant::Wild)), 50);
+            assert_eq!(card_points(PLUS_FOUR), 50);
+        }
+    }
+
+    mod score_round {
+        use super::*;
+
+        #[test]
+        fn winner_scores_other_hands() {
+            let mut state = winning_state(TARGET_SCORE);
+
+            let events = state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert!(events.contains(&GameEvent::Scored {
+                seat: 0,
+                points: 75
+            }));
+            assert_eq!(state.scores, vec![75, 0, 0]);
+            assert!(!state.is_match_over());
+        }
+
+        #[test]
+        fn target_ends_the_match() {
+            let mut state = winning_state(50);
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            assert_eq!(state.match_winner(), Some(0));
+            assert_eq!(state.legal_actions(1), Vec::new());
+            assert_eq!(state.apply(1, Action::NextRound), Err(RuleError::GameOver));
+        }
+    }
+
+    mod next_round {
+        use super::*;
+
+        #[test]
+        fn deals_again_keeping_scores() {
+            let mut state = winning_state(TARGET_SCORE);
+            let cards = state.draw_pile.len()
+                + state.discard_pile.len()
+                + state.hands.iter().map(Vec::len).sum::<usize>();
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+
+            // Any seat can deal the next round once it is over
+            let events = state.apply(2, Action::NextRound).unwrap();
+
+            let dealt = state.draw_pile.len()
+                + state.discard_pile.len()
+                + state.hands.iter().map(Vec::len).sum::<usize>();
+            assert_eq!(events, vec![GameEvent::Dealt { round: 1 }]);
+            assert_eq!(dealt, cards);
+            assert_eq!(state.scores, vec![75, 0, 0]);
+            assert_eq!(state.winner, None);
+            assert_eq!(state.current, 1);
+            assert!(state.hands.iter().all(|hand| hand.len() == 7));
+        }
+
+        #[test]
+        fn back_to_the_light_side() {
+            let mut state = GameState::with_rules(
+                Deck::from_recipe(DeckRecipe::Flip, 0).into_cards(),
+                3,
+                0,
+                HouseRules::default(),
+            );
+            state.discard_pile.push(RED_5);
+            state.active_color = CardColor::Red;
+            state.hands[0] = vec![(CardColor::Red, CardVariant::Flip)];
+            state.current = 0;
+
+            state
+                .apply(
+                    0,
+                    Action::Play {
+                        card: 0,
+                        color: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(state.side, Side::Dark);
+            state.apply(1, Action::NextRound).unwrap();
+
+            let light =
+                |(color, _): CardInfo| color == CardColor::Wild || PLAYABLE_COLORS.contains(&color);
+            assert_eq!(state.side, Side::Light);
+            assert!(PLAYABLE_COLORS.contains(&state.active_color));
+            assert!(state.hands.iter().flatten().all(|card| light(*card)));
+            assert!(state.draw_pile.iter().all(|card| light(*card)));
+        }
+
+        #[test]
+        fn only_after_the_round() {
+            let mut state = winning_state(TARGET_SCORE);
+
+            assert!(state.apply(0, Action::NextRound).is_err());
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::{GameEvent, GameState, RuleError, Seat};
+
+impl GameState {
+    /// Seats `seat` can swap hands with after playing a 7
+    pub fn swap_targets(&self, seat: Seat) -> Vec<Seat> {
+        if self.swapping != Some(seat) {
+            return Vec::new();
+        }
+
+        (0..self.players())
+            .filter(|target| *target != seat)
+            .collect()
+    }
+
+    /// The player of a 7 gives its hand to `target` and takes the one of `target`, then the turn passes
+    pub(super) fn swap_hands(
+        &mut self,
+        seat: Seat,
+        target: Seat,
+    ) -> Result<Vec<GameEvent>, RuleError> {
+        if self.swapping != Some(seat) {
+            return Err(RuleError::NothingToSwap);
+        }
+        if !self.swap_targets(seat).contains(&target) {
+            return Err(RuleError::InvalidTarget(target));
+        }
+
+        self.swapping = None;
+        self.hands.swap(seat, target);
+        self.forget_moved_uno();
+        self.current = self.next_seat();
+
+        Ok(vec![GameEvent::HandsSwapped { seat, target }])
+    }
+
+    /// Every hand moves to the next seat in the play direction
+    pub(super) fn rotate_hands(&mut self, events: &mut Vec<GameEvent>) {
+        let targets = (0..self.players())
+            .map(|seat| self.seat_after(seat, 1))
+            .collect::<Vec<_>>();
+        let mut hands = vec![Vec::new(); self.players()];
+        for (seat, hand) in std::mem::take(&mut self.hands).into_iter().enumerate() {
+            hands[targets[seat]] = hand;
+        }
+
+        self.hands = hands;
+        self.forget_moved_uno();
+        events.push(GameEvent::HandsRotated {
+            direction: self.direction,
+        });
+    }
+
+    /// A forgotten uno can only be caught while its seat still holds the single card
+    fn forget_moved_uno(&mut self) {
+        self.forgot_uno = self.forgot_uno.filter(|seat| self.hands[*seat].len() == 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::CardInfo,
+            rules::{Action, Direction, HouseRules},
+        },
+        CardColor, CardVariant,
+    };
+
+    const RED_0: CardInfo = (CardColor::Red, CardVariant::Number(0));
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_7: CardInfo = (CardColor::Red, CardVariant::Number(7));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+    const GREEN_2: CardInfo = (CardColor::Green, CardVariant::Number(2));
+
+    const PLAY_FIRST: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+
+    /// Seat 0 plays `card` on a red 5 with seven-o rules
+    fn state(card: CardInfo) -> GameState {
+        let mut state = GameState::with_rules(
+            crate::features::deck::Deck::default().into_cards(),
+            3,
+            0,
+            HouseRules {
+                seven_o: true,
+                ..HouseRules::default()
+            },
+        );
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![
+            vec![card, RED_5, RED_5],
+            vec![BLUE_7],
+            vec![GREEN_2, GREEN_2],
+        ];
+        state
+    }
+
+    mod swap_hands {
+        use super::*;
+
+        #[test]
+        fn seven_picks_a_target() {
+            let mut state = state(RED_7);
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.current, 0);
+            assert_eq!(
+                state.legal_actions(0),
+                vec![
+                    Action::SwapHands { target: 1 },
+                    Action::SwapHands { target: 2 }
+                ]
+            );
+            assert_eq!(state.apply(0, Action::Draw), Err(RuleError::SwapPending));
+
+            let events = state.apply(0, Action::SwapHands { target: 1 }).unwrap();
+
+            assert_eq!(events, vec![GameEvent::HandsSwapped { seat: 0, target: 1 }]);
+            assert_eq!(state.hands[0], vec![BLUE_7]);
+            assert_eq!(state.hands[1], vec![RED_5, RED_5]);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn never_with_itself() {
+            let mut state = state(RED_7);
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(
+                state.apply(0, Action::SwapHands { target: 0 }),
+                Err(RuleError::InvalidTarget(0))
+            );
+            assert_eq!(
+                state.apply(1, Action::SwapHands { target: 0 }),
+                Err(RuleError::NothingToSwap)
+            );
+        }
+
+        #[test]
+        fn plain_seven_without_the_rule() {
+            let mut state = state(RED_7);
+            state.rules.seven_o = false;
+
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.swapping, None);
+            assert_eq!(state.current, 1);
+        }
+    }
+
+    mod rotate_hands {
+        use super::*;
+
+        #[test]
+        fn zero_follows_the_direction() {
+            let mut state = state(RED_0);
+            state.direction = Direction::CounterClockwise;
+            state.current = 0;
+
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.hands[2], vec![RED_5, RED_5]);
+            assert_eq!(state.hands[1], vec![GREEN_2, GREEN_2]);
+            assert_eq!(state.hands[0], vec![BLUE_7]);
+            assert_eq!(state.current, 2);
+        }
+    }
+}
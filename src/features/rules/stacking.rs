@@ -0,0 +1,225 @@
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use super::{GameEvent, GameState, Seat};
+use crate::CardVariant;
+
+/// ## House rule answering +2 and +4 cards with another one
+///
+/// Stacked penalties add up on the [`GameState::draw_stack`] until a player can't
+/// or won't stack and draws all of them. A stacked +4 can't be challenged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Stacking {
+    #[default]
+    Off,
+    /// A +2 answers a +2 and a +4 answers a +4
+    SameType,
+    /// Any +2 or +4 answers either
+    Mixed,
+}
+
+impl Stacking {
+    /// Whether a `card` variant can be stacked on a `top` draw card
+    pub fn can_stack(self, top: CardVariant, card: CardVariant) -> bool {
+        match self {
+            Stacking::Off => false,
+            Stacking::SameType => top == card && draw_penalty(card).is_some(),
+            Stacking::Mixed => draw_penalty(card).is_some(),
+        }
+    }
+}
+
+impl FromStr for Stacking {
+    type Err = String;
+
+    fn from_str(stacking: &str) -> Result<Self, Self::Err> {
+        match stacking.to_lowercase().as_str() {
+            "off" => Ok(Stacking::Off),
+            "same" => Ok(Stacking::SameType),
+            "mixed" => Ok(Stacking::Mixed),
+            _ => Err(format!("unknown stacking {:?}", stacking)),
+        }
+    }
+}
+
+impl From<Stacking> for String {
+    fn from(stacking: Stacking) -> Self {
+        match stacking {
+            Stacking::Off => "off".to_string(),
+            Stacking::SameType => "same type".to_string(),
+            Stacking::Mixed => "mixed".to_string(),
+        }
+    }
+}
+
+/// Cards the next player draws for a card `variant`, a wild draw color has no fixed count
+pub fn draw_penalty(variant: CardVariant) -> Option<usize> {
+    match variant {
+        CardVariant::PlusTwo => Some(2),
+        CardVariant::PlusFour => Some(4),
+        CardVariant::DrawFive => Some(5),
+        _ => None,
+    }
+}
+
+impl GameState {
+    /// Adds the `penalty` of the card `seat` just played to the stack, the last card of
+    /// a round makes the next player draw the whole stack right away
+    pub(super) fn stack_draw(&mut self, seat: Seat, penalty: usize, events: &mut Vec<GameEvent>) {
+        self.draw_stack += penalty;
+        if self.hands[seat].is_empty() {
+            let count = std::mem::take(&mut self.draw_stack);
+            self.draw_cards(self.next_seat(), count, events);
+            self.skip_next(events);
+        } else {
+            self.current = self.next_seat();
+        }
+    }
+
+    /// The current player draws the whole stack instead of stacking on it, and loses its turn
+    pub(super) fn draw_stacked(&mut self) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+
+        let count = std::mem::take(&mut self.draw_stack);
+        self.draw_cards(self.current, count, &mut events);
+        self.skip_current(&mut events);
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{
+            deck::CardInfo,
+            rules::{Action, HouseRules},
+        },
+        CardColor,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const RED_PLUS_TWO: CardInfo = (CardColor::Red, CardVariant::PlusTwo);
+    const BLUE_PLUS_TWO: CardInfo = (CardColor::Blue, CardVariant::PlusTwo);
+    const PLUS_FOUR: CardInfo = (CardColor::Wild, CardVariant::PlusFour);
+
+    const PLAY_FIRST: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+    const PLAY_FIRST_WILD: Action = Action::Play {
+        card: 0,
+        color: Some(CardColor::Green),
+    };
+
+    /// Seat 0 plays on a red 5 with the given `stacking`
+    fn state(stacking: Stacking, hands: Vec<Vec<CardInfo>>) -> GameState {
+        let mut state = GameState::with_rules(
+            crate::features::deck::Deck::default().into_cards(),
+            hands.len(),
+            0,
+            HouseRules {
+                stacking,
+                ..HouseRules::default()
+            },
+        );
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = hands;
+        state
+    }
+
+    mod can_stack {
+        use super::*;
+
+        #[test]
+        fn variants() {
+            use CardVariant::{Number, PlusFour, PlusTwo};
+
+            assert!(!Stacking::Off.can_stack(PlusTwo, PlusTwo));
+            assert!(Stacking::SameType.can_stack(PlusTwo, PlusTwo));
+            assert!(!Stacking::SameType.can_stack(PlusTwo, PlusFour));
+            assert!(Stacking::Mixed.can_stack(PlusFour, PlusTwo));
+            assert!(!Stacking::Mixed.can_stack(PlusTwo, Number(2)));
+        }
+    }
+
+    mod stack_draw {
+        use super::*;
+
+        #[test]
+        fn penalties_add_up() {
+            let mut state = state(
+                Stacking::SameType,
+                vec![
+                    vec![RED_PLUS_TWO, RED_5],
+                    vec![BLUE_PLUS_TWO, RED_5],
+                    vec![RED_5; 2],
+                ],
+            );
+
+            state.apply(0, PLAY_FIRST).unwrap();
+            state.apply(1, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.draw_stack, 4);
+            assert_eq!(state.current, 2);
+            assert_eq!(state.playable_cards(2), Vec::<usize>::new());
+        }
+
+        #[test]
+        fn same_type_refuses_plus_four() {
+            let mut state = state(
+                Stacking::SameType,
+                vec![vec![RED_PLUS_TWO, RED_5], vec![PLUS_FOUR, RED_5]],
+            );
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert!(state.apply(1, PLAY_FIRST_WILD).is_err());
+        }
+
+        #[test]
+        fn mixed_stacks_plus_four_on_plus_two() {
+            let mut state = state(
+                Stacking::Mixed,
+                vec![vec![RED_PLUS_TWO, RED_5], vec![PLUS_FOUR, RED_5]],
+            );
+            state.apply(0, PLAY_FIRST).unwrap();
+            state.apply(1, PLAY_FIRST_WILD).unwrap();
+
+            assert_eq!(state.draw_stack, 6);
+            assert_eq!(state.plus_four, None);
+        }
+
+        #[test]
+        fn last_card_passes_the_stack_on() {
+            let mut state = state(Stacking::Mixed, vec![vec![RED_PLUS_TWO], vec![RED_5; 3]]);
+            state.draw_stack = 4;
+
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            assert_eq!(state.hands[1].len(), 3 + 6);
+            assert_eq!(state.draw_stack, 0);
+        }
+    }
+
+    mod draw_stacked {
+        use super::*;
+
+        #[test]
+        fn draws_everything_and_passes() {
+            let mut state = state(
+                Stacking::SameType,
+                vec![vec![RED_PLUS_TWO, RED_5], vec![RED_5; 2], vec![RED_5; 2]],
+            );
+            state.apply(0, PLAY_FIRST).unwrap();
+
+            let events = state.apply(1, Action::Draw).unwrap();
+
+            assert!(events.contains(&GameEvent::Drew { seat: 1, count: 2 }));
+            assert_eq!(state.draw_stack, 0);
+            assert_eq!(state.current, 2);
+        }
+    }
+}
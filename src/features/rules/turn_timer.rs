@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+
+use super::{HouseRules, Seat};
+
+/// Turns in a row a player can run out of time on before a bot takes its seat for good
+pub const TIMEOUTS_BEFORE_BOT: u32 = 3;
+
+impl HouseRules {
+    /// Time each player has for its turn, `None` when turns are not timed
+    pub fn turn_duration(&self) -> Option<Duration> {
+        self.turn_time
+            .map(|seconds| Duration::from_secs(seconds.into()))
+    }
+}
+
+/// ## Turns each seat ran out of time on in a row
+///
+/// With [`HouseRules::turn_time`], a bot plays the turn of a player who ran out of time.
+/// Ending a turn in time starts the count over.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timeouts(Vec<u32>);
+
+impl Timeouts {
+    /// Counts a turn `seat` ran out of time on, returns whether the seat should be given to a bot
+    pub fn timed_out(&mut self, seat: Seat) -> bool {
+        if self.0.len() <= seat {
+            self.0.resize(seat + 1, 0);
+        }
+        self.0[seat] += 1;
+
+        self.0[seat] >= TIMEOUTS_BEFORE_BOT
+    }
+
+    /// Turns in a row `seat` ran out of time on
+    pub fn count(&self, seat: Seat) -> u32 {
+        self.0.get(seat).copied().unwrap_or_default()
+    }
+
+    /// `seat` ended its turn in time
+    pub fn acted(&mut self, seat: Seat) {
+        if let Some(timeouts) = self.0.get_mut(seat) {
+            *timeouts = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod timed_out {
+        use super::*;
+
+        #[test]
+        fn only_in_a_row() {
+            let mut timeouts = Timeouts::default();
+
+            assert!(!timeouts.timed_out(2));
+            assert!(!timeouts.timed_out(2));
+            timeouts.acted(2);
+            assert!(!timeouts.timed_out(2));
+            assert!(!timeouts.timed_out(0));
+            assert!(!timeouts.timed_out(2));
+            assert!(timeouts.timed_out(2));
+            assert_eq!(timeouts.count(2), 3);
+            assert_eq!(timeouts.count(5), 0);
+        }
+    }
+}
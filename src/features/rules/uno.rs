@@ -0,0 +1,174 @@
+use super::{GameEvent, GameState, RuleError, Seat};
+
+/// Cards drawn by a player caught without calling uno
+pub const UNO_PENALTY: usize = 2;
+
+impl GameState {
+    /// ## Whether `seat` may call uno now
+    ///
+    /// The current player calls right before playing its second to last card, once any +4 played
+    /// on it is answered. A player who forgot
+    /// can still call until someone catches it or the next player acts.
+    pub fn can_call_uno(&self, seat: Seat) -> bool {
+        if self.forgot_uno == Some(seat) {
+            return true;
+        }
+
+        !self.is_over()
+            && self.plus_four.is_none()
+            && seat == self.current
+            && self.hands[seat].len() == 2
+            && self.uno_called != Some(seat)
+    }
+
+    /// Seat any opponent can catch for not calling uno
+    pub fn catchable(&self) -> Option<Seat> {
+        self.forgot_uno
+    }
+
+    pub(super) fn call_uno(&mut self, seat: Seat) -> Result<Vec<GameEvent>, RuleError> {
+        if !self.can_call_uno(seat) {
+            return Err(RuleError::UnoNotAllowed);
+        }
+
+        if self.forgot_uno == Some(seat) {
+            self.forgot_uno = None;
+        } else {
+            self.uno_called = Some(seat);
+        }
+        Ok(vec![GameEvent::UnoCalled { seat }])
+    }
+
+    pub(super) fn catch_uno(
+        &mut self,
+        seat: Seat,
+        target: Seat,
+    ) -> Result<Vec<GameEvent>, RuleError> {
+        if seat == target || self.forgot_uno != Some(target) {
+            return Err(RuleError::NothingToCatch(target));
+        }
+
+        self.forgot_uno = None;
+        let mut events = vec![GameEvent::UnoCaught {
+            seat: target,
+            by: seat,
+        }];
+        self.draw_cards(target, UNO_PENALTY, &mut events);
+
+        Ok(events)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        features::{deck::CardInfo, rules::Action},
+        CardColor, CardVariant,
+    };
+
+    const RED_5: CardInfo = (CardColor::Red, CardVariant::Number(5));
+    const BLUE_7: CardInfo = (CardColor::Blue, CardVariant::Number(7));
+
+    /// Seat 0 is about to play its second to last card
+    fn state() -> GameState {
+        let mut state = GameState::from_seed(3, 0);
+        state.discard_pile = vec![RED_5];
+        state.active_color = CardColor::Red;
+        state.hands = vec![vec![RED_5, BLUE_7], vec![BLUE_7; 3], vec![BLUE_7; 3]];
+        state
+    }
+
+    const PLAY_RED_5: Action = Action::Play {
+        card: 0,
+        color: None,
+    };
+
+    mod call_uno {
+        use super::*;
+
+        #[test]
+        fn called_before_playing_is_safe() {
+            let mut state = state();
+
+            let events = state.apply(0, Action::CallUno).unwrap();
+            state.apply(0, PLAY_RED_5).unwrap();
+
+            assert_eq!(events, vec![GameEvent::UnoCalled { seat: 0 }]);
+            assert_eq!(state.catchable(), None);
+            assert_eq!(state.uno_called, None);
+        }
+
+        #[test]
+        fn only_with_two_cards() {
+            let mut state = state();
+            state.hands[0].push(BLUE_7);
+
+            assert_eq!(
+                state.apply(0, Action::CallUno),
+                Err(RuleError::UnoNotAllowed)
+            );
+            assert_eq!(
+                state.apply(1, Action::CallUno),
+                Err(RuleError::UnoNotAllowed)
+            );
+        }
+
+        #[test]
+        fn late_call_before_being_caught() {
+            let mut state = state();
+            state.apply(0, PLAY_RED_5).unwrap();
+
+            state.apply(0, Action::CallUno).unwrap();
+
+            assert_eq!(state.catchable(), None);
+        }
+    }
+
+    mod catch_uno {
+        use super::*;
+
+        #[test]
+        fn forgotten_call_draws_penalty() {
+            let mut state = state();
+            state.apply(0, PLAY_RED_5).unwrap();
+
+            let events = state.apply(2, Action::CatchUno { target: 0 }).unwrap();
+
+            assert_eq!(events[0], GameEvent::UnoCaught { seat: 0, by: 2 });
+            assert_eq!(state.hands[0].len(), 1 + UNO_PENALTY);
+            assert_eq!(state.catchable(), None);
+            assert_eq!(state.current, 1);
+        }
+
+        #[test]
+        fn window_closes_when_next_player_acts() {
+            let mut state = state();
+            state.apply(0, PLAY_RED_5).unwrap();
+            state.apply(1, Action::Draw).unwrap();
+
+            assert_eq!(
+                state.apply(2, Action::CatchUno { target: 0 }),
+                Err(RuleError::NothingToCatch(0))
+            );
+        }
+
+        #[test]
+        fn rejected_action_keeps_window_open() {
+            let mut state = state();
+            state.apply(0, PLAY_RED_5).unwrap();
+
+            assert!(state
+                .apply(
+                    1,
+                    Action::Play {
+                        card: 9,
+                        color: None
+                    }
+                )
+                .is_err());
+
+            assert_eq!(state.catchable(), Some(0));
+        }
+    }
+}
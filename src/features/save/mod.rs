@@ -0,0 +1,215 @@
+pub mod storage;
+pub mod ui;
+
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::features::{game::SeatKind, rules::GameState};
+
+/// Everything needed to continue a local game exactly where it was left
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    pub seats: Vec<SeatKind>,
+    /// Piles in order, hands, direction, chosen color and the seed of the next reshuffles
+    pub state: GameState,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaveFormat {
+    #[default]
+    Ron,
+    Json,
+}
+
+impl SaveFormat {
+    /// Format of a file from its extension, RON unless it ends with `.json`
+    pub fn from_path(path: &std::path::Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("json") => SaveFormat::Json,
+            _ => SaveFormat::Ron,
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            SaveFormat::Ron => "ron",
+            SaveFormat::Json => "json",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveError {
+    Encode(String),
+    Decode(String),
+    /// The save does not match its seats
+    Mismatch,
+    NoSave,
+    Storage(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Encode(error) => write!(f, "could not write the save: {}", error),
+            SaveError::Decode(error) => write!(f, "could not read the save: {}", error),
+            SaveError::Mismatch => write!(f, "the save does not have one seat per hand"),
+            SaveError::NoSave => write!(f, "there is no saved game"),
+            SaveError::Storage(error) => write!(f, "could not access the saves: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Write `value` in the given format, arrays stay on one line to keep piles readable
+pub fn encode<T: Serialize>(value: &T, format: SaveFormat) -> Result<String, SaveError> {
+    match format {
+        SaveFormat::Ron => {
+            let config = ron::ser::PrettyConfig::default().compact_arrays(true);
+            ron::ser::to_string_pretty(value, config)
+                .map_err(|error| SaveError::Encode(error.to_string()))
+        }
+        SaveFormat::Json => serde_json::to_string_pretty(value)
+            .map_err(|error| SaveError::Encode(error.to_string())),
+    }
+}
+
+pub fn decode<T: DeserializeOwned>(text: &str, format: SaveFormat) -> Result<T, SaveError> {
+    match format {
+        SaveFormat::Ron => {
+            ron::from_str(text).map_err(|error| SaveError::Decode(error.to_string()))
+        }
+        SaveFormat::Json => {
+            serde_json::from_str(text).map_err(|error| SaveError::Decode(error.to_string()))
+        }
+    }
+}
+
+impl SavedGame {
+    pub fn encode(&self, format: SaveFormat) -> Result<String, SaveError> {
+        encode(self, format)
+    }
+
+    pub fn decode(text: &str, format: SaveFormat) -> Result<Self, SaveError> {
+        let mut saved: Self = decode(text, format)?;
+        saved.state.fill_scores();
+
+        if saved.seats.len() != saved.state.players() {
+            return Err(SaveError::Mismatch);
+        }
+        Ok(saved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::features::ai::{self, Difficulty, SearchBudget};
+
+    fn saved_game() -> SavedGame {
+        SavedGame {
+            seats: vec![
+                SeatKind::Human,
+                SeatKind::Bot(Difficulty::Easy),
+                SeatKind::Bot(Difficulty::Normal),
+            ],
+            state: GameState::from_seed(3, 11),
+        }
+    }
+
+    /// Let easy bots play `turns` turns, the same `rng` seed always plays the same actions
+    fn play(state: &mut GameState, turns: usize, seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let budget = SearchBudget::default();
+
+        for _ in 0..turns {
+            if state.is_over() {
+                break;
+            }
+            let seat = state.current;
+            let action = ai::choose_action(Difficulty::Easy, state, seat, &budget, &mut rng);
+            state.apply(seat, action).unwrap();
+        }
+    }
+
+    mod decode {
+        use super::*;
+
+        #[test]
+        fn round_trip() {
+            let mut saved = saved_game();
+            play(&mut saved.state, 40, 1);
+
+            for format in [SaveFormat::Ron, SaveFormat::Json] {
+                let text = saved.encode(format).unwrap();
+
+                assert_eq!(SavedGame::decode(&text, format), Ok(saved.clone()));
+            }
+        }
+
+        #[test]
+        fn loaded_game_continues_identically() {
+            let mut saved = saved_game();
+            play(&mut saved.state, 10, 2);
+            // Almost empty the draw pile so the discard pile gets shuffled back soon after loading
+            let buried = saved.state.draw_pile.len() - 2;
+            let cards = saved.state.draw_pile.drain(..buried).collect::<Vec<_>>();
+            saved.state.discard_pile.splice(0..0, cards);
+
+            for format in [SaveFormat::Ron, SaveFormat::Json] {
+                let text = saved.encode(format).unwrap();
+                let mut original = saved.state.clone();
+                let mut loaded = SavedGame::decode(&text, format).unwrap().state;
+
+                play(&mut original, 100, 3);
+                play(&mut loaded, 100, 3);
+
+                assert!(original.reshuffles > 0);
+                assert_eq!(loaded, original);
+            }
+        }
+
+        #[test]
+        fn seats_must_match_hands() {
+            let mut saved = saved_game();
+            saved.seats.pop();
+            let text = saved.encode(SaveFormat::Json).unwrap();
+
+            assert_eq!(
+                SavedGame::decode(&text, SaveFormat::Json),
+                Err(SaveError::Mismatch)
+            );
+        }
+
+        #[test]
+        fn saves_without_scores() {
+            let saved = saved_game();
+            let text = saved.encode(SaveFormat::Json).unwrap();
+            let mut value: serde_json::Value = serde_json::from_str(&text).unwrap();
+            let state = value["state"].as_object_mut().unwrap();
+            state.remove("scores");
+            state.remove("round");
+            state["rules"]
+                .as_object_mut()
+                .unwrap()
+                .remove("target_score");
+
+            assert_eq!(
+                SavedGame::decode(&value.to_string(), SaveFormat::Json),
+                Ok(saved)
+            );
+        }
+
+        #[test]
+        fn garbage_rejected() {
+            assert!(matches!(
+                SavedGame::decode("not a save", SaveFormat::Ron),
+                Err(SaveError::Decode(_))
+            ));
+        }
+    }
+}
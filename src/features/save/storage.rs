@@ -0,0 +1,76 @@
+use super::{SaveError, SaveFormat, SavedGame};
+
+/// Format of everything written by [`write`]
+pub const STORAGE_FORMAT: SaveFormat = SaveFormat::Ron;
+const SAVE_NAME: &str = "save";
+
+/// Replace the saved game with `saved`, it goes to a file in the platform data directory
+/// on native builds and to the browser local storage on wasm
+pub fn store(saved: &SavedGame) -> Result<(), SaveError> {
+    write(SAVE_NAME, &saved.encode(STORAGE_FORMAT)?)
+}
+
+/// Read back the game written by [`store`]
+pub fn load() -> Result<SavedGame, SaveError> {
+    SavedGame::decode(&read(SAVE_NAME)?, STORAGE_FORMAT)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn save_path(name: &str) -> Result<std::path::PathBuf, SaveError> {
+    let directory = dirs::data_dir()
+        .ok_or_else(|| SaveError::Storage("no data directory on this platform".to_string()))?;
+
+    Ok(directory
+        .join(env!("CARGO_PKG_NAME"))
+        .join(name)
+        .with_extension(STORAGE_FORMAT.extension()))
+}
+
+/// Replace what is stored under `name` with `text`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(name: &str, text: &str) -> Result<(), SaveError> {
+    let path = save_path(name)?;
+    let storage_error = |error: std::io::Error| SaveError::Storage(error.to_string());
+
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory).map_err(storage_error)?;
+    }
+    std::fs::write(path, text).map_err(storage_error)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(name: &str) -> Result<String, SaveError> {
+    match std::fs::read_to_string(save_path(name)?) {
+        Ok(text) => Ok(text),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Err(SaveError::NoSave),
+        Err(error) => Err(SaveError::Storage(error.to_string())),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, SaveError> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| SaveError::Storage("local storage is not available".to_string()))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn storage_key(name: &str) -> String {
+    format!("{}/{}", env!("CARGO_PKG_NAME"), name)
+}
+
+/// Replace what is stored under `name` with `text`
+#[cfg(target_arch = "wasm32")]
+pub fn write(name: &str, text: &str) -> Result<(), SaveError> {
+    local_storage()?
+        .set_item(&storage_key(name), text)
+        .map_err(|error| SaveError::Storage(format!("{:?}", error)))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(name: &str) -> Result<String, SaveError> {
+    local_storage()?
+        .get_item(&storage_key(name))
+        .map_err(|error| SaveError::Storage(format!("{:?}", error)))?
+        .ok_or(SaveError::NoSave)
+}
@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+
+use super::{storage, SavedGame};
+use crate::features::{
+    game::{hot_seat::HotSeat, seat_players, AppState, ApplyRequestsSet, Player, SeatKind, Table},
+    replay::{Recording, Replay},
+};
+
+const BUTTON_COLOR: Color = Color::DARK_GRAY;
+
+/// Outcome of the last save or load, shown under the menu
+#[derive(Resource, Debug, Clone, Default)]
+pub struct SaveNotice(pub Option<String>);
+
+#[derive(Component)]
+struct SaveMenuMarker;
+
+#[derive(Component)]
+struct SaveNoticeText;
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+enum SaveButton {
+    Save,
+    Load,
+}
+
+/// Buttons to save the local game and load it back later, online games are kept by the server
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SaveNotice>()
+            .add_systems(
+                OnEnter(AppState::Playing),
+                spawn_save_menu.run_if(resource_exists::<Table>),
+            )
+            .add_systems(
+                Update,
+                (
+                    press_save_buttons.before(ApplyRequestsSet),
+                    show_notice.run_if(resource_changed::<SaveNotice>),
+                )
+                    .run_if(in_state(AppState::Playing).and_then(resource_exists::<Table>)),
+            );
+    }
+}
+
+fn spawn_save_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(20.),
+                    top: Val::Px(20.),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::End,
+                    row_gap: Val::Px(10.),
+                    ..default()
+                },
+                ..default()
+            },
+            SaveMenuMarker,
+        ))
+        .with_children(|builder| {
+            for (button, label) in [(SaveButton::Save, "Save"), (SaveButton::Load, "Load")] {
+                builder
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(5.)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(BUTTON_COLOR),
+                            ..default()
+                        },
+                        button,
+                    ))
+                    .with_children(|builder| {
+                        builder.spawn(TextBundle::from_section(label, TextStyle::default()));
+                    });
+            }
+            builder.spawn((
+                TextBundle::from_section("", TextStyle::default()),
+                SaveNoticeText,
+            ));
+        });
+}
+
+/// The game as it is on the [`Table`], with who sits on each seat
+fn saved_game(table: &Table, players_query: &Query<(Entity, &Player, &SeatKind)>) -> SavedGame {
+    let mut seats = players_query
+        .iter()
+        .map(|(_, player, kind)| (player.seat, *kind))
+        .collect::<Vec<_>>();
+    seats.sort_by_key(|(seat, _)| *seat);
+
+    SavedGame {
+        seats: seats.into_iter().map(|(_, kind)| kind).collect(),
+        state: table.0.clone(),
+    }
+}
+
+/// Replace the game being played with `saved`, players are seated again from scratch
+/// and the replay starts over from the loaded game
+fn restore_game(
+    commands: &mut Commands,
+    saved: SavedGame,
+    players_query: &Query<(Entity, &Player, &SeatKind)>,
+) {
+    players_query
+        .iter()
+        .for_each(|(entity, _, _)| commands.entity(entity).despawn_recursive());
+    commands.remove_resource::<HotSeat>();
+
+    commands.insert_resource(Recording(Replay::new(
+        saved.seats.clone(),
+        saved.state.clone(),
+    )));
+    commands.insert_resource(Table(saved.state));
+    seat_players(commands, &saved.seats);
+}
+
+fn press_save_buttons(
+    mut commands: Commands,
+    table: Res<Table>,
+    mut notice: ResMut<SaveNotice>,
+    buttons_query: Query<(&Interaction, &SaveButton), Changed<Interaction>>,
+    players_query: Query<(Entity, &Player, &SeatKind)>,
+) {
+    for (_, button) in buttons_query
+        .iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+    {
+        let result = match button {
+            SaveButton::Save => storage::store(&saved_game(&table, &players_query))
+                .map(|_| "Game saved".to_string()),
+            SaveButton::Load => storage::load().map(|saved| {
+                restore_game(&mut commands, saved, &players_query);
+                "Game loaded".to_string()
+            }),
+        };
+
+        notice.0 = Some(result.unwrap_or_else(|error| {
+            warn!("{:?} failed: {}", button, error);
+            format!("{}", error)
+        }));
+    }
+}
+
+fn show_notice(notice: Res<SaveNotice>, mut text_query: Query<&mut Text, With<SaveNoticeText>>) {
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = notice.0.clone().unwrap_or_default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{
+        ai::Difficulty,
+        game::LocalPlayer,
+        rules::{Action, GameState},
+    };
+
+    mod restore_game {
+        use super::*;
+
+        #[test]
+        fn seats_players_again() {
+            let mut app = App::new();
+            let saved = SavedGame {
+                seats: vec![SeatKind::Bot(Difficulty::Easy), SeatKind::Human],
+                state: {
+                    let mut state = GameState::from_seed(2, 4);
+                    state.apply(0, Action::Draw).unwrap();
+                    state
+                },
+            };
+
+            app.insert_resource(Table(GameState::from_seed(3, 1)))
+                .insert_resource(HotSeat::new(vec![0, 1]))
+                .add_systems(
+                    Update,
+                    move |mut commands: Commands,
+                          players_query: Query<(Entity, &Player, &SeatKind)>| {
+                        restore_game(&mut commands, saved.clone(), &players_query);
+                    },
+                );
+            for seat in 0..3 {
+                app.world.spawn((Player { seat }, SeatKind::Human));
+            }
+
+            app.update();
+
+            let local = app
+                .world
+                .query_filtered::<&Player, With<LocalPlayer>>()
+                .single(&app.world)
+                .seat;
+            let players = app.world.query::<&Player>().iter(&app.world).count();
+
+            assert_eq!(players, 2);
+            assert_eq!(local, 1);
+            assert!(app.world.get_resource::<HotSeat>().is_none());
+            assert_eq!(app.world.resource::<Table>().0.current, 1);
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    game::layout::DESIGN_SIZE,
+    save::{decode, encode, storage, SaveError},
+};
+
+const SETTINGS_NAME: &str = "settings";
+const FULLSCREEN_KEY: KeyCode = KeyCode::F11;
+const BUTTON_COLOR: Color = Color::DARK_GRAY;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum DisplayMode {
+    Windowed,
+    #[default]
+    Fullscreen,
+}
+
+impl DisplayMode {
+    pub fn toggled(self) -> Self {
+        match self {
+            DisplayMode::Windowed => DisplayMode::Fullscreen,
+            DisplayMode::Fullscreen => DisplayMode::Windowed,
+        }
+    }
+}
+
+impl From<DisplayMode> for WindowMode {
+    fn from(mode: DisplayMode) -> Self {
+        match mode {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Fullscreen => WindowMode::SizedFullscreen,
+        }
+    }
+}
+
+impl From<DisplayMode> for String {
+    fn from(mode: DisplayMode) -> Self {
+        match mode {
+            DisplayMode::Windowed => "windowed",
+            DisplayMode::Fullscreen => "fullscreen",
+        }
+        .to_string()
+    }
+}
+
+/// Display preferences of the player, kept next to the saved game between launches
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DisplaySettings {
+    pub mode: DisplayMode,
+}
+
+impl DisplaySettings {
+    /// Settings stored by the last launch, the default ones the first time
+    pub fn load() -> Self {
+        match storage::read(SETTINGS_NAME).and_then(|text| decode(&text, storage::STORAGE_FORMAT)) {
+            Ok(settings) => settings,
+            Err(SaveError::NoSave) => Self::default(),
+            Err(error) => {
+                warn!("Default settings used: {}", error);
+                Self::default()
+            }
+        }
+    }
+
+    pub fn store(&self) -> Result<(), SaveError> {
+        storage::write(SETTINGS_NAME, &encode(self, storage::STORAGE_FORMAT)?)
+    }
+}
+
+#[derive(Component)]
+struct DisplayModeButton;
+
+/// Switches between windowed and fullscreen with a button or F11,
+/// and scales the UI with the window like the table
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DisplaySettings>()
+            .add_systems(Startup, spawn_settings_menu)
+            .add_systems(
+                Update,
+                (
+                    (press_display_button, press_fullscreen_key),
+                    (apply_display_mode, show_display_mode, store_settings)
+                        .run_if(resource_changed::<DisplaySettings>),
+                )
+                    .chain(),
+            )
+            .add_systems(Update, scale_ui);
+    }
+}
+
+fn spawn_settings_menu(mut commands: Commands, settings: Res<DisplaySettings>) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(20.),
+                bottom: Val::Px(20.),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|builder| {
+            builder
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::all(Val::Px(5.)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(BUTTON_COLOR),
+                        ..default()
+                    },
+                    DisplayModeButton,
+                ))
+                .with_children(|builder| {
+                    builder.spawn(TextBundle::from_section(
+                        display_mode_label(settings.mode),
+                        TextStyle::default(),
+                    ));
+                });
+        });
+}
+
+fn display_mode_label(mode: DisplayMode) -> String {
+    format!("Display: {}", String::from(mode))
+}
+
+fn press_display_button(
+    buttons_query: Query<&Interaction, (Changed<Interaction>, With<DisplayModeButton>)>,
+    mut settings: ResMut<DisplaySettings>,
+) {
+    for _ in buttons_query
+        .iter()
+        .filter(|interaction| **interaction == Interaction::Pressed)
+    {
+        settings.mode = settings.mode.toggled();
+    }
+}
+
+fn press_fullscreen_key(keys: Res<ButtonInput<KeyCode>>, mut settings: ResMut<DisplaySettings>) {
+    if keys.just_pressed(FULLSCREEN_KEY) {
+        settings.mode = settings.mode.toggled();
+    }
+}
+
+fn apply_display_mode(
+    settings: Res<DisplaySettings>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if let Ok(mut window) = window_query.get_single_mut() {
+        let mode = settings.mode.into();
+        if window.mode != mode {
+            window.mode = mode;
+        }
+    }
+}
+
+fn show_display_mode(
+    settings: Res<DisplaySettings>,
+    buttons_query: Query<&Children, With<DisplayModeButton>>,
+    mut text_query: Query<&mut Text>,
+) {
+    for children in buttons_query.iter() {
+        let mut texts = text_query.iter_many_mut(children);
+        while let Some(mut text) = texts.fetch_next() {
+            *text = Text::from_section(display_mode_label(settings.mode), TextStyle::default());
+        }
+    }
+}
+
+/// Settings are only written once the player changed them
+fn store_settings(settings: Res<DisplaySettings>) {
+    if settings.is_added() {
+        return;
+    }
+    if let Err(error) = settings.store() {
+        warn!("Settings not stored: {}", error);
+    }
+}
+
+/// UI sizes are given for [`DESIGN_SIZE`], they grow and shrink with the window
+fn scale_ui(
+    window_query: Query<&Window, (With<PrimaryWindow>, Changed<Window>)>,
+    mut ui_scale: ResMut<UiScale>,
+) {
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+
+    if window_size.cmpgt(Vec2::ZERO).all() {
+        let scale = ui_scale_for(window_size);
+        if ui_scale.0 != scale {
+            ui_scale.0 = scale;
+        }
+    }
+}
+
+/// Scale of the UI in a window of `window_size`, the same as the table's
+fn ui_scale_for(window_size: Vec2) -> f32 {
+    (window_size / DESIGN_SIZE).min_element()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod press_fullscreen_key {
+        use super::*;
+
+        #[test]
+        fn toggles_the_window_mode() {
+            let mut app = App::new();
+            let mut keys = ButtonInput::<KeyCode>::default();
+            keys.press(FULLSCREEN_KEY);
+            app.insert_resource(keys)
+                .init_resource::<DisplaySettings>()
+                .add_systems(Update, (press_fullscreen_key, apply_display_mode).chain());
+            let window = app
+                .world
+                .spawn((
+                    Window {
+                        mode: WindowMode::SizedFullscreen,
+                        ..default()
+                    },
+                    PrimaryWindow,
+                ))
+                .id();
+
+            app.update();
+
+            assert_eq!(
+                app.world.resource::<DisplaySettings>().mode,
+                DisplayMode::Windowed
+            );
+            assert_eq!(
+                app.world.get::<Window>(window).unwrap().mode,
+                WindowMode::Windowed
+            );
+        }
+    }
+
+    mod ui_scale_for {
+        use super::*;
+
+        #[test]
+        fn fits_the_design_size() {
+            assert_eq!(ui_scale_for(DESIGN_SIZE), 1.);
+            assert_eq!(ui_scale_for(Vec2::new(2560., 1080.)), 1.5);
+            assert_eq!(ui_scale_for(Vec2::new(640., 720.)), 0.5);
+        }
+    }
+}
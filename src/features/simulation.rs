@@ -0,0 +1,331 @@
+use std::collections::BTreeMap;
+
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+
+use crate::{
+    features::{
+        ai::{self, Difficulty, SearchBudget},
+        deck::{Deck, DeckRecipe},
+        game::SeatKind,
+        replay::Replay,
+        rules::{GameEvent, GameState, HouseRules, Seat},
+    },
+    CardVariant,
+};
+
+/// Games still running after this many turns are stopped without a winner
+pub const MAX_TURNS: usize = 2000;
+
+/// What happened during a single bot game
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameRecord {
+    pub seed: u64,
+    pub winner: Option<Seat>,
+    pub turns: usize,
+    /// How many cards were played, by kind of card
+    pub played: BTreeMap<&'static str, usize>,
+    pub drawn: usize,
+}
+
+/// Name of the kind of card used in statistics, all numbers are grouped together
+pub fn variant_kind(variant: CardVariant) -> &'static str {
+    match variant {
+        CardVariant::Number(_) => "number",
+        CardVariant::Invert => "invert",
+        CardVariant::Block => "block",
+        CardVariant::PlusTwo => "plus_two",
+        CardVariant::PlusFour => "plus_four",
+        CardVariant::Wild => "wild",
+        CardVariant::Flip => "flip",
+        CardVariant::DrawFive => "draw_five",
+        CardVariant::SkipEveryone => "skip_everyone",
+        CardVariant::WildDrawColor => "wild_draw_color",
+        CardVariant::Custom(_) => "custom",
+    }
+}
+
+/// ## Plays a whole game between bots without any app
+///
+/// Seat `n` is played by a bot of difficulty `seats[n]`. The deck and every bot decision
+/// are seeded from `seed` so the same arguments always give the same game.
+pub fn record_game(seats: &[Difficulty], seed: u64, budget: &SearchBudget) -> Replay {
+    record_game_with(
+        seats,
+        DeckRecipe::default(),
+        HouseRules::default(),
+        seed,
+        budget,
+    )
+}
+
+/// Same as [`record_game`] but plays with the deck of `recipe` and the given house `rules`
+pub fn record_game_with(
+    seats: &[Difficulty],
+    recipe: DeckRecipe,
+    rules: HouseRules,
+    seed: u64,
+    budget: &SearchBudget,
+) -> Replay {
+    let deck = Deck::from_recipe(recipe, seed).into_cards();
+    let mut state = GameState::with_rules(deck, seats.len(), seed, rules);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut replay = Replay::new(
+        seats.iter().copied().map(SeatKind::Bot).collect(),
+        state.clone(),
+    );
+
+    while !state.is_over() && replay.steps.len() < MAX_TURNS {
+        if let Some((seat, action)) = ai::headless_jump_in(&state, |seat| Some(seats[seat])) {
+            let events = state
+                .apply(seat, action)
+                .expect("bots only jump in when allowed");
+            replay.record(seat, action, &events);
+            continue;
+        }
+
+        let seat = state.current;
+        if let Some(action) = ai::uno_action(seats[seat], &state, seat, &mut rng) {
+            let events = state
+                .apply(seat, action)
+                .expect("bots only call legal unos");
+            replay.record(seat, action, &events);
+        }
+
+        let action = ai::choose_action(seats[seat], &state, seat, budget, &mut rng);
+        let events = state
+            .apply(seat, action)
+            .expect("bots only choose legal actions");
+
+        replay.record(seat, action, &events);
+    }
+
+    replay
+}
+
+/// Plays a game with [`record_game`] and only keeps its statistics
+pub fn play_game(seats: &[Difficulty], seed: u64, budget: &SearchBudget) -> GameRecord {
+    GameRecord::from(&record_game(seats, seed, budget))
+}
+
+impl From<&Replay> for GameRecord {
+    fn from(replay: &Replay) -> Self {
+        let mut record = GameRecord {
+            seed: replay.start.seed,
+            winner: replay.winner(),
+            turns: replay.steps.len(),
+            played: BTreeMap::new(),
+            drawn: 0,
+        };
+
+        for event in replay.steps.iter().flat_map(|step| step.events.iter()) {
+            match event {
+                GameEvent::Played { card, .. } => {
+                    *record.played.entry(variant_kind(card.1)).or_default() += 1
+                }
+                GameEvent::Drew { count, .. } => record.drawn += count,
+                _ => {}
+            }
+        }
+
+        record
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeatReport {
+    pub seat: Seat,
+    pub difficulty: String,
+    pub wins: usize,
+    pub win_rate: f64,
+}
+
+/// Statistics over many [`GameRecord`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SimulationReport {
+    pub games: usize,
+    /// Games stopped after [`MAX_TURNS`] without a winner
+    pub unfinished: usize,
+    pub seats: Vec<SeatReport>,
+    pub average_turns: f64,
+    pub played: BTreeMap<&'static str, usize>,
+    pub drawn: usize,
+    #[serde(skip)]
+    total_turns: usize,
+}
+
+impl SimulationReport {
+    pub fn new(seats: &[Difficulty]) -> Self {
+        Self {
+            games: 0,
+            unfinished: 0,
+            seats: seats
+                .iter()
+                .enumerate()
+                .map(|(seat, difficulty)| SeatReport {
+                    seat,
+                    difficulty: format!("{:?}", difficulty).to_lowercase(),
+                    wins: 0,
+                    win_rate: 0.,
+                })
+                .collect(),
+            average_turns: 0.,
+            played: BTreeMap::new(),
+            drawn: 0,
+            total_turns: 0,
+        }
+    }
+
+    pub fn add(&mut self, record: &GameRecord) {
+        self.games += 1;
+        self.total_turns += record.turns;
+        self.drawn += record.drawn;
+
+        match record.winner.and_then(|winner| self.seats.get_mut(winner)) {
+            Some(seat) => seat.wins += 1,
+            None => self.unfinished += 1,
+        }
+        for (kind, count) in record.played.iter() {
+            *self.played.entry(kind).or_default() += count;
+        }
+
+        let games = self.games as f64;
+        self.average_turns = self.total_turns as f64 / games;
+        self.seats
+            .iter_mut()
+            .for_each(|seat| seat.win_rate = seat.wins as f64 / games);
+    }
+
+    /// One `metric,key,value` line per statistic, with a header
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "metric,key,value".to_string(),
+            format!("games,,{}", self.games),
+            format!("unfinished,,{}", self.unfinished),
+            format!("average_turns,,{}", self.average_turns),
+            format!("drawn,,{}", self.drawn),
+        ];
+        lines.extend(self.seats.iter().flat_map(|seat| {
+            let key = format!("seat{}_{}", seat.seat, seat.difficulty);
+            [
+                format!("wins,{},{}", key, seat.wins),
+                format!("win_rate,{},{}", key, seat.win_rate),
+            ]
+        }));
+        lines.extend(
+            self.played
+                .iter()
+                .map(|(kind, count)| format!("played,{},{}", kind, count)),
+        );
+
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BUDGET: SearchBudget = SearchBudget {
+        max_iterations: 20,
+        max_time: None,
+    };
+
+    mod play_game {
+        use super::*;
+
+        #[test]
+        fn game_has_a_winner() {
+            let record = play_game(&[Difficulty::Normal, Difficulty::Easy], 0, &BUDGET);
+
+            assert!(record.winner.is_some());
+            assert!(record.turns > 0);
+            assert!(record.played.values().sum::<usize>() > 0);
+        }
+
+        #[test]
+        fn same_seed_same_game() {
+            let seats = [Difficulty::Hard, Difficulty::Easy, Difficulty::Normal];
+
+            assert_eq!(play_game(&seats, 3, &BUDGET), play_game(&seats, 3, &BUDGET));
+        }
+    }
+
+    mod record_game {
+        use super::*;
+
+        #[test]
+        fn replay_rebuilds_the_game() {
+            let replay = record_game(&[Difficulty::Easy, Difficulty::Normal], 4, &BUDGET);
+
+            let states = replay.states().unwrap();
+
+            assert_eq!(states.len(), replay.steps.len() + 1);
+            assert_eq!(states.last().unwrap().winner, replay.winner());
+        }
+    }
+
+    mod record_game_with {
+        use super::*;
+
+        #[test]
+        fn plays_with_the_house_rules() {
+            let rules = HouseRules {
+                hand_size: 5,
+                jump_in: true,
+                ..HouseRules::default()
+            };
+            let seats = [Difficulty::Easy, Difficulty::Normal];
+
+            let replay = record_game_with(&seats, DeckRecipe::Flip, rules, 4, &BUDGET);
+
+            assert_eq!(replay.start.rules, rules);
+            assert!(replay.start.hands.iter().all(|hand| hand.len() == 5));
+            assert!(replay.states().is_ok());
+        }
+    }
+
+    mod simulation_report {
+        use super::*;
+
+        fn record(winner: Option<Seat>, turns: usize) -> GameRecord {
+            GameRecord {
+                seed: 0,
+                winner,
+                turns,
+                played: BTreeMap::from([("number", 3), ("wild", 1)]),
+                drawn: 2,
+            }
+        }
+
+        #[test]
+        fn aggregates_records() {
+            let mut report = SimulationReport::new(&[Difficulty::Easy, Difficulty::Hard]);
+
+            report.add(&record(Some(1), 10));
+            report.add(&record(Some(1), 20));
+            report.add(&record(None, 30));
+            report.add(&record(Some(0), 40));
+
+            assert_eq!(report.games, 4);
+            assert_eq!(report.unfinished, 1);
+            assert_eq!(report.average_turns, 25.);
+            assert_eq!(report.seats[0].win_rate, 0.25);
+            assert_eq!(report.seats[1].win_rate, 0.5);
+            assert_eq!(report.played.get("number"), Some(&12));
+            assert_eq!(report.drawn, 8);
+        }
+
+        #[test]
+        fn csv_lines() {
+            let mut report = SimulationReport::new(&[Difficulty::Easy, Difficulty::Hard]);
+            report.add(&record(Some(1), 10));
+
+            let csv = report.to_csv();
+
+            assert!(csv.starts_with("metric,key,value\ngames,,1\n"));
+            assert!(csv.contains("\nwin_rate,seat1_hard,1\n"));
+            assert!(csv.ends_with("played,wild,1"));
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::{
+    features::{
+        deck::CardInfo,
+        rules::{Action, GameEvent, GameState, Seat},
+    },
+    CardColor,
+};
+
+const RESET: &str = "\x1b[0m";
+
+/// ANSI escape code of the terminal color closest to `color`
+fn ansi_code(color: CardColor) -> &'static str {
+    match color {
+        CardColor::Yellow => "\x1b[33m",
+        CardColor::Red => "\x1b[31m",
+        CardColor::Blue => "\x1b[34m",
+        CardColor::Green => "\x1b[32m",
+        CardColor::Wild => "\x1b[35m",
+        CardColor::Pink => "\x1b[95m",
+        CardColor::Teal => "\x1b[36m",
+        CardColor::Orange => "\x1b[38;5;208m",
+        CardColor::Purple => "\x1b[38;5;93m",
+    }
+}
+
+/// Colored name of a card, e.g. "blue 2plus"
+pub fn card_label((color, variant): CardInfo) -> String {
+    let color_str: String = color.into();
+    let variant_str: String = variant.into();
+
+    format!("{}{} {}{}", ansi_code(color), color_str, variant_str, RESET)
+}
+
+/// Everything `seat` is allowed to see: top card, active color, hand sizes and its own hand
+pub fn render_table(state: &GameState, seat: Seat) -> String {
+    let color: String = state.active_color.into();
+    let mut lines = vec![format!(
+        "top: {} | color: {}{}{} | deck: {} cards",
+        card_label(state.top_card()),
+        ansi_code(state.active_color),
+        color,
+        RESET,
+        state.draw_pile.len()
+    )];
+
+    lines.extend(
+        state
+            .hands
+            .iter()
+            .enumerate()
+            .filter(|(other, _)| *other != seat)
+            .map(|(other, hand)| format!("player {}: {} cards", other + 1, hand.len())),
+    );
+    if let Some(revealed) = state.revealed.filter(|revealed| revealed.to == seat) {
+        let hand = state.hands[revealed.seat].iter().copied().map(card_label);
+        lines.push(format!(
+            "player {} shows: {}",
+            revealed.seat + 1,
+            hand.collect::<Vec<_>>().join(", ")
+        ));
+    }
+    if state.draw_stack > 0 {
+        lines.push(format!(
+            "+{} stacked: play a draw card or d to draw them all",
+            state.draw_stack
+        ));
+    }
+    if state.drawn.is_some() && state.current == seat {
+        lines.push(if state.rules.forced_play {
+            "you drew a playable card: play it".to_string()
+        } else {
+            "you drew a playable card: play it or p to keep it".to_string()
+        });
+    }
+    if state.swapping == Some(seat) {
+        lines.push("pick a player to swap hands with: s <player>".to_string());
+    }
+    if let Some(challenge) = state.plus_four.filter(|challenge| challenge.victim == seat) {
+        lines.push(format!(
+            "player {} played a +4 on you: c to challenge it, d to draw 4",
+            challenge.offender + 1
+        ));
+    }
+    lines.push("your hand:".to_string());
+    let playable_cards = state.playable_cards(seat);
+    lines.extend(state.hands[seat].iter().enumerate().map(|(index, card)| {
+        let playable = if playable_cards.contains(&index) {
+            "*"
+        } else {
+            " "
+        };
+        format!("{} {:>2}: {}", playable, index, card_label(*card))
+    }));
+
+    lines.join("\n")
+}
+
+/// ## Reads a move typed by the player
+///
+/// - `3` plays the card at index 3
+/// - `3 red` plays the wild card at index 3 and picks red
+/// - `d` or `draw` draws a card
+/// - `p` or `pass` keeps the playable card just drawn
+/// - `c` or `challenge` challenges a +4
+/// - `s 2` or `swap 2` swaps hands with player 2 after playing a 7
+pub fn parse_move(input: &str) -> Result<Action, String> {
+    let mut words = input.split_whitespace();
+
+    match words.next() {
+        Some("d") | Some("draw") => Ok(Action::Draw),
+        Some("p") | Some("pass") => Ok(Action::Pass),
+        Some("c") | Some("challenge") => Ok(Action::ChallengePlusFour),
+        Some("s") | Some("swap") => {
+            let player = words.next().unwrap_or_default();
+            match player.parse::<usize>() {
+                Ok(player) if player > 0 => Ok(Action::SwapHands { target: player - 1 }),
+                _ => Err(format!("{:?} is not a player number", player)),
+            }
+        }
+        Some(index) => {
+            let card = index
+                .parse()
+                .map_err(|_| format!("{:?} is not a card index", index))?;
+            let color = words.next().map(parse_color).transpose()?;
+
+            Ok(Action::Play { card, color })
+        }
+        None => Err("type a card index or d to draw".to_string()),
+    }
+}
+
+fn parse_color(color: &str) -> Result<CardColor, String> {
+    match color.to_lowercase().as_str() {
+        "yellow" | "y" => Ok(CardColor::Yellow),
+        "red" | "r" => Ok(CardColor::Red),
+        "blue" | "b" => Ok(CardColor::Blue),
+        "green" | "g" => Ok(CardColor::Green),
+        "pink" => Ok(CardColor::Pink),
+        "teal" => Ok(CardColor::Teal),
+        "orange" => Ok(CardColor::Orange),
+        "purple" => Ok(CardColor::Purple),
+        _ => Err(format!("{:?} is not a color", color)),
+    }
+}
+
+/// One line describing what happened on the table
+pub fn describe_event(event: &GameEvent) -> String {
+    match event {
+        GameEvent::Played { seat, card, color } => {
+            let color_str: String = (*color).into();
+            format!(
+                "player {} plays {} ({}{}{})",
+                seat + 1,
+                card_label(*card),
+                ansi_code(*color),
+                color_str,
+                RESET
+            )
+        }
+        GameEvent::Drew { seat, count } => format!("player {} draws {} cards", seat + 1, count),
+        GameEvent::Skipped { seat } => format!("player {} is skipped", seat + 1),
+        GameEvent::Reversed { direction } => format!("direction is now {:?}", direction),
+        GameEvent::Reshuffled => "the discard pile is shuffled back into the deck".to_string(),
+        GameEvent::Won { seat } => format!("player {} wins!", seat + 1),
+        GameEvent::Scored { seat, points } => {
+            format!("player {} scores {} points", seat + 1, points)
+        }
+        GameEvent::Dealt { round } => format!("round {} is dealt", round + 1),
+        GameEvent::UnoCalled { seat } => format!("player {} calls uno!", seat + 1),
+        GameEvent::UnoCaught { seat, by } => format!(
+            "player {} forgot to call uno and is caught by player {}",
+            seat + 1,
+            by + 1
+        ),
+        GameEvent::Passed { seat } => format!("player {} keeps the card and passes", seat + 1),
+        GameEvent::JumpedIn { seat } => format!("player {} jumps in!", seat + 1),
+        GameEvent::HandsSwapped { seat, target } => {
+            format!("player {} swaps hands with player {}", seat + 1, target + 1)
+        }
+        GameEvent::Discarded { seat, count } => {
+            format!("player {} discards {} more cards", seat + 1, count)
+        }
+        GameEvent::Flipped { side } => format!("every card is flipped to the {:?} side", side),
+        GameEvent::HandsRotated { direction } => {
+            format!("every hand is passed along {:?}", direction)
+        }
+        GameEvent::Challenged { seat, by, bluffed } => format!(
+            "player {} challenges the +4 of player {}: {}",
+            by + 1,
+            seat + 1,
+            if *bluffed {
+                "it was a bluff"
+            } else {
+                "it was fair"
+            }
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CardVariant;
+
+    mod card_label {
+        use super::*;
+
+        #[test]
+        fn uses_card_names() {
+            assert_eq!(
+                card_label((CardColor::Blue, CardVariant::PlusTwo)),
+                "\x1b[34mblue 2plus\x1b[0m".to_string()
+            );
+        }
+    }
+
+    mod parse_move {
+        use super::*;
+
+        #[test]
+        fn draw() {
+            assert_eq!(parse_move("d"), Ok(Action::Draw));
+            assert_eq!(parse_move(" draw \n"), Ok(Action::Draw));
+            assert_eq!(parse_move("p"), Ok(Action::Pass));
+        }
+
+        #[test]
+        fn challenge() {
+            assert_eq!(parse_move("c"), Ok(Action::ChallengePlusFour));
+        }
+
+        #[test]
+        fn swap_with_player_number() {
+            assert_eq!(parse_move("s 2"), Ok(Action::SwapHands { target: 1 }));
+            assert!(parse_move("swap 0").is_err());
+        }
+
+        #[test]
+        fn play_with_color() {
+            assert_eq!(
+                parse_move("2"),
+                Ok(Action::Play {
+                    card: 2,
+                    color: None
+                })
+            );
+            assert_eq!(
+                parse_move("0 Green"),
+                Ok(Action::Play {
+                    card: 0,
+                    color: Some(CardColor::Green)
+                })
+            );
+            assert_eq!(
+                parse_move("1 purple"),
+                Ok(Action::Play {
+                    card: 1,
+                    color: Some(CardColor::Purple)
+                })
+            );
+        }
+
+        #[test]
+        fn invalid_input() {
+            assert!(parse_move("").is_err());
+            assert!(parse_move("play").is_err());
+            assert!(parse_move("1 brown").is_err());
+        }
+    }
+
+    mod render_table {
+        use super::*;
+
+        #[test]
+        fn hides_opponent_hands() {
+            let state = GameState::from_seed(3, 0);
+
+            let rendered = render_table(&state, 0);
+
+            assert!(rendered.contains("player 2: 7 cards"));
+            assert!(rendered.contains("player 3: 7 cards"));
+            assert_eq!(rendered.lines().count(), 1 + 2 + 1 + state.hands[0].len());
+        }
+    }
+}
@@ -0,0 +1,6 @@
+#![allow(clippy::type_complexity)]
+
+pub mod features;
+pub mod utils;
+
+pub use features::cards::{CardBundle, CardColor, CardVariant};
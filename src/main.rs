@@ -1,67 +1,54 @@
-#![allow(clippy::type_complexity)]
-use bevy::{prelude::*, window::*};
+use bevy::prelude::*;
 
-mod features;
-mod utils;
-
-use features::{
-    cards::{CardBundle, CardColor, CardVariant},
-    deck::DeckPlugin,
+use card_game::{
+    features::{
+        ai::BotPlugin,
+        deck::DeckPlugin,
+        game::{layout, GameConfig, GamePlugin},
+        lobby::ui::LobbyPlugin,
+        network::client::{ClientPlugin, ServerAddress},
+        replay::{viewer::ReplayViewer, ReplayPlugin},
+        save::ui::SavePlugin,
+        settings::{DisplaySettings, SettingsPlugin},
+    },
+    utils::{assets::AssetsPlugin, mouse, tooltip},
 };
-use utils::mouse;
 
 fn main() {
-    App::default()
-        .add_plugins(DefaultPlugins.set(WindowPlugin {
-            primary_window: Some(Window {
-                mode: WindowMode::SizedFullscreen,
-                ..default()
-            }),
-            ..default()
-        }))
-        .add_plugins(mouse::MousePlugins)
-        .add_plugins(DeckPlugin)
-        .add_systems(Startup, setup)
-        .run();
-}
+    let mut app = App::default();
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.spawn(Camera2dBundle::default());
+    if let Some(address) = ServerAddress::from_args(std::env::args()) {
+        app.insert_resource(address);
+    }
 
-    for (color, variant, transform) in [
-        (
-            CardColor::Blue,
-            CardVariant::Number(9),
-            Transform::from_xyz(-300., 0., 0.),
-        ),
-        (
-            CardColor::Wild,
-            CardVariant::Wild,
-            Transform::from_xyz(-100., 0., 0.),
-        ),
-        (
-            CardColor::Wild,
-            CardVariant::PlusFour,
-            Transform::from_xyz(100., 0., 0.),
-        ),
-        (
-            CardColor::Yellow,
-            CardVariant::Invert,
-            Transform::from_xyz(300., 0., 0.),
-        ),
-    ] {
-        let texture_path = format!("assets/{}", CardBundle::texture_path(color, variant));
+    let display = DisplaySettings::load();
 
-        let texture = asset_server.load(texture_path);
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            mode: display.mode.into(),
+            ..default()
+        }),
+        ..default()
+    }))
+    .insert_resource(display)
+    .add_plugins(SettingsPlugin)
+    .add_plugins(AssetsPlugin)
+    .add_plugins(mouse::MousePlugins)
+    .add_plugins(tooltip::TooltipPlugin)
+    .add_plugins(DeckPlugin)
+    .insert_resource(GameConfig::from_args(std::env::args()))
+    .add_plugins((GamePlugin, BotPlugin, ClientPlugin, LobbyPlugin, SavePlugin))
+    .add_plugins(ReplayPlugin)
+    .add_systems(Startup, setup);
 
-        commands.spawn((
-            CardBundle { color, variant },
-            SpriteBundle {
-                texture,
-                transform,
-                ..default()
-            },
-            mouse::MouseInteractionBundle::default(),
-        ));
+    // Read after the plugins so a replay that can't be opened gets logged
+    if let Some(viewer) = ReplayViewer::from_args(std::env::args()) {
+        app.insert_resource(viewer);
     }
+
+    app.run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(layout::table_camera());
 }
@@ -1,5 +1,6 @@
 pub mod assets;
 pub mod mouse;
+pub mod tooltip;
 
 #[cfg(test)]
 pub mod test;
@@ -3,7 +3,7 @@ use bevy::{
     prelude::*,
 };
 
-use super::Hovered;
+use super::{hover, Hovered};
 
 #[derive(Component, Debug, Default)]
 pub struct Clickable;
@@ -27,15 +27,9 @@ fn gizmo(
 ) {
     for (image, transform) in hoverables_query.iter() {
         if let Some(image) = assets.get(image) {
-            let width = image.width() as f32 + 2.;
-            let height = image.height() as f32 + 2.;
-
-            gizmos.rect_2d(
-                transform.translation.truncate(),
-                transform.rotation.z,
-                Vec2::new(width, height),
-                Color::BLUE,
-            );
+            let (position, angle, size) = hover::outline(transform, image.size_f32());
+
+            gizmos.rect_2d(position, angle, size, Color::BLUE);
         }
     }
 }
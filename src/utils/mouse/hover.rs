@@ -26,19 +26,34 @@ fn gizmo(
 ) {
     for (image, transform) in hoverables_query.iter() {
         if let Some(image) = assets.get(image) {
-            let width = image.width() as f32 + 2.;
-            let height = image.height() as f32 + 2.;
-
-            gizmos.rect_2d(
-                transform.translation.truncate(),
-                transform.rotation.z,
-                Vec2::new(width, height),
-                Color::GREEN,
-            );
+            let (position, angle, size) = outline(transform, image.size_f32());
+
+            gizmos.rect_2d(position, angle, size, Color::GREEN);
         }
     }
 }
 
+/// Center, angle and size of a rectangle drawn just around an image of `size` placed with `transform`
+pub(super) fn outline(transform: &Transform, size: Vec2) -> (Vec2, f32, Vec2) {
+    let (angle, _, _) = transform.rotation.to_euler(EulerRot::ZYX);
+
+    (
+        transform.translation.truncate(),
+        angle,
+        size * transform.scale.truncate() + 2.,
+    )
+}
+
+/// Whether `point` is on an image of `size` placed with `transform`, whatever its scale and rotation
+pub fn covers(transform: &Transform, size: Vec2, point: Vec2) -> bool {
+    let local = transform
+        .compute_affine()
+        .inverse()
+        .transform_point3(point.extend(transform.translation.z));
+
+    local.truncate().abs().cmple(size / 2.).all()
+}
+
 fn is_hovered(
     hoverables_query: Query<
         (Entity, &Handle<Image>, &Transform),
@@ -50,15 +65,8 @@ fn is_hovered(
 ) {
     for (entity, image, transform) in hoverables_query.iter() {
         let image = images.get(image).unwrap();
-        let half_width = image.width() as f32 / 2.;
-        let half_height = image.height() as f32 / 2.;
-
-        let min_x = transform.translation.x - half_width;
-        let max_x = transform.translation.x + half_width;
-        let min_y = transform.translation.y - half_height;
-        let max_y = transform.translation.y + half_height;
 
-        if mouse.0.x >= min_x && mouse.0.x <= max_x && mouse.0.y >= min_y && mouse.0.y <= max_y {
+        if covers(transform, image.size_f32(), mouse.0) {
             commands.entity(entity).insert(Hovered);
         } else {
             commands.entity(entity).remove::<Hovered>();
@@ -72,6 +80,31 @@ mod tests {
     use crate::utils::test::asset_loading::{check_loaded, TestAssetLoadingState};
     use crate::utils::test::test_plugins::TestPlugin;
 
+    mod covers {
+        use super::*;
+
+        const SIZE: Vec2 = Vec2::new(100., 200.);
+
+        #[test]
+        fn follows_the_scale() {
+            let transform = Transform::from_xyz(50., 0., 1.).with_scale(Vec3::splat(0.5));
+
+            assert!(covers(&transform, SIZE, Vec2::new(70., 40.)));
+            assert!(!covers(&transform, SIZE, Vec2::new(80., 0.)));
+            assert!(!covers(&transform, SIZE, Vec2::new(50., 60.)));
+        }
+
+        #[test]
+        fn follows_the_rotation() {
+            let transform =
+                Transform::from_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+
+            // Lying on its side the image is wider than it is high
+            assert!(covers(&transform, SIZE, Vec2::new(90., 0.)));
+            assert!(!covers(&transform, SIZE, Vec2::new(0., 90.)));
+        }
+    }
+
     mod is_hovered {
         use crate::{features::deck::CARD_BACK_PATH, utils::assets::loader::is_asset_loaded};
 
@@ -15,8 +15,7 @@ pub fn check_loaded<T: Asset>(
 ) {
     if handles_query
         .iter()
-        .map(|handle| assets.load_state(handle) == LoadState::Loaded)
-        .all(|is_loaded| is_loaded)
+        .all(|handle| assets.load_state(handle) == LoadState::Loaded)
     {
         loading_state.set(TestAssetLoadingState::Loaded);
     }
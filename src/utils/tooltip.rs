@@ -0,0 +1,352 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use super::mouse::{coordinates::UIMouseCoordinates, Hovered};
+
+/// Builds the text shown by a [`Tooltip`] from the hovered entity and the current world
+pub type TooltipTextProvider = Box<dyn Fn(Entity, &World) -> String + Send + Sync>;
+
+/// Attach to any `Hoverable` entity to show a mouse-following tooltip while it is hovered
+#[derive(Component)]
+pub struct Tooltip(TooltipTextProvider);
+
+impl Tooltip {
+    pub fn new(provider: impl Fn(Entity, &World) -> String + Send + Sync + 'static) -> Self {
+        Self(Box::new(provider))
+    }
+
+    /// Tooltip with a text that never changes
+    pub fn from_text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        Self::new(move |_, _| text.clone())
+    }
+
+    pub fn text(&self, entity: Entity, world: &World) -> String {
+        (self.0)(entity, world)
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct TooltipSettings {
+    /// How long an entity must stay hovered before its tooltip appears
+    pub delay: Duration,
+    /// Horizontal distance between the mouse and the tooltip
+    pub offset: f32,
+}
+
+impl Default for TooltipSettings {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(300),
+            offset: 15.,
+        }
+    }
+}
+
+/// Which tooltip entity is hovered and for how long
+#[derive(Resource, Debug, Default)]
+struct TooltipState {
+    target: Option<Entity>,
+    hovered_for: Duration,
+}
+
+impl TooltipState {
+    fn is_shown(&self, settings: &TooltipSettings) -> bool {
+        self.target.is_some() && self.hovered_for >= settings.delay
+    }
+}
+
+#[derive(Component)]
+struct TooltipNodeMarker;
+
+#[derive(Component)]
+struct TooltipTextMarker;
+
+pub struct TooltipPlugin;
+
+/// This plugin shows the text of a hovered [`Tooltip`] entity next to the mouse after [`TooltipSettings::delay`]
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, spawn_tooltip_node)
+            .add_systems(
+                Update,
+                (track_hovered_tooltip, update_tooltip_text, position_tooltip).chain(),
+            )
+            .init_resource::<TooltipSettings>()
+            .init_resource::<TooltipState>();
+    }
+}
+
+/// Spawn the hidden UI node used to display the tooltip text
+fn spawn_tooltip_node(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: BackgroundColor(Color::DARK_GRAY),
+                border_color: BorderColor(Color::BLACK),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(i32::MAX),
+                ..default()
+            },
+            TooltipNodeMarker,
+        ))
+        .with_children(|builder| {
+            builder.spawn((TextBundle::default(), TooltipTextMarker));
+        });
+}
+
+/// Keep track of the hovered tooltip entity, restarting the delay whenever the target changes
+fn track_hovered_tooltip(
+    hovered_query: Query<Entity, (With<Tooltip>, With<Hovered>)>,
+    mut state: ResMut<TooltipState>,
+    time: Res<Time>,
+) {
+    let hovered = hovered_query.iter().next();
+
+    if hovered.is_some() && hovered == state.target {
+        state.hovered_for += time.delta();
+    } else {
+        state.target = hovered;
+        state.hovered_for = Duration::ZERO;
+    }
+}
+
+/// Show the tooltip node with the target text once the delay is elapsed, hide it otherwise
+fn update_tooltip_text(world: &mut World) {
+    let state = world.resource::<TooltipState>();
+    let text = state
+        .target
+        .filter(|_| state.is_shown(world.resource::<TooltipSettings>()))
+        .and_then(|target| {
+            world
+                .get::<Tooltip>(target)
+                .map(|tooltip| tooltip.text(target, world))
+        });
+
+    let mut text_query = world.query_filtered::<&mut Text, With<TooltipTextMarker>>();
+    if let (Some(text), Ok(mut text_component)) = (&text, text_query.get_single_mut(world)) {
+        *text_component = Text::from_section(text.clone(), TextStyle::default());
+    }
+
+    let mut node_query = world.query_filtered::<&mut Visibility, With<TooltipNodeMarker>>();
+    if let Ok(mut visibility) = node_query.get_single_mut(world) {
+        *visibility = match text {
+            Some(_) => Visibility::Visible,
+            None => Visibility::Hidden,
+        };
+    }
+}
+
+/// Move the tooltip node next to the mouse, keeping it inside the window
+fn position_tooltip(
+    mut node_query: Query<(&mut Style, &Node), With<TooltipNodeMarker>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    ui_mouse_coordinates: Res<UIMouseCoordinates>,
+    settings: Res<TooltipSettings>,
+    ui_scale: Option<Res<UiScale>>,
+) {
+    let Ok((mut style, node)) = node_query.get_single_mut() else {
+        return;
+    };
+
+    let mouse = ui_mouse_coordinates.0 + Vec2::new(settings.offset, 0.);
+    let Vec2 { x, y } = match window_query.get_single() {
+        Ok(window) => clamp_to_window(
+            mouse,
+            node.size(),
+            Vec2::new(window.width(), window.height()),
+        ),
+        Err(_) => mouse,
+    };
+
+    // Node sizes are in window pixels, styles get scaled by the UI scale
+    let scale = ui_scale.map_or(1., |ui_scale| ui_scale.0);
+    style.left = Val::Px(x / scale);
+    style.top = Val::Px(y / scale);
+}
+
+/// Top left corner for a node of `size` placed at `position` so that it fits in `window_size`
+fn clamp_to_window(position: Vec2, size: Vec2, window_size: Vec2) -> Vec2 {
+    position.min(window_size - size).max(Vec2::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_app(delay: Duration) -> (App, Entity, Entity) {
+        let mut app = App::new();
+
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(TooltipPlugin)
+            .insert_resource(TooltipSettings { delay, ..default() })
+            .init_resource::<UIMouseCoordinates>();
+
+        app.update();
+
+        let node = app
+            .world
+            .query_filtered::<Entity, With<TooltipNodeMarker>>()
+            .single(&app.world);
+        let text = app
+            .world
+            .query_filtered::<Entity, With<TooltipTextMarker>>()
+            .single(&app.world);
+
+        (app, node, text)
+    }
+
+    fn tooltip_text(app: &App, text: Entity) -> String {
+        app.world
+            .entity(text)
+            .get::<Text>()
+            .and_then(|text| text.sections.first().map(|section| section.value.clone()))
+            .unwrap_or_default()
+    }
+
+    mod update_tooltip_text {
+        use super::*;
+
+        #[test]
+        fn hovered_shows_provider_text() {
+            let (mut app, node, text) = setup_app(Duration::ZERO);
+
+            let hovered = app
+                .world
+                .spawn((Hovered, Tooltip::new(|entity, _| format!("{:?}", entity))))
+                .id();
+
+            app.update();
+
+            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
+
+            assert_eq!(visibility, Visibility::Visible);
+            assert_eq!(tooltip_text(&app, text), format!("{:?}", hovered));
+        }
+
+        #[test]
+        fn provider_reads_world() {
+            let (mut app, _, text) = setup_app(Duration::ZERO);
+
+            #[derive(Component)]
+            struct Counted;
+
+            app.world.spawn_batch([Counted, Counted, Counted]);
+            app.world.spawn((
+                Hovered,
+                Tooltip::new(|_, world| {
+                    let len = world
+                        .iter_entities()
+                        .filter(|entity| entity.contains::<Counted>())
+                        .count();
+                    format!("counted: {}", len)
+                }),
+            ));
+
+            app.update();
+
+            assert_eq!(tooltip_text(&app, text), "counted: 3".to_string());
+        }
+
+        #[test]
+        fn not_hovered_stays_hidden() {
+            let (mut app, node, _) = setup_app(Duration::ZERO);
+
+            app.world.spawn(Tooltip::from_text("hidden"));
+
+            app.update();
+
+            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
+
+            assert_eq!(visibility, Visibility::Hidden);
+        }
+
+        #[test]
+        fn hidden_until_delay_elapsed() {
+            let (mut app, node, _) = setup_app(Duration::from_secs(3600));
+
+            app.world.spawn((Hovered, Tooltip::from_text("later")));
+
+            app.update();
+            app.update();
+
+            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
+
+            assert_eq!(visibility, Visibility::Hidden);
+        }
+
+        #[test]
+        fn hidden_when_hover_ends() {
+            let (mut app, node, _) = setup_app(Duration::ZERO);
+
+            let hovered = app.world.spawn((Hovered, Tooltip::from_text("bye"))).id();
+
+            app.update();
+            app.world.entity_mut(hovered).remove::<Hovered>();
+            app.update();
+
+            let visibility = app.world.entity(node).get::<Visibility>().unwrap();
+
+            assert_eq!(visibility, Visibility::Hidden);
+        }
+    }
+
+    mod position_tooltip {
+        use super::*;
+
+        #[test]
+        fn follows_mouse_with_offset() {
+            let (mut app, node, _) = setup_app(Duration::ZERO);
+
+            app.world.resource_mut::<UIMouseCoordinates>().0 = Vec2::new(40., 20.);
+
+            app.update();
+
+            let style = app.world.entity(node).get::<Style>().unwrap();
+            let offset = TooltipSettings::default().offset;
+
+            assert_eq!(
+                (style.top, style.left),
+                (Val::Px(20.), Val::Px(40. + offset))
+            );
+        }
+    }
+
+    mod clamp_to_window {
+        use super::*;
+
+        #[test]
+        fn inside_window_unchanged() {
+            let position = clamp_to_window(
+                Vec2::new(10., 10.),
+                Vec2::new(50., 20.),
+                Vec2::new(800., 600.),
+            );
+
+            assert_eq!(position, Vec2::new(10., 10.));
+        }
+
+        #[test]
+        fn clamped_to_bottom_right() {
+            let position = clamp_to_window(
+                Vec2::new(790., 595.),
+                Vec2::new(50., 20.),
+                Vec2::new(800., 600.),
+            );
+
+            assert_eq!(position, Vec2::new(750., 580.));
+        }
+
+        #[test]
+        fn bigger_than_window_sticks_to_top_left() {
+            let position = clamp_to_window(
+                Vec2::new(10., 10.),
+                Vec2::new(900., 700.),
+                Vec2::new(800., 600.),
+            );
+
+            assert_eq!(position, Vec2::ZERO);
+        }
+    }
+}
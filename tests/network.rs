@@ -0,0 +1,615 @@
+use std::{
+    net::TcpStream,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use card_game::features::{
+    ai::Difficulty,
+    game::{AppState, DrawCardRequest, LocalPlayer, Player, TableView},
+    lobby::{CurrentRoom, LobbyError, LobbyNotice, RoomInfo, RoomSettings, SeatInfo},
+    network::{
+        client::{ClientPlugin, ServerAddress, ServerConnection},
+        server::{GracePeriod, Server},
+        ClientMessage, ServerMessage, POLL_INTERVAL,
+    },
+    rules::{
+        projection::{PlayerView, SeenCard},
+        Action, GameState, HouseRules, STARTING_HAND_SIZE,
+    },
+};
+use tungstenite::{stream::MaybeTlsStream, Message, WebSocket};
+
+const SEED: u64 = 11;
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a server on a free localhost port and returns its url
+fn start_server() -> String {
+    start_server_with(|server| server)
+}
+
+fn start_server_with(configure: impl FnOnce(Server) -> Server + Send + 'static) -> String {
+    let (address_sender, address_receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut server = configure(Server::bind("127.0.0.1:0").unwrap());
+        address_sender.send(server.local_addr().unwrap()).unwrap();
+
+        loop {
+            server.poll();
+            thread::sleep(POLL_INTERVAL);
+        }
+    });
+
+    format!("ws://{}", address_receiver.recv().unwrap())
+}
+
+/// A client app without any window or rendering
+fn headless_client(url: &str) -> App {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(ServerAddress(url.to_string()))
+        .add_plugins(ClientPlugin);
+    // Opens the connection
+    app.update();
+
+    app
+}
+
+fn send(app: &App, message: ClientMessage) {
+    app.world.resource::<ServerConnection>().send(message);
+}
+
+fn room(app: &App) -> Option<RoomInfo> {
+    app.world.resource::<CurrentRoom>().0.clone()
+}
+
+fn local_seat(app: &mut App) -> Option<usize> {
+    app.world
+        .query_filtered::<&Player, With<LocalPlayer>>()
+        .get_single(&app.world)
+        .ok()
+        .map(|player| player.seat)
+}
+
+fn table_view(app: &App) -> Option<PlayerView> {
+    app.world
+        .get_resource::<TableView>()
+        .map(|table_view| table_view.0.clone())
+}
+
+/// Update every app until `condition` holds
+fn update_until(apps: &mut [App], condition: impl Fn(&mut [App]) -> bool) {
+    let start = Instant::now();
+
+    while !condition(apps) {
+        assert!(
+            start.elapsed() < TIMEOUT,
+            "timed out waiting for the server"
+        );
+
+        apps.iter_mut().for_each(App::update);
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The first app hosts a room with `seats`, `None` being human seats taken by the apps in order,
+/// then everyone gets ready and the game starts. Returns the room code
+fn start_room(apps: &mut [App], seats: &[Option<Difficulty>]) -> String {
+    send(&apps[0], ClientMessage::CreateRoom);
+    update_until(apps, |apps| room(&apps[0]).is_some());
+
+    let settings = RoomSettings {
+        seats: seats.len(),
+        seed: Some(SEED),
+        ..RoomSettings::default()
+    };
+    send(&apps[0], ClientMessage::ChangeSettings(settings));
+    for (seat, bot) in seats.iter().enumerate() {
+        if let Some(difficulty) = bot {
+            let difficulty = Some(*difficulty);
+            send(&apps[0], ClientMessage::SetBot { seat, difficulty });
+        }
+    }
+    let code = room(&apps[0]).unwrap().code;
+
+    let human_seats = seats
+        .iter()
+        .enumerate()
+        .filter(|(_, bot)| bot.is_none())
+        .map(|(seat, _)| seat);
+    for (guest, seat) in human_seats.enumerate().skip(1) {
+        send(&apps[guest], ClientMessage::JoinRoom(code.clone()));
+        send(&apps[guest], ClientMessage::TakeSeat(seat));
+        update_until(apps, |apps| {
+            room(&apps[guest]).is_some_and(|room| room.seat == Some(seat))
+        });
+    }
+
+    for app in apps.iter() {
+        send(app, ClientMessage::Ready(true));
+    }
+    update_until(apps, |apps| {
+        room(&apps[0]).is_some_and(|room| {
+            room.seats.len() == seats.len()
+                && room.seats.iter().all(|seat| match seat {
+                    SeatInfo::Human { ready, .. } => *ready,
+                    _ => true,
+                })
+        })
+    });
+
+    send(&apps[0], ClientMessage::Start);
+    update_until(apps, |apps| {
+        apps.iter_mut().all(|app| local_seat(app).is_some())
+    });
+
+    code
+}
+
+/// A client speaking the protocol directly, to see exactly what the server sends
+struct RawClient {
+    socket: WebSocket<MaybeTlsStream<TcpStream>>,
+    token: String,
+}
+
+impl RawClient {
+    fn connect(url: &str) -> Self {
+        let (socket, _) = tungstenite::connect(url).unwrap();
+        if let MaybeTlsStream::Plain(stream) = socket.get_ref() {
+            stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+        }
+        let mut client = Self {
+            socket,
+            token: String::new(),
+        };
+
+        match serde_json::from_str(&client.receive_text()).unwrap() {
+            ServerMessage::Session(token) => client.token = token,
+            message => panic!("expected a session first, got {:?}", message),
+        }
+        client
+    }
+
+    fn send(&mut self, message: &ClientMessage) {
+        let text = serde_json::to_string(message).unwrap();
+        self.socket.send(Message::Text(text)).unwrap();
+    }
+
+    fn receive_text(&mut self) -> String {
+        self.socket.read().unwrap().into_text().unwrap()
+    }
+
+    /// Next message, session tokens are kept like real clients do
+    fn receive(&mut self) -> ServerMessage {
+        loop {
+            match serde_json::from_str(&self.receive_text()).unwrap() {
+                ServerMessage::Session(token) => self.token = token,
+                message => return message,
+            }
+        }
+    }
+
+    /// Skip messages until `pick` keeps one
+    fn receive_until<T>(&mut self, pick: impl Fn(ServerMessage) -> Option<T>) -> T {
+        loop {
+            if let Some(picked) = pick(self.receive()) {
+                return picked;
+            }
+        }
+    }
+}
+
+/// `host` starts a game with one seat per guest after its own and a last seat for `bot` if any,
+/// returns the room code
+fn raw_room(
+    host: &mut RawClient,
+    guests: &mut [&mut RawClient],
+    bot: Option<Difficulty>,
+) -> String {
+    let seats = 1 + guests.len() + usize::from(bot.is_some());
+
+    host.send(&ClientMessage::CreateRoom);
+    let code = host.receive_until(|message| match message {
+        ServerMessage::Room(room) => Some(room.code),
+        _ => None,
+    });
+    host.send(&ClientMessage::ChangeSettings(RoomSettings {
+        seats,
+        seed: Some(SEED),
+        ..RoomSettings::default()
+    }));
+    if let Some(difficulty) = bot {
+        host.send(&ClientMessage::SetBot {
+            seat: seats - 1,
+            difficulty: Some(difficulty),
+        });
+    }
+
+    for (index, guest) in guests.iter_mut().enumerate() {
+        guest.send(&ClientMessage::JoinRoom(code.clone()));
+        guest.send(&ClientMessage::TakeSeat(index + 1));
+        guest.send(&ClientMessage::Ready(true));
+    }
+    host.send(&ClientMessage::Ready(true));
+    host.receive_until(|message| match message {
+        ServerMessage::Room(room) => room
+            .seats
+            .iter()
+            .all(|seat| matches!(seat, SeatInfo::Human { ready: true, .. } | SeatInfo::Bot(_)))
+            .then_some(()),
+        _ => None,
+    });
+    host.send(&ClientMessage::Start);
+
+    code
+}
+
+#[test]
+fn clients_get_their_own_seat() {
+    let url = start_server();
+    let mut apps = [headless_client(&url), headless_client(&url)];
+
+    start_room(&mut apps, &[None, None]);
+    // States change at the start of the next frame
+    apps.iter_mut().for_each(App::update);
+
+    for (seat, app) in apps.iter_mut().enumerate() {
+        assert_eq!(local_seat(app), Some(seat));
+        assert_eq!(
+            table_view(app),
+            Some(GameState::from_seed(2, SEED).view_for(Some(seat)))
+        );
+        assert_eq!(
+            app.world.resource::<State<AppState>>().get(),
+            &AppState::Playing
+        );
+    }
+}
+
+#[test]
+fn actions_are_applied_by_every_client() {
+    let url = start_server();
+    let mut apps = [headless_client(&url), headless_client(&url)];
+
+    start_room(&mut apps, &[None, Some(Difficulty::Easy), None]);
+    apps[0].world.send_event(DrawCardRequest { seat: 0 });
+
+    let mut expected = GameState::from_seed(3, SEED);
+    expected.apply(0, Action::Draw).unwrap();
+
+    // The bot plays right after the draw
+    update_until(&mut apps, |apps| {
+        apps.iter().all(|app| {
+            table_view(app).is_some_and(|view| {
+                view.hands[0].len() == STARTING_HAND_SIZE + 1 && view.current != 1
+            })
+        })
+    });
+
+    let (first_view, second_view) = (table_view(&apps[0]).unwrap(), table_view(&apps[1]).unwrap());
+
+    assert_eq!(first_view.discard_pile, second_view.discard_pile);
+    assert_eq!(first_view.current, second_view.current);
+    assert_eq!(first_view.hand(0), expected.hands[0]);
+    assert_eq!(
+        second_view.hands[0],
+        vec![SeenCard::FaceDown; STARTING_HAND_SIZE + 1]
+    );
+}
+
+#[test]
+fn out_of_turn_requests_are_ignored() {
+    let url = start_server();
+    let mut apps = [headless_client(&url), headless_client(&url)];
+
+    start_room(&mut apps, &[None, None]);
+    apps[1].world.send_event(DrawCardRequest { seat: 1 });
+
+    for _ in 0..20 {
+        apps.iter_mut().for_each(App::update);
+        thread::sleep(POLL_INTERVAL);
+    }
+
+    for app in apps.iter() {
+        assert_eq!(table_view(app).unwrap().current, 0);
+        assert_eq!(
+            table_view(app).unwrap().discard_pile,
+            GameState::from_seed(2, SEED).discard_pile
+        );
+    }
+}
+
+#[test]
+fn started_rooms_turn_away_new_clients() {
+    let url = start_server();
+    let mut apps = [headless_client(&url)];
+
+    let code = start_room(&mut apps, &[None, Some(Difficulty::Easy)]);
+
+    let mut late = [headless_client(&url)];
+    send(&late[0], ClientMessage::JoinRoom(code));
+    update_until(&mut late, |late| {
+        late[0].world.resource::<LobbyNotice>().0.is_some()
+    });
+
+    assert_eq!(
+        late[0].world.resource::<LobbyNotice>().0,
+        Some(LobbyError::AlreadyStarted.to_string())
+    );
+    assert_eq!(room(&late[0]), None);
+    assert_eq!(local_seat(&mut late[0]), None);
+    assert_eq!(table_view(&late[0]), None);
+}
+
+#[test]
+fn silent_connections_hold_no_one_up() {
+    let url = start_server();
+    let address = url.trim_start_matches("ws://");
+    // Connections never sending their handshake
+    let _silent = (0..5)
+        .map(|_| TcpStream::connect(address).unwrap())
+        .collect::<Vec<_>>();
+
+    let start = Instant::now();
+    let mut host = RawClient::connect(&url);
+    host.send(&ClientMessage::CreateRoom);
+    host.receive_until(|message| match message {
+        ServerMessage::Room(room) => Some(room),
+        _ => None,
+    });
+
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn unknown_room_codes_are_rejected() {
+    let url = start_server();
+    let mut apps = [headless_client(&url)];
+
+    send(&apps[0], ClientMessage::JoinRoom("ZZZZ".to_string()));
+    update_until(&mut apps, |apps| {
+        apps[0].world.resource::<LobbyNotice>().0.is_some()
+    });
+
+    assert_eq!(
+        apps[0].world.resource::<LobbyNotice>().0,
+        Some(LobbyError::NoSuchRoom("ZZZZ".to_string()).to_string())
+    );
+}
+
+#[test]
+fn hidden_cards_never_sent() {
+    let url = start_server();
+    let mut client = RawClient::connect(&url);
+    raw_room(&mut client, &mut [], Some(Difficulty::Hard));
+
+    let mut received = Vec::new();
+    while received.len() < 2 {
+        let text = client.receive_text();
+        let view = match serde_json::from_str(&text).unwrap() {
+            ServerMessage::Room(_) => continue,
+            ServerMessage::Welcome { view, .. } | ServerMessage::Updated { view, .. } => view,
+            message => panic!("unexpected message: {:?}", message),
+        };
+        client.send(&ClientMessage::Act(Action::Draw));
+        received.push((text, view));
+    }
+
+    for (text, view) in received {
+        let face_up = text.matches("FaceUp").count();
+
+        assert_eq!(
+            face_up,
+            view.hands[0].len(),
+            "only seat 0 cards in {}",
+            text
+        );
+        assert!(view.hands[1].iter().all(|card| *card == SeenCard::FaceDown));
+        assert!(view
+            .draw_pile
+            .iter()
+            .all(|card| *card == SeenCard::FaceDown));
+    }
+}
+
+#[test]
+fn resumed_sessions_get_a_snapshot() {
+    let url = start_server_with(|server| {
+        server.with_grace_period(GracePeriod {
+            stand_in: None,
+            ..GracePeriod::default()
+        })
+    });
+    let mut client = RawClient::connect(&url);
+    raw_room(&mut client, &mut [], Some(Difficulty::Easy));
+    client.receive_until(|message| match message {
+        ServerMessage::Welcome { .. } => Some(()),
+        _ => None,
+    });
+    let token = client.token.clone();
+    drop(client);
+
+    let mut resumed = RawClient::connect(&url);
+    resumed.send(&ClientMessage::Resume(token.clone()));
+    let (seat, view) = resumed.receive_until(|message| match message {
+        ServerMessage::Welcome { seat, view } => Some((seat, view)),
+        _ => None,
+    });
+
+    assert_eq!(resumed.token, token);
+    assert_eq!(seat, Some(0));
+    assert_eq!(view, GameState::from_seed(2, SEED).view_for(Some(0)));
+}
+
+#[test]
+fn stand_in_plays_while_away() {
+    let url = start_server();
+    let mut host = RawClient::connect(&url);
+    let mut guest = RawClient::connect(&url);
+    raw_room(&mut host, &mut [&mut guest], None);
+    guest.receive_until(|message| match message {
+        ServerMessage::Welcome { .. } => Some(()),
+        _ => None,
+    });
+    let token = host.token.clone();
+    drop(host);
+
+    let guest_view = guest.receive_until(|message| match message {
+        ServerMessage::Updated { view, .. } if view.current == 1 => Some(view),
+        _ => None,
+    });
+    let mut resumed = RawClient::connect(&url);
+    resumed.send(&ClientMessage::Resume(token));
+    let (seat, view) = resumed.receive_until(|message| match message {
+        ServerMessage::Welcome { seat, view } => Some((seat, view)),
+        _ => None,
+    });
+
+    assert_eq!(seat, Some(0));
+    assert_eq!(view.current, 1);
+    assert_eq!(view.discard_pile, guest_view.discard_pile);
+    assert_eq!(view.hands[0].len(), guest_view.hands[0].len());
+}
+
+#[test]
+fn expired_sessions_are_rejected() {
+    let url = start_server_with(|server| {
+        server.with_grace_period(GracePeriod {
+            duration: Duration::ZERO,
+            ..GracePeriod::default()
+        })
+    });
+    let mut client = RawClient::connect(&url);
+    raw_room(&mut client, &mut [], Some(Difficulty::Easy));
+    let token = client.token.clone();
+    drop(client);
+    thread::sleep(POLL_INTERVAL * 10);
+
+    let mut resumed = RawClient::connect(&url);
+    resumed.send(&ClientMessage::Resume(token));
+
+    assert_eq!(resumed.receive(), ServerMessage::Left);
+    assert_eq!(
+        resumed.receive(),
+        ServerMessage::Rejected(LobbyError::SessionExpired.to_string())
+    );
+}
+
+#[test]
+fn spectators_see_no_hand() {
+    let url = start_server();
+    let mut host = RawClient::connect(&url);
+    let code = raw_room(&mut host, &mut [], Some(Difficulty::Easy));
+    host.receive_until(|message| match message {
+        ServerMessage::Welcome { .. } => Some(()),
+        _ => None,
+    });
+
+    let mut spectator = RawClient::connect(&url);
+    spectator.send(&ClientMessage::Spectate {
+        code: code.to_lowercase(),
+        god_view: false,
+    });
+    let (seat, view) = spectator.receive_until(|message| match message {
+        ServerMessage::Welcome { seat, view } => Some((seat, view)),
+        _ => None,
+    });
+
+    assert_eq!(seat, None);
+    assert_eq!(view, GameState::from_seed(2, SEED).view_for(None));
+
+    host.send(&ClientMessage::Act(Action::Draw));
+    let view = spectator.receive_until(|message| match message {
+        ServerMessage::Updated { view, .. } => Some(view),
+        _ => None,
+    });
+
+    assert_eq!(
+        view.hands[0],
+        vec![SeenCard::FaceDown; STARTING_HAND_SIZE + 1]
+    );
+}
+
+#[test]
+fn god_view_is_delayed() {
+    const DELAY: Duration = Duration::from_millis(300);
+    let url = start_server_with(|server| server.with_god_view_delay(DELAY));
+    let mut host = RawClient::connect(&url);
+    let mut god_viewer = RawClient::connect(&url);
+    let code = raw_room(&mut host, &mut [], Some(Difficulty::Easy));
+    host.receive_until(|message| match message {
+        ServerMessage::Welcome { .. } => Some(()),
+        _ => None,
+    });
+
+    god_viewer.send(&ClientMessage::Spectate {
+        code,
+        god_view: true,
+    });
+    let view = god_viewer.receive_until(|message| match message {
+        ServerMessage::Welcome { view, .. } => Some(view),
+        _ => None,
+    });
+    assert!(view
+        .hands
+        .iter()
+        .flatten()
+        .all(|card| *card == SeenCard::FaceDown));
+
+    let drawn_at = Instant::now();
+    host.send(&ClientMessage::Act(Action::Draw));
+    let view = god_viewer.receive_until(|message| match message {
+        ServerMessage::Updated { events, view } if !events.is_empty() => Some(view),
+        _ => None,
+    });
+
+    assert!(drawn_at.elapsed() >= DELAY);
+    assert_eq!(view.hands[0].len(), STARTING_HAND_SIZE + 1);
+    assert!(view
+        .hands
+        .iter()
+        .flatten()
+        .all(|card| matches!(card, SeenCard::FaceUp(_))));
+    assert!(view
+        .draw_pile
+        .iter()
+        .all(|card| *card == SeenCard::FaceDown));
+}
+
+#[test]
+fn idle_players_lose_their_seat() {
+    let url = start_server();
+    let mut host = RawClient::connect(&url);
+    host.send(&ClientMessage::CreateRoom);
+    host.send(&ClientMessage::ChangeSettings(RoomSettings {
+        seats: 2,
+        seed: Some(SEED),
+        rules: HouseRules {
+            turn_time: Some(0),
+            ..HouseRules::default()
+        },
+        ..RoomSettings::default()
+    }));
+    host.send(&ClientMessage::SetBot {
+        seat: 1,
+        difficulty: Some(Difficulty::Easy),
+    });
+    host.send(&ClientMessage::Ready(true));
+    host.send(&ClientMessage::Start);
+
+    let (seat, _) = host.receive_until(|message| match message {
+        ServerMessage::Welcome { seat, view } => Some((seat, view)),
+        _ => None,
+    });
+    let played_for_host = host.receive_until(|message| match message {
+        ServerMessage::Updated { events, view } if view.viewer.is_none() => Some(events),
+        _ => None,
+    });
+
+    assert_eq!(seat, Some(0));
+    assert!(!played_for_host.is_empty());
+}
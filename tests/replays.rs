@@ -0,0 +1,31 @@
+use std::fs;
+
+use card_game::features::{replay::Replay, save::SaveFormat};
+
+const FIXTURES: &str = "tests/replays";
+
+/// Every recorded game must still play out the same way with the current rules
+#[test]
+fn recorded_games_replay_identically() {
+    let mut fixtures = 0;
+
+    for entry in fs::read_dir(FIXTURES).unwrap() {
+        let path = entry.unwrap().path();
+        let text = fs::read_to_string(&path).unwrap();
+        let replay = Replay::decode(&text, SaveFormat::from_path(&path)).unwrap();
+
+        let states = replay
+            .states()
+            .unwrap_or_else(|error| panic!("{} diverged: {}", path.display(), error));
+
+        assert_eq!(
+            states.last().unwrap().winner,
+            replay.winner(),
+            "{}",
+            path.display()
+        );
+        fixtures += 1;
+    }
+
+    assert!(fixtures > 0, "no replay found in {}", FIXTURES);
+}